@@ -13,8 +13,11 @@ use std::convert::Infallible;
 pub mod rejection;
 
 mod request_parts;
+mod trailers;
 mod tuple;
 
+pub use self::trailers::Trailers;
+
 /// Types that can be created from requests.
 ///
 /// See [`axum::extract`] for more details.
@@ -100,3 +100,14 @@ composite_rejection! {
         InvalidUtf8,
     }
 }
+
+composite_rejection! {
+    /// Rejection used for [`Trailers`](super::Trailers).
+    ///
+    /// Contains one variant for each way the [`Trailers`](super::Trailers)
+    /// extractor can fail.
+    pub enum TrailersRejection {
+        BodyAlreadyExtracted,
+        FailedToBufferBody,
+    }
+}
@@ -1,4 +1,4 @@
-use super::{rejection::*, FromRequest, RequestParts};
+use super::{rejection::*, trailers::BufferedBody, FromRequest, RequestParts};
 use crate::BoxError;
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -92,6 +92,11 @@ where
     type Rejection = BytesRejection;
 
     async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        // the body might have been buffered by `Trailers`
+        if let Some(BufferedBody(bytes)) = req.extensions_mut().remove::<BufferedBody>() {
+            return Ok(bytes);
+        }
+
         let body = take_body(req)?;
 
         let bytes = crate::body::to_bytes(body)
@@ -112,12 +117,11 @@ where
     type Rejection = StringRejection;
 
     async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
-        let body = take_body(req)?;
-
-        let bytes = crate::body::to_bytes(body)
-            .await
-            .map_err(FailedToBufferBody::from_err)?
-            .to_vec();
+        let bytes = match Bytes::from_request(req).await {
+            Ok(bytes) => bytes.to_vec(),
+            Err(BytesRejection::BodyAlreadyExtracted(err)) => return Err(err.into()),
+            Err(BytesRejection::FailedToBufferBody(err)) => return Err(err.into()),
+        };
 
         let string = String::from_utf8(bytes).map_err(InvalidUtf8::from_err)?;
 
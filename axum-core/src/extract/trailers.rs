@@ -0,0 +1,85 @@
+use super::{rejection::*, request_parts::take_body, FromRequest, RequestParts};
+use crate::BoxError;
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::HeaderMap;
+use http_body::Body;
+
+/// Extractor for the trailers of the request body.
+///
+/// Trailers are headers sent after the body, for example gRPC sends the
+/// status of a call in the trailers. They're only available once the whole
+/// body has been received, so this extractor reads the body to the end. If
+/// the request doesn't have trailers an empty [`HeaderMap`] is extracted.
+///
+/// ```rust
+/// use axum::{extract::Trailers, routing::post, Router};
+///
+/// async fn handler(Trailers(trailers): Trailers, body: String) {
+///     if let Some(checksum) = trailers.get("x-checksum") {
+///         // ...
+///     }
+/// }
+///
+/// let app = Router::new().route("/", post(handler));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+///
+/// # Order of extractors
+///
+/// `Trailers` must run before the body is extracted, since other extractors
+/// don't wait for the trailers. It buffers the body itself and extractors that
+/// buffer the body, such as [`Bytes`], [`String`] and `Json`, can still be
+/// used afterwards. Extractors that stream the body, such as `BodyStream`, will
+/// be rejected with [`BodyAlreadyExtracted`].
+///
+/// `Trailers` is rejected with [`BodyAlreadyExtracted`] if it runs after the
+/// body has been extracted.
+#[derive(Debug, Clone, Default)]
+pub struct Trailers(pub HeaderMap);
+
+#[async_trait]
+impl<B> FromRequest<B> for Trailers
+where
+    B: Body + Send,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Rejection = TrailersRejection;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let body = take_body(req)?;
+        let (bytes, trailers) = read_body_with_trailers(body).await?;
+
+        // so extractors that buffer the body can still run after this one
+        req.extensions_mut().insert(BufferedBody(bytes));
+
+        Ok(Self(trailers))
+    }
+}
+
+/// A body that was buffered by [`Trailers`] before it was extracted.
+pub(super) struct BufferedBody(pub(super) Bytes);
+
+/// Read the whole body, followed by its trailers.
+async fn read_body_with_trailers<B>(body: B) -> Result<(Bytes, HeaderMap), FailedToBufferBody>
+where
+    B: Body,
+    B::Error: Into<BoxError>,
+{
+    futures_util::pin_mut!(body);
+
+    let bytes = crate::body::to_bytes(body.as_mut())
+        .await
+        .map_err(FailedToBufferBody::from_err)?;
+
+    let trailers = body
+        .trailers()
+        .await
+        .map_err(FailedToBufferBody::from_err)?
+        .unwrap_or_default();
+
+    Ok((bytes, trailers))
+}
@@ -0,0 +1,69 @@
+use super::{IntoResponse, IntoResponseParts, Response, ResponseParts, TryIntoHeaderError};
+use http::header::{HeaderMap, HeaderName, HeaderValue};
+use std::fmt;
+
+/// Set headers from an iterator of name and value pairs.
+///
+/// This is useful when the headers are computed at runtime. Arrays of headers,
+/// such as `[("content-type", "text/plain")]`, require the number of headers to
+/// be known at compile time.
+///
+/// Headers are set the same way as returning a [`HeaderMap`]: the first value
+/// for each name replaces any existing values, and subsequent values for the
+/// same name are appended. Use [`AppendHeaders`](super::AppendHeaders) to never
+/// override existing headers.
+///
+/// If a name or value is invalid a `500 Internal Server Error` response is
+/// returned instead, with the error in the body. The error is also logged
+/// using [`tracing`] if the `tracing` feature is enabled, which axum's
+/// `tracing-errors` feature does.
+///
+/// ```rust
+/// use axum::response::{Headers, IntoResponse};
+///
+/// async fn handler() -> impl IntoResponse {
+///     let headers = (1..=3).map(|n| (format!("x-header-{}", n), n.to_string()));
+///
+///     (Headers(headers), "Hello, World!")
+/// }
+/// ```
+///
+/// [`tracing`]: https://crates.io/crates/tracing
+#[derive(Debug, Clone, Copy)]
+pub struct Headers<I>(pub I);
+
+impl<I, K, V> IntoResponse for Headers<I>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: TryInto<HeaderName>,
+    K::Error: fmt::Display,
+    V: TryInto<HeaderValue>,
+    V::Error: fmt::Display,
+{
+    fn into_response(self) -> Response {
+        (self, ()).into_response()
+    }
+}
+
+impl<I, K, V> IntoResponseParts for Headers<I>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: TryInto<HeaderName>,
+    K::Error: fmt::Display,
+    V: TryInto<HeaderValue>,
+    V::Error: fmt::Display,
+{
+    type Error = TryIntoHeaderError<K::Error, V::Error>;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        let mut headers = HeaderMap::new();
+        for (key, value) in self.0 {
+            let key = key.try_into().map_err(TryIntoHeaderError::key)?;
+            let value = value.try_into().map_err(TryIntoHeaderError::value)?;
+            headers.append(key, value);
+        }
+
+        res.headers_mut().extend(headers);
+        Ok(res)
+    }
+}
@@ -148,6 +148,53 @@ impl IntoResponse for Infallible {
     }
 }
 
+/// Responds with `500 Internal Server Error` and logs the error using
+/// [`tracing`] if the `tracing` feature is enabled, which axum's
+/// `tracing-errors` feature does.
+///
+/// The body is a generic message so details about the error aren't leaked to
+/// clients. Enable the `error-details` feature to include the error, and the
+/// chain of its sources, in the body instead. That is useful during
+/// development but shouldn't be enabled in production.
+///
+/// This allows handlers to use `?` with most error types:
+///
+/// ```rust
+/// use axum::{routing::get, BoxError, Router};
+///
+/// async fn handler() -> Result<String, BoxError> {
+///     let contents = std::fs::read_to_string("config.toml")?;
+///     Ok(contents)
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # let _: Router = app;
+/// ```
+///
+/// [`tracing`]: https://crates.io/crates/tracing
+impl IntoResponse for Box<dyn std::error::Error + Send + Sync> {
+    fn into_response(self) -> Response {
+        #[cfg(feature = "tracing")]
+        tracing::error!(error = %self, "handler failed");
+
+        #[cfg(feature = "error-details")]
+        let body = {
+            let mut body = self.to_string();
+            let mut source = self.source();
+            while let Some(err) = source {
+                body.push_str(": ");
+                body.push_str(&err.to_string());
+                source = err.source();
+            }
+            body
+        };
+        #[cfg(not(feature = "error-details"))]
+        let body = "Internal Server Error";
+
+        (StatusCode::INTERNAL_SERVER_ERROR, body).into_response()
+    }
+}
+
 impl<T, E> IntoResponse for Result<T, E>
 where
     T: IntoResponse,
@@ -125,6 +125,9 @@ impl ResponseParts {
     }
 }
 
+/// Each header name in the map replaces any existing values for that name and
+/// all values in the map are kept, so a map with several values for the same
+/// name sets all of them. Headers with other names are left untouched.
 impl IntoResponseParts for HeaderMap {
     type Error = Infallible;
 
@@ -188,9 +191,15 @@ where
     fn into_response(self) -> Response {
         match self.kind {
             TryIntoHeaderErrorKind::Key(inner) => {
+                #[cfg(feature = "tracing")]
+                tracing::error!(error = %inner, "failed to convert key to a header name");
+
                 (StatusCode::INTERNAL_SERVER_ERROR, inner.to_string()).into_response()
             }
             TryIntoHeaderErrorKind::Value(inner) => {
+                #[cfg(feature = "tracing")]
+                tracing::error!(error = %inner, "failed to convert value to a header value");
+
                 (StatusCode::INTERNAL_SERVER_ERROR, inner.to_string()).into_response()
             }
         }
@@ -7,11 +7,13 @@
 use crate::body::BoxBody;
 
 mod append_headers;
+mod headers;
 mod into_response;
 mod into_response_parts;
 
 pub use self::{
     append_headers::AppendHeaders,
+    headers::Headers,
     into_response::IntoResponse,
     into_response_parts::{IntoResponseParts, ResponseParts, TryIntoHeaderError},
 };
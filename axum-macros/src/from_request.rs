@@ -28,15 +28,21 @@ pub(crate) fn expand(item: syn::Item) -> syn::Result<TokenStream> {
                 FromRequestContainerAttr::Via(path) => {
                     impl_struct_by_extracting_all_at_once(ident, fields, path)
                 }
-                FromRequestContainerAttr::RejectionDerive(_, opt_outs) => {
-                    impl_struct_by_extracting_each_field(ident, fields, vis, opt_outs)
+                FromRequestContainerAttr::EachField {
+                    rejection_derive,
+                    collect_rejections,
+                } => {
+                    let opt_outs = rejection_derive
+                        .map(|(_, opt_outs)| opt_outs)
+                        .unwrap_or_default();
+                    impl_struct_by_extracting_each_field(
+                        ident,
+                        fields,
+                        vis,
+                        opt_outs,
+                        collect_rejections.is_some(),
+                    )
                 }
-                FromRequestContainerAttr::None => impl_struct_by_extracting_each_field(
-                    ident,
-                    fields,
-                    vis,
-                    RejectionDeriveOptOuts::default(),
-                ),
             }
         }
         syn::Item::Enum(item) => {
@@ -56,13 +62,24 @@ pub(crate) fn expand(item: syn::Item) -> syn::Result<TokenStream> {
                 FromRequestContainerAttr::Via(path) => {
                     impl_enum_by_extracting_all_at_once(ident, variants, path)
                 }
-                FromRequestContainerAttr::RejectionDerive(rejection_derive, _) => {
-                    Err(syn::Error::new_spanned(
-                        rejection_derive,
-                        "cannot use `rejection_derive` on enums",
-                    ))
-                }
-                FromRequestContainerAttr::None => Err(syn::Error::new(
+                FromRequestContainerAttr::EachField {
+                    rejection_derive: Some((rejection_derive, _)),
+                    collect_rejections: _,
+                } => Err(syn::Error::new_spanned(
+                    rejection_derive,
+                    "cannot use `rejection_derive` on enums",
+                )),
+                FromRequestContainerAttr::EachField {
+                    rejection_derive: None,
+                    collect_rejections: Some(collect_rejections),
+                } => Err(syn::Error::new_spanned(
+                    collect_rejections,
+                    "cannot use `collect_rejections` on enums",
+                )),
+                FromRequestContainerAttr::EachField {
+                    rejection_derive: None,
+                    collect_rejections: None,
+                } => Err(syn::Error::new(
                     Span::call_site(),
                     "missing `#[from_request(via(...))]`",
                 )),
@@ -91,7 +108,17 @@ fn impl_struct_by_extracting_each_field(
     fields: syn::Fields,
     vis: syn::Visibility,
     rejection_derive_opt_outs: RejectionDeriveOptOuts,
+    collect_rejections: bool,
 ) -> syn::Result<TokenStream> {
+    if collect_rejections && !has_no_fields(&fields) {
+        return impl_struct_by_extracting_each_field_collecting_rejections(
+            ident,
+            fields,
+            vis,
+            rejection_derive_opt_outs,
+        );
+    }
+
     let extract_fields = extract_fields(&fields)?;
 
     let (rejection_ident, rejection) = if has_no_fields(&fields) {
@@ -127,6 +154,191 @@ fn impl_struct_by_extracting_each_field(
     })
 }
 
+fn impl_struct_by_extracting_each_field_collecting_rejections(
+    ident: syn::Ident,
+    fields: syn::Fields,
+    vis: syn::Visibility,
+    rejection_derive_opt_outs: RejectionDeriveOptOuts,
+) -> syn::Result<TokenStream> {
+    let rejection_ident = rejection_ident(&ident);
+    let rejections_ident = format_ident!("{}Rejections", ident);
+
+    let mut extract_fields = Vec::new();
+    let mut init_fields = Vec::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        let FromRequestFieldAttr { via } = parse_field_attrs(&field.attrs)?;
+
+        let member = field_member(index, field);
+        let field_ty = &field.ty;
+        let ty_span = field_ty.span();
+        let binding = format_ident!("__field_{}", index);
+        let into_inner = into_inner(via.as_ref().map(|(_, path)| path), ty_span);
+
+        if peel_option(field_ty).is_some() {
+            extract_fields.push(quote_spanned! {ty_span=>
+                let #binding: #field_ty = ::axum::extract::FromRequest::from_request(req)
+                    .await
+                    .ok()
+                    .map(#into_inner);
+            });
+            init_fields.push(quote! { #member: #binding, });
+        } else if peel_result_ok(field_ty).is_some() {
+            extract_fields.push(quote_spanned! {ty_span=>
+                let #binding: #field_ty = ::axum::extract::FromRequest::from_request(req)
+                    .await
+                    .map(#into_inner);
+            });
+            init_fields.push(quote! { #member: #binding, });
+        } else {
+            let rejection_variant_name = rejection_variant_name(field)?;
+            extract_fields.push(quote_spanned! {ty_span=>
+                let #binding: ::std::option::Option<#field_ty> =
+                    match ::axum::extract::FromRequest::from_request(req).await {
+                        ::std::result::Result::Ok(value) => {
+                            ::std::option::Option::Some((#into_inner)(value))
+                        }
+                        ::std::result::Result::Err(rejection) => {
+                            rejections.push(#rejection_ident::#rejection_variant_name(rejection));
+                            ::std::option::Option::None
+                        }
+                    };
+            });
+            init_fields.push(quote! {
+                #member: #binding.expect("all fields were extracted"),
+            });
+        }
+    }
+
+    let rejections = collected_rejections(
+        &rejection_ident,
+        &rejections_ident,
+        &vis,
+        &rejection_derive_opt_outs,
+    );
+    let rejection = extract_each_field_rejection(&ident, &fields, &vis, rejection_derive_opt_outs)?;
+
+    Ok(quote! {
+        #[::axum::async_trait]
+        #[automatically_derived]
+        impl<B> ::axum::extract::FromRequest<B> for #ident
+        where
+            B: ::axum::body::HttpBody + ::std::marker::Send + 'static,
+            B::Data: ::std::marker::Send,
+            B::Error: ::std::convert::Into<::axum::BoxError>,
+        {
+            type Rejection = #rejections_ident;
+
+            async fn from_request(
+                req: &mut ::axum::extract::RequestParts<B>,
+            ) -> ::std::result::Result<Self, Self::Rejection> {
+                let mut rejections = ::std::vec::Vec::new();
+
+                #(#extract_fields)*
+
+                if !rejections.is_empty() {
+                    return ::std::result::Result::Err(#rejections_ident(rejections));
+                }
+
+                ::std::result::Result::Ok(Self {
+                    #(#init_fields)*
+                })
+            }
+        }
+
+        #rejection
+        #rejections
+    })
+}
+
+fn collected_rejections(
+    rejection_ident: &syn::Type,
+    rejections_ident: &syn::Ident,
+    vis: &syn::Visibility,
+    rejection_derive_opt_outs: &RejectionDeriveOptOuts,
+) -> TokenStream {
+    let impl_debug = rejection_derive_opt_outs.derive_debug().then(|| {
+        quote! { #[derive(Debug)] }
+    });
+
+    let impl_error = rejection_derive_opt_outs.derive_error().then(|| {
+        quote! {
+            #[automatically_derived]
+            impl ::std::error::Error for #rejections_ident {
+                fn source(&self) -> ::std::option::Option<&(dyn ::std::error::Error + 'static)> {
+                    self.0
+                        .first()
+                        .map(|rejection| rejection as &(dyn ::std::error::Error + 'static))
+                }
+            }
+        }
+    });
+
+    quote! {
+        #impl_debug
+        #vis struct #rejections_ident(#vis ::std::vec::Vec<#rejection_ident>);
+
+        #[automatically_derived]
+        impl ::axum::response::IntoResponse for #rejections_ident {
+            fn into_response(self) -> ::axum::response::Response {
+                let mut status = ::std::option::Option::None;
+                let mut body = ::std::string::String::new();
+
+                for rejection in self.0 {
+                    body.push_str(&::std::string::ToString::to_string(&rejection));
+                    body.push('\n');
+
+                    let res = ::axum::response::IntoResponse::into_response(rejection);
+                    status.get_or_insert(res.status());
+                }
+
+                let status = status.unwrap_or(::axum::http::StatusCode::BAD_REQUEST);
+                ::axum::response::IntoResponse::into_response((status, body))
+            }
+        }
+
+        #[automatically_derived]
+        impl ::std::fmt::Display for #rejections_ident {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                for (idx, rejection) in self.0.iter().enumerate() {
+                    if idx != 0 {
+                        f.write_str(", ")?;
+                    }
+                    ::std::fmt::Display::fmt(rejection, f)?;
+                }
+                ::std::result::Result::Ok(())
+            }
+        }
+
+        #impl_error
+    }
+}
+
+fn field_member(index: usize, field: &syn::Field) -> TokenStream {
+    if let Some(ident) = &field.ident {
+        quote! { #ident }
+    } else {
+        let member = syn::Member::Unnamed(syn::Index {
+            index: index as u32,
+            span: field.span(),
+        });
+        quote! { #member }
+    }
+}
+
+fn into_inner(via: Option<&syn::Path>, ty_span: Span) -> TokenStream {
+    if let Some(path) = via {
+        let span = path.span();
+        quote_spanned! {span=>
+            |#path(inner)| inner
+        }
+    } else {
+        quote_spanned! {ty_span=>
+            ::std::convert::identity
+        }
+    }
+}
+
 fn has_no_fields(fields: &syn::Fields) -> bool {
     match fields {
         syn::Fields::Named(fields) => fields.named.is_empty(),
@@ -147,28 +359,11 @@ fn extract_fields(fields: &syn::Fields) -> syn::Result<Vec<TokenStream>> {
         .map(|(index, field)| {
             let FromRequestFieldAttr { via } = parse_field_attrs(&field.attrs)?;
 
-            let member = if let Some(ident) = &field.ident {
-                quote! { #ident }
-            } else {
-                let member = syn::Member::Unnamed(syn::Index {
-                    index: index as u32,
-                    span: field.span(),
-                });
-                quote! { #member }
-            };
+            let member = field_member(index, field);
 
             let ty_span = field.ty.span();
 
-            let into_inner = if let Some((_, path)) = via {
-                let span = path.span();
-                quote_spanned! {span=>
-                    |#path(inner)| inner
-                }
-            } else {
-                quote_spanned! {ty_span=>
-                    ::std::convert::identity
-                }
-            };
+            let into_inner = into_inner(via.as_ref().map(|(_, path)| path), ty_span);
 
             let rejection_variant_name = rejection_variant_name(field)?;
 
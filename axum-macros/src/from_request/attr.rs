@@ -12,13 +12,16 @@ pub(crate) struct FromRequestFieldAttr {
 
 pub(crate) enum FromRequestContainerAttr {
     Via(syn::Path),
-    RejectionDerive(kw::rejection_derive, RejectionDeriveOptOuts),
-    None,
+    EachField {
+        rejection_derive: Option<(kw::rejection_derive, RejectionDeriveOptOuts)>,
+        collect_rejections: Option<kw::collect_rejections>,
+    },
 }
 
 pub(crate) mod kw {
     syn::custom_keyword!(via);
     syn::custom_keyword!(rejection_derive);
+    syn::custom_keyword!(collect_rejections);
     syn::custom_keyword!(Display);
     syn::custom_keyword!(Debug);
     syn::custom_keyword!(Error);
@@ -51,6 +54,7 @@ pub(crate) fn parse_container_attrs(
 
     let mut out_via = None;
     let mut out_rejection_derive = None;
+    let mut out_collect_rejections = None;
 
     // we track the index of the attribute to know which comes last
     // used to give more accurate error messages
@@ -73,28 +77,75 @@ pub(crate) fn parse_container_attrs(
                     out_rejection_derive = Some((idx, rejection_derive, opt_outs));
                 }
             }
+            ContainerAttr::CollectRejections { collect_rejections } => {
+                if out_collect_rejections.is_some() {
+                    return Err(double_attr_error("collect_rejections", collect_rejections));
+                } else {
+                    out_collect_rejections = Some((idx, collect_rejections));
+                }
+            }
         }
     }
 
-    match (out_via, out_rejection_derive) {
-        (Some((via_idx, via, _)), Some((rejection_derive_idx, rejection_derive, _))) => {
-            if via_idx > rejection_derive_idx {
-                Err(syn::Error::new_spanned(
-                    via,
-                    "cannot use both `rejection_derive` and `via`",
-                ))
-            } else {
-                Err(syn::Error::new_spanned(
-                    rejection_derive,
-                    "cannot use both `via` and `rejection_derive`",
-                ))
-            }
+    if let Some((via_idx, via, path)) = out_via {
+        if let Some((rejection_derive_idx, rejection_derive, _)) = out_rejection_derive {
+            return Err(via_conflict_error(
+                via_idx,
+                via,
+                rejection_derive_idx,
+                rejection_derive,
+                "rejection_derive",
+            ));
+        }
+
+        if let Some((collect_rejections_idx, collect_rejections)) = out_collect_rejections {
+            return Err(via_conflict_error(
+                via_idx,
+                via,
+                collect_rejections_idx,
+                collect_rejections,
+                "collect_rejections",
+            ));
         }
-        (Some((_, _, path)), None) => Ok(FromRequestContainerAttr::Via(path)),
-        (None, Some((_, rejection_derive, opt_outs))) => Ok(
-            FromRequestContainerAttr::RejectionDerive(rejection_derive, opt_outs),
-        ),
-        (None, None) => Ok(FromRequestContainerAttr::None),
+
+        return Ok(FromRequestContainerAttr::Via(path));
+    }
+
+    if let (Some((_, _, opt_outs)), Some((_, collect_rejections))) =
+        (&out_rejection_derive, &out_collect_rejections)
+    {
+        if !opt_outs.derive_display() {
+            return Err(syn::Error::new_spanned(
+                collect_rejections,
+                "`collect_rejections` requires the rejection to implement `Display`",
+            ));
+        }
+    }
+
+    Ok(FromRequestContainerAttr::EachField {
+        rejection_derive: out_rejection_derive
+            .map(|(_, rejection_derive, opt_outs)| (rejection_derive, opt_outs)),
+        collect_rejections: out_collect_rejections
+            .map(|(_, collect_rejections)| collect_rejections),
+    })
+}
+
+/// Error for using `via` together with another container attribute, pointing
+/// at whichever of the two comes last.
+fn via_conflict_error<T>(
+    via_idx: usize,
+    via: kw::via,
+    other_idx: usize,
+    other: T,
+    other_name: &str,
+) -> syn::Error
+where
+    T: ToTokens,
+{
+    if via_idx > other_idx {
+        syn::Error::new_spanned(via, format!("cannot use both `{}` and `via`", other_name))
+    } else {
+        syn::Error::new_spanned(other, format!("cannot use both `via` and `{}`", other_name))
     }
 }
 
@@ -129,6 +180,9 @@ enum ContainerAttr {
         rejection_derive: kw::rejection_derive,
         opt_outs: RejectionDeriveOptOuts,
     },
+    CollectRejections {
+        collect_rejections: kw::collect_rejections,
+    },
 }
 
 impl Parse for ContainerAttr {
@@ -147,6 +201,10 @@ impl Parse for ContainerAttr {
                 rejection_derive,
                 opt_outs,
             })
+        } else if lh.peek(kw::collect_rejections) {
+            input
+                .parse()
+                .map(|collect_rejections| Self::CollectRejections { collect_rejections })
         } else {
             Err(lh.error())
         }
@@ -49,6 +49,7 @@ use syn::parse::Parse;
 
 mod debug_handler;
 mod from_request;
+mod route;
 mod typed_path;
 
 /// Derive an implementation of [`FromRequest`].
@@ -155,6 +156,48 @@ mod typed_path;
 /// async fn handler(extractor: MyExtractor) {}
 /// ```
 ///
+/// ## Newtypes
+///
+/// Tuple structs are supported as well, which makes it easy to give a single
+/// extractor a more meaningful name:
+///
+/// ```
+/// use axum_macros::FromRequest;
+/// use axum::{
+///     extract::TypedHeader,
+///     headers::{authorization::Bearer, Authorization},
+/// };
+///
+/// #[derive(FromRequest)]
+/// struct AuthToken(TypedHeader<Authorization<Bearer>>);
+///
+/// async fn handler(AuthToken(TypedHeader(Authorization(bearer))): AuthToken) {
+///     let token = bearer.token();
+///     // ...
+/// }
+/// ```
+///
+/// Use `#[from_request(via(...))]` on the field to store the inner value directly:
+///
+/// ```
+/// use axum_macros::FromRequest;
+/// use axum::{
+///     extract::TypedHeader,
+///     headers::{authorization::Bearer, Authorization},
+/// };
+///
+/// #[derive(FromRequest)]
+/// struct AuthToken(#[from_request(via(TypedHeader))] Authorization<Bearer>);
+///
+/// async fn handler(AuthToken(Authorization(bearer)): AuthToken) {
+///     let token = bearer.token();
+///     // ...
+/// }
+/// ```
+///
+/// The rejection of a newtype is generated like for any other struct, as described below, so it
+/// has a single variant wrapping the rejection of the inner extractor.
+///
 /// ## The rejection
 ///
 /// A rejection enum is also generated. It has a variant for each field:
@@ -237,6 +280,47 @@ mod typed_path;
 /// }
 /// ```
 ///
+/// # Collecting all rejections
+///
+/// By default the first field that fails to extract causes the whole extractor to fail. With
+/// `#[from_request(collect_rejections)]` every field is extracted and the rejections of all fields
+/// that fail are returned together. This is useful for forms where users should see every problem
+/// at once:
+///
+/// ```
+/// use axum_macros::FromRequest;
+/// use axum::{
+///     extract::{Query, TypedHeader},
+///     headers::UserAgent,
+/// };
+/// use std::collections::HashMap;
+///
+/// #[derive(FromRequest)]
+/// #[from_request(collect_rejections)]
+/// struct MyExtractor {
+///     query: Query<HashMap<String, String>>,
+///     #[from_request(via(TypedHeader))]
+///     user_agent: UserAgent,
+/// }
+///
+/// // also generates `MyExtractorRejection`, as above, and
+/// //
+/// // #[derive(Debug)]
+/// // struct MyExtractorRejections(Vec<MyExtractorRejection>);
+/// //
+/// // impl axum::response::IntoResponse for MyExtractorRejections { ... }
+/// //
+/// // impl std::fmt::Display for MyExtractorRejections { ... }
+/// //
+/// // impl std::error::Error for MyExtractorRejections { ... }
+/// ```
+///
+/// The rejection is then `MyExtractorRejections`, which holds the rejection of each field that
+/// failed, in the order of the fields. Its response has the status code of the first rejection and
+/// a plain text body with the `Display` output of each rejection on its own line, so the field
+/// rejections must implement `Display`. Note that fields that consume the request body, such as
+/// `String` or `Json<_>`, can only be extracted once, so at most one of them should be used.
+///
 /// # The whole type at once
 ///
 /// By using `#[from_request(via(...))]` on the container you can extract the whole type at once,
@@ -396,6 +480,53 @@ pub fn derive_typed_path(input: TokenStream) -> TokenStream {
     expand_with(input, typed_path::expand)
 }
 
+/// Add a route to a [`Router`], checking the path at compile time.
+///
+/// `route!(router, "/path", method_router)` expands to
+/// `router.route("/path", method_router)` but fails to compile, rather than
+/// panicking when the router is built, if the path
+///
+/// - doesn't start with `/`,
+/// - contains empty segments, such as `/users//:id`,
+/// - captures the same parameter more than once, such as `/:id/posts/:id`,
+/// - has parameters without a name, such as `/users/:`, or
+/// - has a `*` anywhere but at the start of the last segment.
+///
+/// Routes that overlap with other routes are still only detected when the
+/// router is built.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_macros::route;
+///
+/// async fn users() {}
+/// async fn user() {}
+///
+/// let app = Router::new();
+/// let app = route!(app, "/users", get(users));
+/// let app = route!(app, "/users/:id", get(user));
+/// # let _: Router = app;
+/// ```
+///
+/// Paths with typos that make them invalid are rejected:
+///
+/// ```compile_fail
+/// use axum::{routing::get, Router};
+/// use axum_macros::route;
+///
+/// async fn user() {}
+///
+/// let app = route!(Router::new(), "/users/:id/:id", get(user));
+/// ```
+///
+/// [`Router`]: https://docs.rs/axum/latest/axum/struct.Router.html
+#[proc_macro]
+pub fn route(input: TokenStream) -> TokenStream {
+    expand_with(input, route::expand)
+}
+
 fn expand_with<F, I, K>(input: TokenStream, f: F) -> TokenStream
 where
     F: FnOnce(I) -> syn::Result<K>,
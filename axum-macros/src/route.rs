@@ -0,0 +1,108 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::collections::HashSet;
+use syn::{parse::Parse, Expr, LitStr, Token};
+
+pub(crate) fn expand(input: Input) -> syn::Result<TokenStream> {
+    let Input {
+        router,
+        path,
+        method_router,
+    } = input;
+
+    validate_path(&path)?;
+
+    Ok(quote! {
+        (#router).route(#path, #method_router)
+    })
+}
+
+pub(crate) struct Input {
+    router: Expr,
+    path: LitStr,
+    method_router: Expr,
+}
+
+impl Parse for Input {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let router = input.parse()?;
+        let _: Token![,] = input.parse()?;
+        let path = input.parse()?;
+        let _: Token![,] = input.parse()?;
+        let method_router = input.parse()?;
+        let _: Option<Token![,]> = input.parse()?;
+
+        Ok(Self {
+            router,
+            path,
+            method_router,
+        })
+    }
+}
+
+fn validate_path(path: &LitStr) -> syn::Result<()> {
+    let value = path.value();
+    let err = |msg: String| Err(syn::Error::new_spanned(path, msg));
+
+    if value.is_empty() {
+        return err("paths must start with a `/`. Use \"/\" for root routes".to_owned());
+    } else if !value.starts_with('/') {
+        return err("paths must start with a `/`".to_owned());
+    } else if value.contains("//") {
+        return err("paths cannot contain empty segments (`//`)".to_owned());
+    }
+
+    let segments = value[1..].split('/').collect::<Vec<_>>();
+    let mut params = HashSet::new();
+
+    for (idx, segment) in segments.iter().enumerate() {
+        let param = if let Some(name) = segment.strip_prefix(':') {
+            name
+        } else if let Some(name) = segment.strip_prefix('*') {
+            if idx != segments.len() - 1 {
+                return err(format!(
+                    "wildcard `{}` must be the last segment of the path",
+                    segment
+                ));
+            }
+            name
+        } else {
+            continue;
+        };
+
+        if param.is_empty() {
+            return err(format!("parameter `{}` must be named", segment));
+        } else if param.contains(|c| c == ':' || c == '*') {
+            return err(format!("invalid parameter `{}`", segment));
+        } else if !params.insert(param) {
+            return err(format!("parameter `{}` is captured more than once", param));
+        }
+    }
+
+    if let Some(segment) = segments
+        .iter()
+        .find(|segment| !segment.starts_with('*') && segment.contains('*'))
+    {
+        return err(format!(
+            "`*` is only allowed at the start of the last segment, found `{}`",
+            segment
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn ui() {
+    #[rustversion::stable]
+    fn go() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/route/fail/*.rs");
+        t.pass("tests/route/pass/*.rs");
+    }
+
+    #[rustversion::not(stable)]
+    fn go() {}
+
+    go();
+}
@@ -0,0 +1,9 @@
+use axum_macros::FromRequest;
+
+#[derive(FromRequest)]
+#[from_request(rejection_derive(!Display, !Error), collect_rejections)]
+struct Extractor {
+    body: String,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use axum_macros::FromRequest;
+
+#[derive(FromRequest, Clone)]
+#[from_request(collect_rejections)]
+enum Extractor {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use axum_macros::FromRequest;
+use axum::extract::Extension;
+
+#[derive(FromRequest, Clone)]
+#[from_request(via(Extension), collect_rejections)]
+struct Extractor {
+    config: String,
+}
+
+fn main() {}
@@ -0,0 +1,50 @@
+use axum::{
+    body::Body,
+    extract::{
+        rejection::{ExtensionRejection, TypedHeaderRejection},
+        Extension, FromRequest, TypedHeader,
+    },
+    headers::{self, UserAgent},
+};
+use axum_macros::FromRequest;
+
+#[derive(FromRequest)]
+#[from_request(collect_rejections)]
+struct Extractor {
+    #[from_request(via(Extension))]
+    state: State,
+    #[from_request(via(TypedHeader))]
+    user_agent: UserAgent,
+    #[from_request(via(TypedHeader))]
+    etag: Option<headers::ETag>,
+    #[from_request(via(TypedHeader))]
+    host: Result<headers::Host, TypedHeaderRejection>,
+}
+
+fn assert_from_request()
+where
+    Extractor: FromRequest<Body, Rejection = ExtractorRejections>,
+{
+}
+
+fn assert_rejection(rejections: ExtractorRejections)
+where
+    ExtractorRejections: std::fmt::Debug + std::fmt::Display + std::error::Error,
+{
+    for rejection in rejections.0 {
+        match rejection {
+            ExtractorRejection::State(inner) => {
+                let _: ExtensionRejection = inner;
+            }
+            ExtractorRejection::UserAgent(inner) => {
+                let _: TypedHeaderRejection = inner;
+            }
+            ExtractorRejection::Etag(_) | ExtractorRejection::Host(_) => {}
+        }
+    }
+}
+
+#[derive(Clone)]
+struct State;
+
+fn main() {}
@@ -0,0 +1,22 @@
+use axum::{
+    extract::TypedHeader,
+    headers::{authorization::Bearer, Authorization},
+};
+use axum_macros::FromRequest;
+
+#[derive(FromRequest)]
+struct AuthToken(TypedHeader<Authorization<Bearer>>);
+
+async fn handler(AuthToken(TypedHeader(Authorization(bearer))): AuthToken) -> String {
+    bearer.token().to_owned()
+}
+
+fn assert_from_request()
+where
+    AuthToken: axum::extract::FromRequest<axum::body::Body, Rejection = AuthTokenRejection>,
+{
+}
+
+fn main() {
+    let _: axum::routing::MethodRouter = axum::routing::get(handler);
+}
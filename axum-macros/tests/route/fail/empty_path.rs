@@ -0,0 +1,8 @@
+use axum::Router;
+use axum_macros::route;
+
+async fn handler() {}
+
+fn main() {
+    let _: Router = route!(Router::new(), "", axum::routing::get(handler));
+}
@@ -0,0 +1,13 @@
+use axum::{routing::get, Router};
+use axum_macros::route;
+
+async fn handler() {}
+
+fn main() {
+    let app = Router::new();
+    let app = route!(app, "/", get(handler));
+    let app = route!(app, "/users/", get(handler));
+    let app = route!(app, "/users/:id", get(handler));
+    let app = route!(app, "/users/:id/posts/:post_id", get(handler),);
+    let _: Router = route!(app, "/assets/*path", get(handler));
+}
@@ -0,0 +1,67 @@
+//! Counts the allocations `IntoMakeService` makes to produce the service for
+//! each new connection.
+//!
+//! Run with `cargo bench -p axum --bench per_connection_allocations`.
+
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use futures::FutureExt;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+use tower::Service;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const CONNECTIONS: usize = 10_000;
+
+fn main() {
+    for routes in [1, 10, 100] {
+        let mut app: Router = Router::new();
+        for i in 0..routes {
+            let path = format!("/route-{}", i);
+            app = app
+                .route_named(
+                    &format!("route-{}", i),
+                    &path,
+                    get(|| async {}).post(|| async {}),
+                )
+                .route_timeout(&path, Duration::from_secs(10));
+        }
+        let app = app.nest("/nested", Router::new().route("/", post(|| async {})));
+        let mut make_service = app.into_make_service();
+
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        for _ in 0..CONNECTIONS {
+            let svc = make_service.call(()).now_or_never().unwrap().unwrap();
+            drop(svc);
+        }
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+        println!(
+            "{:>3} routes: {:>6.1} allocations per connection",
+            routes,
+            allocations as f64 / CONNECTIONS as f64,
+        );
+    }
+}
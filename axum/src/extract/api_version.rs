@@ -0,0 +1,288 @@
+//! Extractor for enforcing the version of versioned APIs.
+//!
+//! See [`ApiVersion`] for more details.
+
+use super::{rejection::*, FromRequest, RequestParts};
+use crate::routing::url_params::UrlParams;
+use async_trait::async_trait;
+use http::header::{HeaderMap, HeaderName, ACCEPT};
+use std::sync::Arc;
+
+/// Extractor that reads the API version of the request and rejects versions
+/// that aren't supported.
+///
+/// Where the version is read from, and which versions are supported, is
+/// configured with an [`ApiVersionConfig`] added as an [`Extension`]. The
+/// version can be read from
+///
+/// - a path parameter, such as `:version` in `/:version/users`, with
+///   [`ApiVersionConfig::path_param`],
+/// - a header, such as `X-Api-Version: 2`, with [`ApiVersionConfig::header`],
+///   or
+/// - a parameter of the media type in the `Accept` header, such as
+///   `Accept: application/vnd.example+json; version=2`, with
+///   [`ApiVersionConfig::accept_param`].
+///
+/// Requests without a version are rejected with `400 Bad Request`, unless a
+/// default version is set with [`ApiVersionConfig::default_version`].
+/// Requests with an unsupported version are rejected with `400 Bad Request`,
+/// or `406 Not Acceptable` if the version was read from the `Accept` header.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{
+///     extract::api_version::{ApiVersion, ApiVersionConfig},
+///     routing::get,
+///     Extension, Router,
+/// };
+///
+/// async fn users(ApiVersion(version): ApiVersion) -> String {
+///     match version.as_str() {
+///         "v1" => "users v1".to_owned(),
+///         _ => "users v2".to_owned(),
+///     }
+/// }
+///
+/// let config = ApiVersionConfig::path_param("version").supported(["v1", "v2"]);
+///
+/// let app = Router::new()
+///     .route("/:version/users", get(users))
+///     .layer(Extension(config));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+///
+/// [`Extension`]: crate::Extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiVersion(pub String);
+
+#[async_trait]
+impl<B> FromRequest<B> for ApiVersion
+where
+    B: Send,
+{
+    type Rejection = ApiVersionRejection;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let config = req
+            .extensions()
+            .get::<ApiVersionConfig>()
+            .ok_or_else(|| {
+                MissingExtension::from_err(
+                    "Extension of type `axum::extract::api_version::ApiVersionConfig` was not found. Perhaps you forgot to add it? See `axum::Extension`.",
+                )
+            })?;
+
+        let version = match &config.source {
+            Source::PathParam(name) => path_param(req, name),
+            Source::Header(name) => req
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.trim().to_owned()),
+            Source::AcceptParam(name) => accept_param(req.headers(), name),
+        };
+
+        let version = version
+            .or_else(|| config.default_version.as_deref().map(Into::into))
+            .ok_or(MissingApiVersion)?;
+
+        if !config.is_supported(&version) {
+            return match config.source {
+                Source::AcceptParam(_) => Err(NotAcceptableApiVersion.into()),
+                _ => Err(UnsupportedApiVersion.into()),
+            };
+        }
+
+        Ok(Self(version))
+    }
+}
+
+fn path_param<B>(req: &RequestParts<B>, name: &str) -> Option<String> {
+    match req.extensions().get::<UrlParams>()? {
+        UrlParams::Params(params) => params
+            .iter()
+            .find(|(key, _)| &**key == name)
+            .map(|(_, value)| value.as_str().to_owned()),
+        UrlParams::InvalidUtf8InPathParam { .. } => None,
+    }
+}
+
+/// Find the media type parameter `name` in the `Accept` header, such as
+/// `version` in `application/vnd.example+json; version=2`.
+fn accept_param(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get_all(ACCEPT)
+        .into_iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .flat_map(|media_range| media_range.split(';').skip(1))
+        .find_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            key.trim()
+                .eq_ignore_ascii_case(name)
+                .then(|| value.trim().trim_matches('"').to_owned())
+        })
+}
+
+/// Configuration for the [`ApiVersion`] extractor.
+///
+/// Must be added to your app with [`Extension`]. See [`ApiVersion`] for an
+/// example.
+///
+/// [`Extension`]: crate::Extension
+#[derive(Debug, Clone)]
+pub struct ApiVersionConfig {
+    source: Source,
+    supported: Option<Arc<[String]>>,
+    default_version: Option<Arc<str>>,
+}
+
+#[derive(Debug, Clone)]
+enum Source {
+    PathParam(Arc<str>),
+    Header(HeaderName),
+    AcceptParam(Arc<str>),
+}
+
+impl ApiVersionConfig {
+    /// Read the version from the path parameter `name`.
+    pub fn path_param(name: &str) -> Self {
+        Self::new(Source::PathParam(name.into()))
+    }
+
+    /// Read the version from the header `name`.
+    pub fn header(name: HeaderName) -> Self {
+        Self::new(Source::Header(name))
+    }
+
+    /// Read the version from the media type parameter `name` in the `Accept`
+    /// header, such as `version` in `application/vnd.example+json; version=2`.
+    pub fn accept_param(name: &str) -> Self {
+        Self::new(Source::AcceptParam(name.into()))
+    }
+
+    fn new(source: Source) -> Self {
+        Self {
+            source,
+            supported: None,
+            default_version: None,
+        }
+    }
+
+    /// Set the supported versions.
+    ///
+    /// By default all versions are supported.
+    pub fn supported<I>(mut self, versions: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.supported = Some(versions.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set the version used for requests that don't specify one.
+    ///
+    /// By default such requests are rejected.
+    pub fn default_version(mut self, version: &str) -> Self {
+        self.default_version = Some(version.into());
+        self
+    }
+
+    fn is_supported(&self, version: &str) -> bool {
+        match &self.supported {
+            Some(supported) => supported.iter().any(|supported| supported == version),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{routing::get, test_helpers::*, Extension, Router};
+    use http::StatusCode;
+
+    async fn version(ApiVersion(version): ApiVersion) -> String {
+        version
+    }
+
+    #[tokio::test]
+    async fn path_param() {
+        let app = Router::new()
+            .route("/:version/users", get(version))
+            .layer(Extension(
+                ApiVersionConfig::path_param("version").supported(["v1", "v2"]),
+            ));
+        let client = TestClient::new(app);
+
+        let res = client.get("/v2/users").send().await;
+        assert_eq!(res.text().await, "v2");
+
+        let res = client.get("/v3/users").send().await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn header() {
+        let app = Router::new().route("/", get(version)).layer(Extension(
+            ApiVersionConfig::header(HeaderName::from_static("x-api-version"))
+                .supported(["1", "2"])
+                .default_version("1"),
+        ));
+        let client = TestClient::new(app);
+
+        let res = client.get("/").header("x-api-version", "2").send().await;
+        assert_eq!(res.text().await, "2");
+
+        let res = client.get("/").send().await;
+        assert_eq!(res.text().await, "1");
+
+        let res = client.get("/").header("x-api-version", "3").send().await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn accept_param() {
+        let app = Router::new().route("/", get(version)).layer(Extension(
+            ApiVersionConfig::accept_param("version").supported(["1", "2"]),
+        ));
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header(
+                "accept",
+                "text/html, application/vnd.example+json; version=\"2\"",
+            )
+            .send()
+            .await;
+        assert_eq!(res.text().await, "2");
+
+        let res = client
+            .get("/")
+            .header("accept", "application/vnd.example+json; version=3")
+            .send()
+            .await;
+        assert_eq!(res.status(), StatusCode::NOT_ACCEPTABLE);
+
+        let res = client
+            .get("/")
+            .header("accept", "application/json")
+            .send()
+            .await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn missing_config() {
+        let app = Router::new().route("/", get(version));
+        let client = TestClient::new(app);
+
+        let res = client.get("/").send().await;
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
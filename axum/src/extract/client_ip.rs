@@ -0,0 +1,462 @@
+//! Extractor for resolving the client's IP address behind proxies.
+//!
+//! See [`ClientIp`] for more details.
+
+use super::{connect_info::ConnectInfo, rejection::ExtensionRejection, FromRequest, RequestParts};
+use async_trait::async_trait;
+use http::header::{HeaderMap, FORWARDED};
+use std::{
+    error::Error,
+    fmt,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    str::FromStr,
+};
+
+const X_FORWARDED_FOR_HEADER_KEY: &str = "X-Forwarded-For";
+
+/// Extractor that resolves the IP address of the client, taking trusted
+/// proxies into account.
+///
+/// The address of the peer is taken from [`ConnectInfo<SocketAddr>`] so this
+/// extractor requires running your app with
+/// [`Router::into_make_service_with_connect_info`].
+///
+/// If the peer is one of the [`TrustedProxies`] added as an [`Extension`] the
+/// header set with [`TrustedProxies::header`], `X-Forwarded-For` by default,
+/// is read from right to left, skipping addresses of trusted proxies. The first
+/// address that isn't trusted is the client. Addresses added by clients are
+/// never trusted over the ones added by proxies, so clients cannot spoof
+/// their IP by sending the header themselves.
+///
+/// Only the configured header is read. It must be the header your proxies
+/// append to, otherwise clients can forge the whole chain. For example if
+/// your proxies only set `X-Forwarded-For`, a `Forwarded` header can only have
+/// been sent by the client.
+///
+/// Without [`TrustedProxies`] nothing is trusted and the peer address is
+/// always used. If an entry in the headers cannot be parsed, such as
+/// `for=unknown`, the address of the last trusted proxy is used.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::client_ip::{ClientIp, ForwardedHeader, TrustedProxies},
+///     routing::get,
+///     Extension, Router,
+/// };
+/// use std::net::SocketAddr;
+///
+/// async fn handler(ClientIp(ip): ClientIp) -> String {
+///     format!("Hello {}", ip)
+/// }
+///
+/// let trusted_proxies = TrustedProxies::new()
+///     .header(ForwardedHeader::XForwardedFor)
+///     .trust("10.0.0.0/8")
+///     .unwrap()
+///     .trust("::1")
+///     .unwrap();
+///
+/// let app = Router::new()
+///     .route("/", get(handler))
+///     .layer(Extension(trusted_proxies));
+///
+/// # async {
+/// axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
+///     .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+///     .await
+///     .unwrap();
+/// # };
+/// ```
+///
+/// [`Router::into_make_service_with_connect_info`]: crate::routing::Router::into_make_service_with_connect_info
+/// [`Extension`]: crate::Extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+#[async_trait]
+impl<B> FromRequest<B> for ClientIp
+where
+    B: Send,
+{
+    type Rejection = ExtensionRejection;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let ConnectInfo(peer) = ConnectInfo::<SocketAddr>::from_request(req).await?;
+        let peer = canonical(peer.ip());
+
+        let trusted_proxies = match req.extensions().get::<TrustedProxies>() {
+            Some(trusted_proxies) => trusted_proxies,
+            None => return Ok(Self(peer)),
+        };
+
+        let chain = match trusted_proxies.header {
+            ForwardedHeader::XForwardedFor => x_forwarded_for_chain(req.headers()),
+            ForwardedHeader::Forwarded => forwarded_chain(req.headers()),
+        };
+
+        let mut client = peer;
+        for hop in chain.into_iter().rev() {
+            if !trusted_proxies.is_trusted(client) {
+                break;
+            }
+
+            match hop {
+                Some(hop) => client = hop,
+                None => break,
+            }
+        }
+
+        Ok(Self(client))
+    }
+}
+
+fn x_forwarded_for_chain(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    headers
+        .get_all(X_FORWARDED_FOR_HEADER_KEY)
+        .into_iter()
+        .flat_map(|value| value.to_str().ok().unwrap_or_default().split(','))
+        .map(parse_node)
+        .collect()
+}
+
+fn forwarded_chain(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    headers
+        .get_all(FORWARDED)
+        .into_iter()
+        .flat_map(|value| value.to_str().ok().unwrap_or_default().split(','))
+        .map(|element| {
+            let node = element.split(';').find_map(|pair| {
+                let (key, value) = pair.split_once('=')?;
+                key.trim().eq_ignore_ascii_case("for").then(|| value)
+            })?;
+            parse_node(node.trim().trim_matches('"'))
+        })
+        .collect()
+}
+
+/// Parse an address from a forwarding header, with or without a port.
+fn parse_node(node: &str) -> Option<IpAddr> {
+    let node = node.trim();
+    node.parse::<IpAddr>()
+        .or_else(|_| node.parse::<SocketAddr>().map(|addr| addr.ip()))
+        .or_else(|_| {
+            node.strip_prefix('[')
+                .and_then(|node| node.strip_suffix(']'))
+                .unwrap_or_default()
+                .parse::<IpAddr>()
+        })
+        .ok()
+        .map(canonical)
+}
+
+/// Convert IPv4-mapped IPv6 addresses, as seen on dual-stack sockets, to IPv4.
+fn canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => match v6.segments() {
+            [0, 0, 0, 0, 0, 0xffff, hi, lo] => {
+                IpAddr::V4(Ipv4Addr::from(u32::from(hi) << 16 | u32::from(lo)))
+            }
+            _ => ip,
+        },
+        IpAddr::V4(_) => ip,
+    }
+}
+
+/// The proxies trusted by [`ClientIp`] to report the client's address.
+///
+/// Must be added to your app with [`Extension`] for [`ClientIp`] to read
+/// forwarding headers. See [`ClientIp`] for an example.
+///
+/// [`Extension`]: crate::Extension
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    cidrs: Vec<Cidr>,
+    header: ForwardedHeader,
+}
+
+impl TrustedProxies {
+    /// Create a new `TrustedProxies` that doesn't trust any proxies.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust proxies in a CIDR range, such as `10.0.0.0/8` or `fd00::/8`, or
+    /// a single address, such as `192.168.0.1`.
+    pub fn trust(mut self, cidr: &str) -> Result<Self, InvalidCidr> {
+        self.cidrs.push(cidr.parse()?);
+        Ok(self)
+    }
+
+    /// Set the header trusted proxies report the client's address in.
+    ///
+    /// Defaults to [`ForwardedHeader::XForwardedFor`].
+    pub fn header(mut self, header: ForwardedHeader) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Returns `true` if the address is in one of the trusted ranges.
+    pub fn is_trusted(&self, ip: IpAddr) -> bool {
+        let ip = canonical(ip);
+        self.cidrs.iter().any(|cidr| cidr.contains(ip))
+    }
+}
+
+/// The header [`TrustedProxies`] report the client's address in.
+///
+/// See [`TrustedProxies::header`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForwardedHeader {
+    /// The `X-Forwarded-For` header.
+    XForwardedFor,
+    /// The standard `Forwarded` header, as defined in [RFC 7239].
+    ///
+    /// [RFC 7239]: https://www.rfc-editor.org/rfc/rfc7239
+    Forwarded,
+}
+
+impl Default for ForwardedHeader {
+    fn default() -> Self {
+        Self::XForwardedFor
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Cidr {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl Cidr {
+    fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - self.prefix_len as u32)
+                    .unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - self.prefix_len as u32)
+                    .unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = InvalidCidr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidCidr { cidr: s.to_owned() };
+
+        let (addr, prefix_len) = match s.split_once('/') {
+            Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+            None => (s, None),
+        };
+
+        let addr = addr.parse::<IpAddr>().map_err(|_| invalid())?;
+        let max_len = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len.parse::<u8>().map_err(|_| invalid())?,
+            None => max_len,
+        };
+        if prefix_len > max_len {
+            return Err(invalid());
+        }
+
+        Ok(Self { addr, prefix_len })
+    }
+}
+
+/// Error returned by [`TrustedProxies::trust`] if the CIDR range is invalid.
+#[derive(Debug)]
+pub struct InvalidCidr {
+    cidr: String,
+}
+
+impl fmt::Display for InvalidCidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid CIDR range `{}`", self.cidr)
+    }
+}
+
+impl Error for InvalidCidr {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{routing::get, test_helpers::TestClient, Extension, Router};
+
+    fn test_client(peer: &str, trusted_proxies: Option<&[&str]>) -> TestClient {
+        test_client_with_header(peer, trusted_proxies, ForwardedHeader::XForwardedFor)
+    }
+
+    fn test_client_with_header(
+        peer: &str,
+        trusted_proxies: Option<&[&str]>,
+        header: ForwardedHeader,
+    ) -> TestClient {
+        async fn client_ip_as_body(ClientIp(ip): ClientIp) -> String {
+            ip.to_string()
+        }
+
+        let peer: SocketAddr = peer.parse().unwrap();
+        let mut app = Router::new().route("/", get(client_ip_as_body));
+        if let Some(cidrs) = trusted_proxies {
+            let trusted_proxies = cidrs
+                .iter()
+                .fold(TrustedProxies::new().header(header), |proxies, cidr| {
+                    proxies.trust(cidr).unwrap()
+                });
+            app = app.layer(Extension(trusted_proxies));
+        }
+
+        TestClient::new(app.layer(Extension(ConnectInfo(peer))))
+    }
+
+    #[tokio::test]
+    async fn no_trusted_proxies() {
+        let res = test_client("10.0.0.1:1234", None)
+            .get("/")
+            .header(X_FORWARDED_FOR_HEADER_KEY, "1.2.3.4")
+            .send()
+            .await;
+        assert_eq!(res.text().await, "10.0.0.1");
+    }
+
+    #[tokio::test]
+    async fn untrusted_peer() {
+        let res = test_client("203.0.113.1:1234", Some(&["10.0.0.0/8"]))
+            .get("/")
+            .header(X_FORWARDED_FOR_HEADER_KEY, "1.2.3.4")
+            .send()
+            .await;
+        assert_eq!(res.text().await, "203.0.113.1");
+    }
+
+    #[tokio::test]
+    async fn x_forwarded_for_skips_trusted_hops() {
+        let res = test_client("10.0.0.1:1234", Some(&["10.0.0.0/8"]))
+            .get("/")
+            .header(X_FORWARDED_FOR_HEADER_KEY, "6.6.6.6, 1.2.3.4")
+            .header(X_FORWARDED_FOR_HEADER_KEY, "10.0.0.2")
+            .send()
+            .await;
+        assert_eq!(res.text().await, "1.2.3.4");
+    }
+
+    #[tokio::test]
+    async fn all_hops_trusted() {
+        let res = test_client("10.0.0.1:1234", Some(&["10.0.0.0/8"]))
+            .get("/")
+            .header(X_FORWARDED_FOR_HEADER_KEY, "10.0.0.3, 10.0.0.2")
+            .send()
+            .await;
+        assert_eq!(res.text().await, "10.0.0.3");
+    }
+
+    #[tokio::test]
+    async fn forwarded_header_with_ipv6() {
+        let res = test_client_with_header(
+            "[::1]:1234",
+            Some(&["::1", "fd00::/8"]),
+            ForwardedHeader::Forwarded,
+        )
+        .get("/")
+        .header(
+            FORWARDED,
+            r#"for="[2001:db8::1]:4711";proto=https, for="[fd00::2]""#,
+        )
+        .header(X_FORWARDED_FOR_HEADER_KEY, "1.2.3.4")
+        .send()
+        .await;
+        assert_eq!(res.text().await, "2001:db8::1");
+    }
+
+    #[tokio::test]
+    async fn unparsable_hop_uses_last_trusted_proxy() {
+        let res = test_client_with_header(
+            "10.0.0.1:1234",
+            Some(&["10.0.0.0/8"]),
+            ForwardedHeader::Forwarded,
+        )
+        .get("/")
+        .header(FORWARDED, "for=1.2.3.4, for=unknown")
+        .send()
+        .await;
+        assert_eq!(res.text().await, "10.0.0.1");
+    }
+
+    #[tokio::test]
+    async fn forged_forwarded_header_is_ignored() {
+        // the proxy appends to `X-Forwarded-For`, so `Forwarded` comes from
+        // the client
+        let res = test_client("10.0.0.1:1234", Some(&["10.0.0.0/8"]))
+            .get("/")
+            .header(FORWARDED, "for=6.6.6.6")
+            .header(X_FORWARDED_FOR_HEADER_KEY, "1.2.3.4")
+            .send()
+            .await;
+        assert_eq!(res.text().await, "1.2.3.4");
+    }
+
+    #[tokio::test]
+    async fn forged_x_forwarded_for_header_is_ignored() {
+        let res = test_client_with_header(
+            "10.0.0.1:1234",
+            Some(&["10.0.0.0/8"]),
+            ForwardedHeader::Forwarded,
+        )
+        .get("/")
+        .header(FORWARDED, "for=1.2.3.4")
+        .header(X_FORWARDED_FOR_HEADER_KEY, "6.6.6.6")
+        .send()
+        .await;
+        assert_eq!(res.text().await, "1.2.3.4");
+    }
+
+    #[tokio::test]
+    async fn ipv4_mapped_peer() {
+        let res = test_client("[::ffff:10.0.0.1]:1234", Some(&["10.0.0.0/8"]))
+            .get("/")
+            .header(X_FORWARDED_FOR_HEADER_KEY, "1.2.3.4:5678")
+            .send()
+            .await;
+        assert_eq!(res.text().await, "1.2.3.4");
+    }
+
+    #[tokio::test]
+    async fn missing_connect_info() {
+        async fn handler(_: ClientIp) {}
+
+        let client = TestClient::new(Router::new().route("/", get(handler)));
+        let res = client.get("/").send().await;
+        assert_eq!(res.status(), http::StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn parsing_cidrs() {
+        let proxies = TrustedProxies::new()
+            .trust("192.168.0.0/16")
+            .unwrap()
+            .trust("0.0.0.0/0")
+            .unwrap();
+        assert!(proxies.is_trusted("1.2.3.4".parse().unwrap()));
+        assert!(!proxies.is_trusted("::2".parse().unwrap()));
+
+        assert!(TrustedProxies::new().trust("10.0.0.0/33").is_err());
+        assert!(TrustedProxies::new().trust("::/129").is_err());
+        assert!(TrustedProxies::new().trust("not an ip").is_err());
+        assert!(TrustedProxies::new().trust("10.0.0.0/").is_err());
+    }
+}
@@ -0,0 +1,376 @@
+//! Extractor for conditional request headers.
+//!
+//! See [`Conditionals`] for more details.
+
+use super::{FromRequest, RequestParts};
+use async_trait::async_trait;
+use http::header::{HeaderMap, IF_MODIFIED_SINCE, IF_NONE_MATCH};
+use std::{
+    convert::Infallible,
+    fmt,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Extractor that parses the `If-None-Match` and `If-Modified-Since` headers
+/// of conditional requests.
+///
+/// Use [`Conditionals::is_not_modified`] to decide whether to respond with
+/// `304 Not Modified` for the current version of a resource.
+///
+/// Headers that can't be parsed are ignored, as required by [RFC 7232], so
+/// this extractor never fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::{Conditionals, ETag},
+///     http::{header, StatusCode},
+///     response::{IntoResponse, Response},
+///     routing::get,
+///     Router,
+/// };
+///
+/// async fn handler(conditionals: Conditionals) -> Response {
+///     let etag = ETag::strong("v42");
+///
+///     if conditionals.is_not_modified(Some(&etag), None) {
+///         return StatusCode::NOT_MODIFIED.into_response();
+///     }
+///
+///     ([(header::ETAG, etag.to_string())], "the resource").into_response()
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+///
+/// [RFC 7232]: https://datatracker.ietf.org/doc/html/rfc7232
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Conditionals {
+    if_none_match: Option<IfNoneMatch>,
+    if_modified_since: Option<SystemTime>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum IfNoneMatch {
+    Any,
+    Tags(Vec<ETag>),
+}
+
+impl Conditionals {
+    /// Parse the conditional request headers in a [`HeaderMap`].
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let if_none_match = parse_if_none_match(headers);
+
+        let if_modified_since = headers
+            .get(IF_MODIFIED_SINCE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| httpdate::parse_http_date(value).ok());
+
+        Self {
+            if_none_match,
+            if_modified_since,
+        }
+    }
+
+    /// Get the entity tags from the `If-None-Match` header.
+    ///
+    /// Returns `None` if the header is missing, invalid, or `*`.
+    pub fn if_none_match(&self) -> Option<&[ETag]> {
+        match &self.if_none_match {
+            Some(IfNoneMatch::Tags(tags)) => Some(tags),
+            _ => None,
+        }
+    }
+
+    /// Whether the `If-None-Match` header is `*`, which matches any version of
+    /// the resource.
+    pub fn if_none_match_any(&self) -> bool {
+        self.if_none_match == Some(IfNoneMatch::Any)
+    }
+
+    /// Get the date from the `If-Modified-Since` header.
+    ///
+    /// Returns `None` if the header is missing or isn't a valid HTTP date.
+    pub fn if_modified_since(&self) -> Option<SystemTime> {
+        self.if_modified_since
+    }
+
+    /// Whether a `GET` or `HEAD` request should get a `304 Not Modified`
+    /// response, given the current entity tag and last modification time of
+    /// the resource.
+    ///
+    /// If the request has an `If-None-Match` header the entity tags are
+    /// compared using the weak comparison and `If-Modified-Since` is ignored.
+    /// Otherwise the resource is not modified if it was last modified at or
+    /// before the `If-Modified-Since` date, at a precision of one second.
+    /// Returns `false` if the request isn't conditional or the needed
+    /// information about the resource isn't given.
+    pub fn is_not_modified(&self, etag: Option<&ETag>, last_modified: Option<SystemTime>) -> bool {
+        match (&self.if_none_match, self.if_modified_since) {
+            (Some(IfNoneMatch::Any), _) => true,
+            (Some(IfNoneMatch::Tags(tags)), _) => match etag {
+                Some(etag) => tags.iter().any(|tag| tag.weak_eq(etag)),
+                None => false,
+            },
+            (None, Some(since)) => match last_modified {
+                Some(last_modified) => unix_secs(last_modified) <= unix_secs(since),
+                None => false,
+            },
+            (None, None) => false,
+        }
+    }
+}
+
+#[async_trait]
+impl<B> FromRequest<B> for Conditionals
+where
+    B: Send,
+{
+    type Rejection = Infallible;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(req.headers()))
+    }
+}
+
+fn parse_if_none_match(headers: &HeaderMap) -> Option<IfNoneMatch> {
+    let mut tags = Vec::new();
+    let mut found = false;
+
+    for value in headers.get_all(IF_NONE_MATCH) {
+        found = true;
+        let value = value.to_str().ok()?;
+        if value.trim() == "*" {
+            return Some(IfNoneMatch::Any);
+        }
+        tags.extend(parse_etag_list(value)?);
+    }
+
+    found.then(|| IfNoneMatch::Tags(tags))
+}
+
+/// Parse a comma separated list of entity tags.
+///
+/// Returns `None` if any of the tags is invalid.
+fn parse_etag_list(mut s: &str) -> Option<Vec<ETag>> {
+    let mut tags = Vec::new();
+
+    loop {
+        s = s.trim_start_matches(|c: char| c == ',' || c == ' ' || c == '\t');
+        if s.is_empty() {
+            return Some(tags);
+        }
+
+        let (tag, rest) = split_etag(s)?;
+        tags.push(tag);
+
+        s = rest.trim_start_matches(|c: char| c == ' ' || c == '\t');
+        if !s.is_empty() && !s.starts_with(',') {
+            return None;
+        }
+    }
+}
+
+/// Split the entity tag at the start of `s` from the rest of the string.
+fn split_etag(s: &str) -> Option<(ETag, &str)> {
+    let (weak, s) = match s.strip_prefix("W/") {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+
+    let s = s.strip_prefix('"')?;
+    let end = s.find('"')?;
+    let tag = &s[..end];
+
+    if !tag.bytes().all(is_etagc) {
+        return None;
+    }
+
+    let etag = ETag {
+        tag: tag.to_owned(),
+        weak,
+    };
+    Some((etag, &s[end + 1..]))
+}
+
+fn is_etagc(b: u8) -> bool {
+    b == 0x21 || (0x23..=0x7e).contains(&b) || b >= 0x80
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/// An entity tag, as used by the `ETag` and `If-None-Match` headers.
+///
+/// The `Display` implementation formats the tag as it appears in headers,
+/// such as `"v1"` or `W/"v1"`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ETag {
+    tag: String,
+    weak: bool,
+}
+
+impl ETag {
+    /// Create a strong entity tag.
+    ///
+    /// # Panics
+    ///
+    /// If `tag` contains characters not allowed in entity tags, such as `"`.
+    pub fn strong(tag: impl Into<String>) -> Self {
+        Self::new(tag.into(), false)
+    }
+
+    /// Create a weak entity tag.
+    ///
+    /// # Panics
+    ///
+    /// If `tag` contains characters not allowed in entity tags, such as `"`.
+    pub fn weak(tag: impl Into<String>) -> Self {
+        Self::new(tag.into(), true)
+    }
+
+    fn new(tag: String, weak: bool) -> Self {
+        if !tag.bytes().all(is_etagc) {
+            panic!("Invalid entity tag: {:?}", tag);
+        }
+        Self { tag, weak }
+    }
+
+    /// Get the opaque tag, without quotes.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Whether this is a weak entity tag.
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Compare two entity tags using the strong comparison, where both tags
+    /// must be strong and have the same opaque tag.
+    pub fn strong_eq(&self, other: &ETag) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// Compare two entity tags using the weak comparison, where only the
+    /// opaque tags must be the same.
+    pub fn weak_eq(&self, other: &ETag) -> bool {
+        self.tag == other.tag
+    }
+}
+
+impl fmt::Display for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weak {
+            write!(f, "W/\"{}\"", self.tag)
+        } else {
+            write!(f, "\"{}\"", self.tag)
+        }
+    }
+}
+
+impl FromStr for ETag {
+    type Err = InvalidETag;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match split_etag(s.trim()) {
+            Some((etag, "")) => Ok(etag),
+            _ => Err(InvalidETag { _priv: () }),
+        }
+    }
+}
+
+/// Error returned when parsing an [`ETag`] fails.
+#[derive(Debug)]
+pub struct InvalidETag {
+    _priv: (),
+}
+
+impl fmt::Display for InvalidETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid entity tag")
+    }
+}
+
+impl std::error::Error for InvalidETag {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+    use std::time::Duration;
+
+    fn conditionals(headers: &[(&'static str, &'static str)]) -> Conditionals {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            map.append(*name, HeaderValue::from_static(value));
+        }
+        Conditionals::from_headers(&map)
+    }
+
+    #[test]
+    fn parsing_etags() {
+        let c = conditionals(&[
+            ("if-none-match", r#""a", W/"b""#),
+            ("if-none-match", r#""c,d""#),
+        ]);
+        assert_eq!(
+            c.if_none_match().unwrap(),
+            [ETag::strong("a"), ETag::weak("b"), ETag::strong("c,d")]
+        );
+
+        assert!(conditionals(&[("if-none-match", "*")]).if_none_match_any());
+        assert!(conditionals(&[("if-none-match", "a")])
+            .if_none_match()
+            .is_none());
+        assert!(conditionals(&[("if-none-match", r#""a" "b""#)])
+            .if_none_match()
+            .is_none());
+
+        assert_eq!("W/\"x\"".parse::<ETag>().unwrap(), ETag::weak("x"));
+        assert_eq!(ETag::weak("x").to_string(), "W/\"x\"");
+        assert!("\"x".parse::<ETag>().is_err());
+    }
+
+    #[test]
+    fn not_modified_by_etag() {
+        let c = conditionals(&[
+            ("if-none-match", r#"W/"a", "b""#),
+            // ignored since `If-None-Match` is present
+            ("if-modified-since", "Sun, 06 Nov 1994 08:49:37 GMT"),
+        ]);
+
+        assert!(c.is_not_modified(Some(&ETag::strong("a")), None));
+        assert!(c.is_not_modified(Some(&ETag::weak("b")), None));
+        assert!(!c.is_not_modified(Some(&ETag::strong("c")), Some(UNIX_EPOCH)));
+        assert!(!c.is_not_modified(None, Some(UNIX_EPOCH)));
+    }
+
+    #[test]
+    fn not_modified_by_date() {
+        let c = conditionals(&[("if-modified-since", "Sun, 06 Nov 1994 08:49:37 GMT")]);
+        let since = UNIX_EPOCH + Duration::from_secs(784111777);
+
+        assert_eq!(c.if_modified_since(), Some(since));
+        assert!(c.is_not_modified(None, Some(since + Duration::from_millis(500))));
+        assert!(c.is_not_modified(None, Some(since - Duration::from_secs(1))));
+        assert!(!c.is_not_modified(None, Some(since + Duration::from_secs(1))));
+        assert!(!c.is_not_modified(Some(&ETag::strong("a")), None));
+    }
+
+    #[test]
+    fn malformed_date_is_ignored() {
+        let c = conditionals(&[("if-modified-since", "yesterday")]);
+
+        assert_eq!(c, Conditionals::default());
+        assert!(!c.is_not_modified(None, Some(UNIX_EPOCH)));
+    }
+}
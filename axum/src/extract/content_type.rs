@@ -0,0 +1,181 @@
+//! Extractor that requires a specific `Content-Type`.
+//!
+//! See [`RequireContentType`] for more details.
+
+use super::{rejection::UnsupportedContentType, FromRequest, RequestParts};
+use async_trait::async_trait;
+use http::header::CONTENT_TYPE;
+use std::{fmt, marker::PhantomData};
+
+/// Extractor that rejects requests that don't have a specific `Content-Type`.
+///
+/// The required content type is given by a type that implements [`MediaType`].
+/// Parameters such as `charset` are ignored when comparing content types.
+///
+/// If the `Content-Type` header is missing or doesn't match this will reject
+/// the request with `415 Unsupported Media Type` before any later extractors
+/// run. This makes it possible to enforce the content type before parsing the
+/// request body.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     body::Bytes,
+///     extract::content_type::{ApplicationJson, RequireContentType},
+///     routing::post,
+///     Router,
+/// };
+///
+/// async fn handler(_: RequireContentType<ApplicationJson>, body: Bytes) {
+///     // `body` is only extracted for requests with `Content-Type: application/json`
+/// }
+///
+/// let app = Router::new().route("/", post(handler));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+///
+/// Custom media types can be required by implementing [`MediaType`]:
+///
+/// ```rust
+/// use axum::extract::content_type::{MediaType, RequireContentType};
+///
+/// struct Csv;
+///
+/// impl MediaType for Csv {
+///     const MEDIA_TYPE: &'static str = "text/csv";
+/// }
+///
+/// async fn handler(_: RequireContentType<Csv>, body: String) {
+///     // ...
+/// }
+/// ```
+pub struct RequireContentType<M>(PhantomData<M>);
+
+#[async_trait]
+impl<B, M> FromRequest<B> for RequireContentType<M>
+where
+    B: Send,
+    M: MediaType,
+{
+    type Rejection = UnsupportedContentType;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let matches = req
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .and_then(|content_type| content_type.parse::<mime::Mime>().ok())
+            .map_or(false, |mime| {
+                mime.essence_str().eq_ignore_ascii_case(M::MEDIA_TYPE)
+            });
+
+        if matches {
+            Ok(Self(PhantomData))
+        } else {
+            Err(UnsupportedContentType::new(M::MEDIA_TYPE))
+        }
+    }
+}
+
+impl<M> fmt::Debug for RequireContentType<M>
+where
+    M: MediaType,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RequireContentType")
+            .field(&M::MEDIA_TYPE)
+            .finish()
+    }
+}
+
+impl<M> Clone for RequireContentType<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for RequireContentType<M> {}
+
+/// A media type that can be required with [`RequireContentType`].
+pub trait MediaType: Send + 'static {
+    /// The media type, without any parameters, such as `application/json`.
+    const MEDIA_TYPE: &'static str;
+}
+
+macro_rules! media_types {
+    ( $( $(#[$m:meta])* $name:ident => $media_type:literal, )* ) => {
+        $(
+            $(#[$m])*
+            #[derive(Debug, Clone, Copy)]
+            pub struct $name;
+
+            impl MediaType for $name {
+                const MEDIA_TYPE: &'static str = $media_type;
+            }
+        )*
+    };
+}
+
+media_types! {
+    /// The `application/json` media type.
+    ApplicationJson => "application/json",
+    /// The `application/x-www-form-urlencoded` media type.
+    FormUrlEncoded => "application/x-www-form-urlencoded",
+    /// The `multipart/form-data` media type.
+    MultipartFormData => "multipart/form-data",
+    /// The `application/octet-stream` media type.
+    OctetStream => "application/octet-stream",
+    /// The `text/plain` media type.
+    TextPlain => "text/plain",
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{routing::post, test_helpers::TestClient, Router};
+    use http::StatusCode;
+
+    #[tokio::test]
+    async fn requires_content_type() {
+        async fn handler(_: RequireContentType<ApplicationJson>, body: String) -> String {
+            body
+        }
+
+        let client = TestClient::new(Router::new().route("/", post(handler)));
+
+        let res = client
+            .post("/")
+            .header(CONTENT_TYPE, "application/json")
+            .body("{}")
+            .send()
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "{}");
+
+        let res = client
+            .post("/")
+            .header(CONTENT_TYPE, "Application/JSON; charset=utf-8")
+            .body("{}")
+            .send()
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = client
+            .post("/")
+            .header(CONTENT_TYPE, "text/plain")
+            .body("{}")
+            .send()
+            .await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(
+            res.text().await,
+            "Expected request with `Content-Type: application/json`"
+        );
+
+        let res = client.post("/").body("{}").send().await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+}
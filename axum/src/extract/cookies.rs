@@ -0,0 +1,188 @@
+use super::{FromRequest, RequestParts};
+use async_trait::async_trait;
+use http::header::{HeaderMap, COOKIE};
+use std::{collections::HashMap, convert::Infallible};
+
+/// Extractor that parses the `Cookie` header into a map of cookie names to
+/// values.
+///
+/// This only provides read access to the cookies sent by the client. It
+/// doesn't support setting cookies, nor signing or encrypting them. For that
+/// see [`axum-extra`]'s `CookieJar`.
+///
+/// Cookies from all `Cookie` headers are included, as HTTP/2 clients are
+/// allowed to split cookies over several headers. Values surrounded by double
+/// quotes have the quotes removed. If the same name is sent multiple times the
+/// first value is used. Pairs that aren't valid are ignored.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::Cookies,
+///     routing::get,
+///     Router,
+/// };
+///
+/// async fn handler(cookies: Cookies) -> String {
+///     match cookies.get("session_id") {
+///         Some(session_id) => format!("Your session is {}", session_id),
+///         None => "You're not logged in".to_owned(),
+///     }
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+///
+/// [`axum-extra`]: https://docs.rs/axum-extra
+#[derive(Debug, Clone, Default)]
+pub struct Cookies {
+    cookies: HashMap<String, String>,
+}
+
+impl Cookies {
+    /// Parse the cookies from all `Cookie` headers in a [`HeaderMap`].
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let mut cookies = HashMap::new();
+
+        let pairs = headers
+            .get_all(COOKIE)
+            .into_iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(';'));
+
+        for pair in pairs {
+            if let Some((name, value)) = parse_cookie_pair(pair) {
+                cookies
+                    .entry(name.to_owned())
+                    .or_insert_with(|| value.to_owned());
+            }
+        }
+
+        Self { cookies }
+    }
+
+    /// Get the value of the cookie with the given name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.cookies.get(name).map(|value| &**value)
+    }
+
+    /// Returns `true` if a cookie with the given name was sent.
+    pub fn contains(&self, name: &str) -> bool {
+        self.cookies.contains_key(name)
+    }
+
+    /// Get an iterator over all cookie names and values, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.cookies.iter().map(|(name, value)| (&**name, &**value))
+    }
+
+    /// Returns the number of cookies.
+    pub fn len(&self) -> usize {
+        self.cookies.len()
+    }
+
+    /// Returns `true` if no cookies were sent.
+    pub fn is_empty(&self) -> bool {
+        self.cookies.is_empty()
+    }
+
+    /// Consume the `Cookies` and return the underlying map.
+    pub fn into_inner(self) -> HashMap<String, String> {
+        self.cookies
+    }
+}
+
+#[async_trait]
+impl<B> FromRequest<B> for Cookies
+where
+    B: Send,
+{
+    type Rejection = Infallible;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(req.headers()))
+    }
+}
+
+fn parse_cookie_pair(pair: &str) -> Option<(&str, &str)> {
+    let (name, value) = pair.split_once('=')?;
+
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let value = value.trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value);
+
+    Some((name, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{routing::get, test_helpers::TestClient, Router};
+
+    fn cookies(values: &[&str]) -> Cookies {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append(COOKIE, value.parse().unwrap());
+        }
+        Cookies::from_headers(&headers)
+    }
+
+    #[test]
+    fn parsing() {
+        let jar = cookies(&["a=1; b=2;c=3"]);
+        assert_eq!(jar.len(), 3);
+        assert_eq!(jar.get("a"), Some("1"));
+        assert_eq!(jar.get("b"), Some("2"));
+        assert_eq!(jar.get("c"), Some("3"));
+        assert_eq!(jar.get("d"), None);
+    }
+
+    #[test]
+    fn quoted_values() {
+        let jar = cookies(&[r#"a="quoted value""#, r#"b=""#, r#"c="""#]);
+        assert_eq!(jar.get("a"), Some("quoted value"));
+        assert_eq!(jar.get("b"), Some("\""));
+        assert_eq!(jar.get("c"), Some(""));
+    }
+
+    #[test]
+    fn multiple_headers() {
+        let jar = cookies(&["a=1", "b=2; a=3"]);
+        assert_eq!(jar.get("a"), Some("1"));
+        assert_eq!(jar.get("b"), Some("2"));
+    }
+
+    #[test]
+    fn invalid_pairs_are_ignored() {
+        let jar = cookies(&["no-equals; =no-name; ok=yes; empty="]);
+        assert_eq!(jar.len(), 2);
+        assert_eq!(jar.get("ok"), Some("yes"));
+        assert_eq!(jar.get("empty"), Some(""));
+    }
+
+    #[tokio::test]
+    async fn extracting() {
+        async fn handler(cookies: Cookies) -> String {
+            cookies.get("foo").unwrap_or("missing").to_owned()
+        }
+
+        let client = TestClient::new(Router::new().route("/", get(handler)));
+
+        let res = client.get("/").header("cookie", "foo=bar").send().await;
+        assert_eq!(res.text().await, "bar");
+
+        let res = client.get("/").send().await;
+        assert_eq!(res.text().await, "missing");
+    }
+}
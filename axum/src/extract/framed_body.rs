@@ -0,0 +1,269 @@
+use super::{rejection::*, BodyStream, FromRequest, RequestParts};
+use crate::{BoxError, Error};
+use async_trait::async_trait;
+use bytes::{Buf, Bytes, BytesMut};
+use futures_util::{ready, stream::Stream};
+use http_body::Body as HttpBody;
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+const DEFAULT_MAX_FRAME_SIZE: usize = 1024 * 1024;
+
+/// Extractor that decodes the request body as a [`Stream`] of length-prefixed
+/// frames.
+///
+/// Each frame is a 4 byte big-endian unsigned length followed by that many
+/// bytes of payload. Frames are decoded as the body is received, so only the
+/// frame currently being received is buffered, and the stream yields the
+/// payloads without the length prefix.
+///
+/// The stream yields an error, and then ends, if
+///
+/// - a frame is larger than the maximum frame size, 1 MiB by default, which
+///   can be changed with [`FramedBody::max_frame_size`],
+/// - the body ends in the middle of a frame, or
+/// - reading the body fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::FramedBody,
+///     routing::post,
+///     Router,
+/// };
+/// use futures::StreamExt;
+///
+/// async fn handler(frames: FramedBody) {
+///     let mut frames = frames.max_frame_size(64 * 1024);
+///
+///     while let Some(frame) = frames.next().await {
+///         match frame {
+///             Ok(frame) => {
+///                 // ...
+///             }
+///             Err(err) => {
+///                 // ...
+///             }
+///         }
+///     }
+/// }
+///
+/// let app = Router::new().route("/messages", post(handler));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+///
+/// [`Stream`]: https://docs.rs/futures/latest/futures/stream/trait.Stream.html
+pub struct FramedBody {
+    body: BodyStream,
+    buf: BytesMut,
+    max_frame_size: usize,
+    done: bool,
+}
+
+impl FramedBody {
+    /// Set the maximum size of a frame's payload, in bytes.
+    ///
+    /// Defaults to 1 MiB.
+    pub fn max_frame_size(mut self, bytes: usize) -> Self {
+        self.max_frame_size = bytes;
+        self
+    }
+
+    /// Decode the next frame from the buffered data, if it has been received
+    /// completely.
+    fn decode(&mut self) -> Option<Result<Bytes, Error>> {
+        if self.buf.len() < 4 {
+            return None;
+        }
+
+        let len = u32::from_be_bytes([self.buf[0], self.buf[1], self.buf[2], self.buf[3]]) as usize;
+        if len > self.max_frame_size {
+            return Some(Err(self.fail(FrameTooLarge {
+                max: self.max_frame_size,
+            })));
+        }
+
+        if self.buf.len() < 4 + len {
+            self.buf.reserve(4 + len - self.buf.len());
+            return None;
+        }
+
+        self.buf.advance(4);
+        Some(Ok(self.buf.split_to(len).freeze()))
+    }
+
+    fn fail<E>(&mut self, err: E) -> Error
+    where
+        E: Into<BoxError>,
+    {
+        self.done = true;
+        self.buf.clear();
+        Error::new(err)
+    }
+}
+
+impl Stream for FramedBody {
+    type Item = Result<Bytes, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        loop {
+            if let Some(frame) = this.decode() {
+                return Poll::Ready(Some(frame));
+            }
+
+            if this.done {
+                if this.buf.is_empty() {
+                    return Poll::Ready(None);
+                }
+                return Poll::Ready(Some(Err(this.fail(IncompleteFrame))));
+            }
+
+            match ready!(Pin::new(&mut this.body).poll_next(cx)) {
+                Some(Ok(chunk)) => this.buf.extend_from_slice(&chunk),
+                Some(Err(err)) => {
+                    this.done = true;
+                    this.buf.clear();
+                    return Poll::Ready(Some(Err(err)));
+                }
+                None => this.done = true,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<B> FromRequest<B> for FramedBody
+where
+    B: HttpBody + Send + 'static,
+    B::Data: Into<Bytes>,
+    B::Error: Into<BoxError>,
+{
+    type Rejection = BodyAlreadyExtracted;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let body = BodyStream::from_request(req).await?;
+
+        Ok(Self {
+            body,
+            buf: BytesMut::new(),
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+            done: false,
+        })
+    }
+}
+
+impl fmt::Debug for FramedBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FramedBody")
+            .field("max_frame_size", &self.max_frame_size)
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+struct FrameTooLarge {
+    max: usize,
+}
+
+impl fmt::Display for FrameTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Frame is larger than the maximum of {} bytes", self.max)
+    }
+}
+
+impl std::error::Error for FrameTooLarge {}
+
+#[derive(Debug)]
+struct IncompleteFrame;
+
+impl fmt::Display for IncompleteFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Body ended in the middle of a frame")
+    }
+}
+
+impl std::error::Error for IncompleteFrame {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{routing::post, test_helpers::*, Router};
+    use futures_util::StreamExt;
+
+    #[test]
+    fn framed_body_traits() {
+        assert_send::<FramedBody>();
+        assert_sync::<FramedBody>();
+    }
+
+    fn frame(payload: &[u8]) -> Vec<u8> {
+        let mut frame = (payload.len() as u32).to_be_bytes().to_vec();
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    async fn handler(frames: FramedBody) -> String {
+        let frames = frames.max_frame_size(8).collect::<Vec<_>>().await;
+        frames
+            .into_iter()
+            .map(|frame| match frame {
+                Ok(frame) => String::from_utf8(frame.to_vec()).unwrap(),
+                Err(err) => format!("error: {}", err),
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    #[tokio::test]
+    async fn decodes_frames_across_chunks() {
+        let client = TestClient::new(Router::new().route("/", post(handler)));
+
+        let mut body = frame(b"one");
+        body.extend(frame(b""));
+        body.extend(frame(b"three"));
+
+        // send the body in small chunks so frames and length prefixes are
+        // split
+        let chunks = body
+            .chunks(2)
+            .map(|chunk| Ok::<_, BoxError>(chunk.to_vec()))
+            .collect::<Vec<_>>();
+        let res = client
+            .post("/")
+            .body(reqwest::Body::wrap_stream(futures_util::stream::iter(
+                chunks,
+            )))
+            .send()
+            .await;
+        assert_eq!(res.text().await, "one,,three");
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        let client = TestClient::new(Router::new().route("/", post(handler)));
+
+        let mut body = frame(b"ok");
+        body.extend(frame(b"too large!"));
+        let res = client.post("/").body(body).send().await;
+        assert_eq!(
+            res.text().await,
+            "ok,error: Frame is larger than the maximum of 8 bytes"
+        );
+
+        let mut body = frame(b"ok");
+        body.extend(&frame(b"cut off")[..6]);
+        let res = client.post("/").body(body).send().await;
+        assert_eq!(
+            res.text().await,
+            "ok,error: Body ended in the middle of a frame"
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use super::{rejection::InvalidHeaderList, FromRequest, RequestParts};
+use async_trait::async_trait;
+use std::{fmt, marker::PhantomData, ops::Deref};
+
+/// A header that can be extracted with [`HeaderList`].
+///
+/// Implement this for a marker type to name the header.
+pub trait ListHeader {
+    /// The name of the header, such as `"x-forwarded-for"`.
+    const NAME: &'static str;
+
+    /// Whether the values are comma-separated lists.
+    ///
+    /// Defaults to `true`, which is the case for most headers that can have
+    /// several values. Set it to `false` for headers whose values can contain
+    /// commas that don't separate values.
+    const SPLIT_COMMAS: bool = true;
+}
+
+/// Extractor that gets all values of a header.
+///
+/// Headers with several values can be sent either as repeated headers or as a
+/// single comma-separated list, and both forms mean the same thing. This
+/// extractor combines all occurrences of the header named by
+/// [`ListHeader::NAME`] and splits them on commas, so
+///
+/// ```text
+/// Accept-Language: de, en;q=0.8
+/// Accept-Language: fr
+/// ```
+///
+/// gives `["de", "en;q=0.8", "fr"]`. Whitespace around values is removed,
+/// empty values are skipped and commas inside quoted strings don't separate
+/// values. Splitting can be turned off with [`ListHeader::SPLIT_COMMAS`].
+///
+/// If the header is missing the list is empty. Requests where a value of the
+/// header isn't visible ASCII are rejected with `400 Bad Request`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::{HeaderList, ListHeader},
+///     routing::get,
+///     Router,
+/// };
+///
+/// struct AcceptLanguage;
+///
+/// impl ListHeader for AcceptLanguage {
+///     const NAME: &'static str = "accept-language";
+/// }
+///
+/// async fn handler(languages: HeaderList<AcceptLanguage>) -> String {
+///     languages.join(" ")
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+pub struct HeaderList<H> {
+    values: Vec<String>,
+    _header: PhantomData<fn() -> H>,
+}
+
+impl<H> HeaderList<H> {
+    /// Get the values of the header.
+    pub fn into_inner(self) -> Vec<String> {
+        self.values
+    }
+}
+
+impl<H> Deref for HeaderList<H> {
+    type Target = [String];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+impl<H> Clone for HeaderList<H> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            _header: PhantomData,
+        }
+    }
+}
+
+impl<H> fmt::Debug for HeaderList<H>
+where
+    H: ListHeader,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HeaderList")
+            .field("name", &H::NAME)
+            .field("values", &self.values)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<H, B> FromRequest<B> for HeaderList<H>
+where
+    H: ListHeader,
+    B: Send,
+{
+    type Rejection = InvalidHeaderList;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let mut values = Vec::new();
+
+        for value in req.headers().get_all(H::NAME) {
+            let value = value.to_str().map_err(|_| InvalidHeaderList)?;
+
+            if H::SPLIT_COMMAS {
+                values.extend(split_list(value).map(str::to_owned));
+            } else if !value.trim().is_empty() {
+                values.push(value.trim().to_owned());
+            }
+        }
+
+        Ok(Self {
+            values,
+            _header: PhantomData,
+        })
+    }
+}
+
+/// Split a comma-separated header value, ignoring commas in quoted strings.
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    let mut in_quotes = false;
+    let mut escaped = false;
+    let mut start = 0;
+    let mut elements = Vec::new();
+
+    for (idx, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => {
+                elements.push(&value[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    elements.push(&value[start..]);
+
+    elements
+        .into_iter()
+        .map(str::trim)
+        .filter(|element| !element.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{routing::get, test_helpers::*, Router};
+    use http::StatusCode;
+
+    #[test]
+    fn splitting() {
+        let split = |value| split_list(value).collect::<Vec<_>>();
+
+        assert_eq!(split("a"), ["a"]);
+        assert_eq!(split(" a , b,,c "), ["a", "b", "c"]);
+        assert_eq!(split(r#"a="x, y", b"#), [r#"a="x, y""#, "b"]);
+        assert_eq!(split(r#""x\", y", z"#), [r#""x\", y""#, "z"]);
+        assert!(split(" , ").is_empty());
+    }
+
+    struct AcceptLanguage;
+
+    impl ListHeader for AcceptLanguage {
+        const NAME: &'static str = "accept-language";
+    }
+
+    struct UserAgent;
+
+    impl ListHeader for UserAgent {
+        const NAME: &'static str = "user-agent";
+        const SPLIT_COMMAS: bool = false;
+    }
+
+    #[tokio::test]
+    async fn header_list() {
+        let app = Router::new()
+            .route(
+                "/languages",
+                get(|languages: HeaderList<AcceptLanguage>| async move { languages.join("|") }),
+            )
+            .route(
+                "/user-agent",
+                get(|agents: HeaderList<UserAgent>| async move { agents.join("|") }),
+            );
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/languages")
+            .header("accept-language", "de, en;q=0.8")
+            .header("accept-language", "fr")
+            .send()
+            .await;
+        assert_eq!(res.text().await, "de|en;q=0.8|fr");
+
+        let res = client.get("/languages").send().await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "");
+
+        let res = client
+            .get("/user-agent")
+            .header("user-agent", "agent (a, b)")
+            .send()
+            .await;
+        assert_eq!(res.text().await, "agent (a, b)");
+
+        let res = client
+            .get("/languages")
+            .header("accept-language", "d\u{e9}")
+            .send()
+            .await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+}
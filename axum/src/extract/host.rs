@@ -3,7 +3,10 @@ use super::{
     FromRequest, RequestParts,
 };
 use async_trait::async_trait;
-use http::header::{HeaderMap, FORWARDED};
+use http::{
+    header::{HeaderMap, FORWARDED},
+    Uri,
+};
 
 const X_FORWARDED_HOST_HEADER_KEY: &str = "X-Forwarded-Host";
 
@@ -28,32 +31,33 @@ where
     type Rejection = HostRejection;
 
     async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
-        if let Some(host) = parse_forwarded(req.headers()) {
-            return Ok(Host(host.to_owned()));
-        }
-
-        if let Some(host) = req
-            .headers()
-            .get(X_FORWARDED_HOST_HEADER_KEY)
-            .and_then(|host| host.to_str().ok())
-        {
-            return Ok(Host(host.to_owned()));
-        }
+        resolve_host(req.headers(), req.uri())
+            .map(|host| Host(host.to_owned()))
+            .ok_or(HostRejection::FailedToResolveHost(FailedToResolveHost))
+    }
+}
 
-        if let Some(host) = req
-            .headers()
-            .get(http::header::HOST)
-            .and_then(|host| host.to_str().ok())
-        {
-            return Ok(Host(host.to_owned()));
-        }
+/// Resolve the host of a request the same way as [`Host`].
+pub(crate) fn resolve_host<'a>(headers: &'a HeaderMap, uri: &'a Uri) -> Option<&'a str> {
+    if let Some(host) = parse_forwarded(headers) {
+        return Some(host);
+    }
 
-        if let Some(host) = req.uri().host() {
-            return Ok(Host(host.to_owned()));
-        }
+    if let Some(host) = headers
+        .get(X_FORWARDED_HOST_HEADER_KEY)
+        .and_then(|host| host.to_str().ok())
+    {
+        return Some(host);
+    }
 
-        Err(HostRejection::FailedToResolveHost(FailedToResolveHost))
+    if let Some(host) = headers
+        .get(http::header::HOST)
+        .and_then(|host| host.to_str().ok())
+    {
+        return Some(host);
     }
+
+    uri.host()
 }
 
 #[allow(warnings)]
@@ -3,36 +3,68 @@
 use http::header;
 use rejection::*;
 
+pub mod api_version;
+pub mod client_ip;
+pub mod conditionals;
 pub mod connect_info;
+pub mod content_type;
 pub mod path;
+pub mod range;
+pub mod rate_limit;
 pub mod rejection;
+pub mod tls;
 
 #[cfg(feature = "ws")]
 pub mod ws;
 
 mod content_length_limit;
+mod cookies;
+mod framed_body;
+mod header_list;
 mod host;
 mod raw_query;
+mod raw_request_target;
 mod request_parts;
+mod scheme;
+mod timed;
 
 #[doc(inline)]
-pub use axum_core::extract::{FromRequest, RequestParts};
+pub use axum_core::extract::{FromRequest, RequestParts, Trailers};
 
 #[doc(inline)]
 #[allow(deprecated)]
 pub use self::{
+    api_version::ApiVersion,
+    client_ip::ClientIp,
+    conditionals::{Conditionals, ETag},
     connect_info::ConnectInfo,
     content_length_limit::ContentLengthLimit,
+    content_type::RequireContentType,
+    cookies::Cookies,
+    framed_body::FramedBody,
+    header_list::{HeaderList, ListHeader},
     host::Host,
-    path::Path,
+    path::{ParsedPath, Path, PathAndTail, RawPath},
+    range::Range,
+    rate_limit::RateLimited,
     raw_query::RawQuery,
-    request_parts::{BodyStream, RawBody},
+    raw_request_target::{RawRequestTarget, RequestTargetForm},
+    request_parts::{BodySizeHint, BodyStream, RawBody},
+    scheme::Scheme,
+    timed::{ExtractionTimings, Timed},
+    tls::{PeerCertificate, TlsInfo},
 };
 
+pub(crate) use self::host::resolve_host;
+
 #[doc(no_inline)]
 #[cfg(feature = "json")]
 pub use crate::Json;
 
+#[doc(inline)]
+#[cfg(feature = "json")]
+pub use crate::json::BoundedJson;
+
 #[doc(no_inline)]
 pub use crate::Extension;
 
@@ -40,6 +72,9 @@ pub use crate::Extension;
 #[doc(no_inline)]
 pub use crate::form::Form;
 
+#[cfg(feature = "form")]
+pub use crate::form::FormOrQuery;
+
 #[cfg(feature = "matched-path")]
 mod matched_path;
 
@@ -47,6 +82,13 @@ mod matched_path;
 #[doc(inline)]
 pub use self::matched_path::MatchedPath;
 
+#[cfg(feature = "authorization")]
+pub mod authorization;
+
+#[cfg(feature = "authorization")]
+#[doc(inline)]
+pub use self::authorization::Authorization;
+
 #[cfg(feature = "multipart")]
 pub mod multipart;
 
@@ -59,7 +101,14 @@ mod query;
 
 #[cfg(feature = "query")]
 #[doc(inline)]
-pub use self::query::Query;
+pub use self::query::{BoundedQuery, Query};
+
+#[cfg(feature = "query")]
+pub mod pagination;
+
+#[cfg(feature = "query")]
+#[doc(inline)]
+pub use self::pagination::Pagination;
 
 #[cfg(feature = "original-uri")]
 #[doc(inline)]
@@ -43,10 +43,75 @@ use std::{
 ///
 /// For security reasons it's recommended to combine this with
 /// [`ContentLengthLimit`](super::ContentLengthLimit) to limit the size of the request payload.
+///
+/// # Streaming
+///
+/// The request body is read as the fields are consumed so large uploads don't
+/// have to be buffered in memory. Each [`Field`] is a [`Stream`] of its data.
+/// Data is only read from the request when the next chunk is requested, so a
+/// slow consumer, such as a file being written to, also slows down reading the
+/// request.
+///
+/// [`Multipart::set_field_size_limit`] can be used to limit the size of each
+/// field. Reading a field that exceeds the limit returns an error.
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::Multipart,
+///     routing::post,
+///     http::StatusCode,
+///     Router,
+/// };
+/// use futures::stream::StreamExt;
+/// use tokio::{fs::File, io::AsyncWriteExt};
+///
+/// async fn upload(mut multipart: Multipart) -> Result<(), StatusCode> {
+///     multipart.set_field_size_limit(100 * 1024 * 1024);
+///
+///     while let Some(mut field) = multipart
+///         .next_field()
+///         .await
+///         .map_err(|_| StatusCode::BAD_REQUEST)?
+///     {
+///         let file_name = field.file_name().unwrap_or("upload").to_owned();
+///         let mut file = File::create(file_name)
+///             .await
+///             .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+///
+///         while let Some(chunk) = field.next().await {
+///             let chunk = chunk.map_err(|_| StatusCode::BAD_REQUEST)?;
+///             file.write_all(&chunk)
+///                 .await
+///                 .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+///         }
+///     }
+///
+///     Ok(())
+/// }
+///
+/// let app = Router::new().route("/upload", post(upload));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
 #[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
 #[derive(Debug)]
 pub struct Multipart {
-    inner: multer::Multipart<'static>,
+    state: State,
+}
+
+#[derive(Debug)]
+enum State {
+    // multer only supports setting constraints when creating the parser so
+    // that is delayed until the first field is requested
+    Pending {
+        stream: BodyStream,
+        boundary: String,
+        field_size_limit: Option<u64>,
+    },
+    Parsing(multer::Multipart<'static>),
+    // only used while transitioning from `Pending` to `Parsing`
+    Empty,
 }
 
 #[async_trait]
@@ -61,16 +126,39 @@ where
         let stream = BodyStream::from_request(req).await?;
         let headers = req.headers();
         let boundary = parse_boundary(headers).ok_or(InvalidBoundary)?;
-        let multipart = multer::Multipart::new(stream, boundary);
-        Ok(Self { inner: multipart })
+        Ok(Self {
+            state: State::Pending {
+                stream,
+                boundary,
+                field_size_limit: None,
+            },
+        })
     }
 }
 
 impl Multipart {
+    /// Limit the size of each field to `limit` bytes.
+    ///
+    /// Reading data from a field that exceeds the limit returns an error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after [`Multipart::next_field`].
+    pub fn set_field_size_limit(&mut self, limit: u64) {
+        match &mut self.state {
+            State::Pending {
+                field_size_limit, ..
+            } => *field_size_limit = Some(limit),
+            State::Parsing(_) | State::Empty => {
+                panic!("`Multipart::set_field_size_limit` must be called before `next_field`")
+            }
+        }
+    }
+
     /// Yields the next [`Field`] if available.
     pub async fn next_field(&mut self) -> Result<Option<Field<'_>>, MultipartError> {
         let field = self
-            .inner
+            .parser()
             .next_field()
             .await
             .map_err(MultipartError::from_multer)?;
@@ -86,6 +174,35 @@ impl Multipart {
     }
 }
 
+impl Multipart {
+    fn parser(&mut self) -> &mut multer::Multipart<'static> {
+        if let State::Pending { .. } = self.state {
+            if let State::Pending {
+                stream,
+                boundary,
+                field_size_limit,
+            } = std::mem::replace(&mut self.state, State::Empty)
+            {
+                let mut size_limit = multer::SizeLimit::new();
+                if let Some(limit) = field_size_limit {
+                    size_limit = size_limit.per_field(limit);
+                }
+                let constraints = multer::Constraints::new().size_limit(size_limit);
+                self.state = State::Parsing(multer::Multipart::with_constraints(
+                    stream,
+                    boundary,
+                    constraints,
+                ));
+            }
+        }
+
+        match &mut self.state {
+            State::Parsing(parser) => parser,
+            State::Pending { .. } | State::Empty => unreachable!(),
+        }
+    }
+}
+
 /// A single field in a multipart stream.
 #[derive(Debug)]
 pub struct Field<'a> {
@@ -271,4 +388,32 @@ mod tests {
 
         client.post("/").multipart(form).send().await;
     }
+
+    #[tokio::test]
+    async fn field_size_limit() {
+        async fn handle(mut multipart: Multipart) -> String {
+            multipart.set_field_size_limit(4);
+
+            let small = multipart.next_field().await.unwrap().unwrap();
+            assert_eq!(small.text().await.unwrap(), "abcd");
+
+            let large = multipart.next_field().await.unwrap().unwrap();
+            let err = large.bytes().await.unwrap_err();
+            std::error::Error::source(&err).unwrap().to_string()
+        }
+
+        let app = Router::new().route("/", post(handle));
+
+        let client = TestClient::new(app);
+
+        let form = reqwest::multipart::Form::new()
+            .text("small", "abcd")
+            .text("large", "abcde");
+
+        let res = client.post("/").multipart(form).send().await;
+        assert_eq!(
+            res.text().await,
+            r#"field "large" exceeded the size limit: 4 bytes"#
+        );
+    }
 }
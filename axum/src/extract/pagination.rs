@@ -0,0 +1,249 @@
+//! Extractor for pagination parameters.
+//!
+//! See [`Pagination`] for more details.
+
+use super::{rejection::*, FromRequest, RequestParts};
+use async_trait::async_trait;
+
+/// Extractor that parses and validates pagination parameters from the query
+/// string.
+///
+/// Clients can ask for a page either with `?page=3&per_page=20`, where pages
+/// start at 1, or with `?offset=40&limit=20`. Either way the extractor gives
+/// the [`offset`](Pagination::offset) and [`limit`](Pagination::limit) to use
+/// when fetching the items. Other query parameters are ignored so
+/// `Pagination` can be combined with [`Query`](super::Query) for filters.
+///
+/// If the client doesn't send a limit the default limit is used, and limits
+/// larger than the maximum are lowered to the maximum. Both can be set by
+/// adding a [`PaginationConfig`] as an [`Extension`](crate::Extension) and
+/// default to 20 and 100.
+///
+/// Requests are rejected with `400 Bad Request` if a parameter isn't a
+/// non-negative integer, `page` or the limit is 0, `page` is combined with
+/// `offset`, or `per_page` is combined with `limit`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::pagination::{Pagination, PaginationConfig},
+///     routing::get,
+///     Extension, Router,
+/// };
+///
+/// async fn list_users(pagination: Pagination) -> String {
+///     format!(
+///         "SELECT * FROM users LIMIT {} OFFSET {}",
+///         pagination.limit(),
+///         pagination.offset(),
+///     )
+/// }
+///
+/// let app = Router::new()
+///     .route("/users", get(list_users))
+///     .layer(Extension(PaginationConfig::new().default_limit(50).max_limit(500)));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "query")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pagination {
+    offset: u64,
+    limit: u64,
+}
+
+impl Pagination {
+    /// The number of items to skip.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// The maximum number of items to return.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+}
+
+#[async_trait]
+impl<B> FromRequest<B> for Pagination
+where
+    B: Send,
+{
+    type Rejection = InvalidPagination;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let config = req
+            .extensions()
+            .get::<PaginationConfig>()
+            .copied()
+            .unwrap_or_default();
+
+        let query = req.uri().query().unwrap_or_default();
+        let params = serde_urlencoded::from_str::<Vec<(String, String)>>(query)
+            .map_err(InvalidPagination::from_err)?;
+
+        let mut page = None;
+        let mut per_page = None;
+        let mut offset = None;
+        let mut limit = None;
+
+        for (key, value) in &params {
+            let slot = match &**key {
+                "page" => &mut page,
+                "per_page" => &mut per_page,
+                "offset" => &mut offset,
+                "limit" => &mut limit,
+                _ => continue,
+            };
+            let value = value.parse::<u64>().map_err(|_| {
+                InvalidPagination::from_err(format!("`{}` must be a non-negative integer", key))
+            })?;
+            *slot = Some(value);
+        }
+
+        let limit = match (per_page, limit) {
+            (Some(_), Some(_)) => {
+                return Err(InvalidPagination::from_err(
+                    "`per_page` and `limit` can't be combined",
+                ))
+            }
+            (Some(0), _) => return Err(InvalidPagination::from_err("`per_page` must not be 0")),
+            (_, Some(0)) => return Err(InvalidPagination::from_err("`limit` must not be 0")),
+            (Some(limit), None) | (None, Some(limit)) => limit,
+            (None, None) => config.default_limit,
+        }
+        .min(config.max_limit);
+
+        let offset = match (page, offset) {
+            (Some(_), Some(_)) => {
+                return Err(InvalidPagination::from_err(
+                    "`page` and `offset` can't be combined",
+                ))
+            }
+            (Some(0), None) => return Err(InvalidPagination::from_err("`page` starts at 1")),
+            (Some(page), None) => (page - 1)
+                .checked_mul(limit)
+                .ok_or_else(|| InvalidPagination::from_err("`page` is too large"))?,
+            (None, Some(offset)) => offset,
+            (None, None) => 0,
+        };
+
+        Ok(Self { offset, limit })
+    }
+}
+
+/// Configuration for [`Pagination`].
+///
+/// Added to requests as an [`Extension`](crate::Extension). Requests without
+/// it use the default configuration.
+#[cfg_attr(docsrs, doc(cfg(feature = "query")))]
+#[derive(Debug, Clone, Copy)]
+pub struct PaginationConfig {
+    default_limit: u64,
+    max_limit: u64,
+}
+
+impl PaginationConfig {
+    /// Create a new `PaginationConfig` with a default limit of 20 and a
+    /// maximum limit of 100.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the limit used if the client doesn't send one.
+    ///
+    /// If it's larger than the maximum limit the maximum is used instead.
+    ///
+    /// # Panics
+    ///
+    /// If `limit` is 0.
+    pub fn default_limit(mut self, limit: u64) -> Self {
+        assert!(limit > 0, "default pagination limit must not be 0");
+        self.default_limit = limit;
+        self
+    }
+
+    /// Set the largest limit clients can ask for.
+    ///
+    /// Larger limits are lowered to `limit`.
+    ///
+    /// # Panics
+    ///
+    /// If `limit` is 0.
+    pub fn max_limit(mut self, limit: u64) -> Self {
+        assert!(limit > 0, "maximum pagination limit must not be 0");
+        self.max_limit = limit;
+        self
+    }
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        Self {
+            default_limit: 20,
+            max_limit: 100,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{routing::get, test_helpers::*, Extension, Router};
+    use http::StatusCode;
+
+    async fn pagination(pagination: Pagination) -> String {
+        format!("{} {}", pagination.offset(), pagination.limit())
+    }
+
+    #[tokio::test]
+    async fn parses_pagination() {
+        let client = TestClient::new(Router::new().route("/", get(pagination)));
+
+        let paginate = |uri: &'static str| {
+            let req = client.get(uri).send();
+            async move { req.await.text().await }
+        };
+
+        assert_eq!(paginate("/").await, "0 20");
+        assert_eq!(paginate("/?page=3&per_page=10").await, "20 10");
+        assert_eq!(paginate("/?page=2").await, "20 20");
+        assert_eq!(paginate("/?offset=5&limit=10&sort=name").await, "5 10");
+        assert_eq!(paginate("/?limit=1000").await, "0 100");
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_pagination() {
+        let client = TestClient::new(Router::new().route("/", get(pagination)));
+
+        for uri in [
+            "/?page=0",
+            "/?limit=0",
+            "/?per_page=0",
+            "/?page=-1",
+            "/?offset=ten",
+            "/?page=1&offset=0",
+            "/?per_page=10&limit=10",
+            "/?page=18446744073709551615&limit=2",
+        ] {
+            let res = client.get(uri).send().await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{}", uri);
+        }
+    }
+
+    #[tokio::test]
+    async fn uses_config() {
+        let app = Router::new().route("/", get(pagination)).layer(Extension(
+            PaginationConfig::new().default_limit(50).max_limit(30),
+        ));
+        let client = TestClient::new(app);
+
+        let res = client.get("/").send().await;
+        assert_eq!(res.text().await, "0 30");
+
+        let res = client.get("/?page=2&per_page=10").send().await;
+        assert_eq!(res.text().await, "10 10");
+    }
+}
@@ -2,10 +2,12 @@
 //! [`serde`].
 
 mod de;
+mod parsed;
 
 use crate::{
     extract::{rejection::*, FromRequest, RequestParts},
-    routing::url_params::UrlParams,
+    routing::url_params::{RawUrlParams, UrlParams, WildcardParam},
+    util::PercentDecodedStr,
 };
 use async_trait::async_trait;
 use axum_core::response::{IntoResponse, Response};
@@ -14,14 +16,19 @@ use serde::de::DeserializeOwned;
 use std::{
     fmt,
     ops::{Deref, DerefMut},
+    sync::Arc,
 };
 
+pub use self::parsed::{FromPathParam, ParsedPath};
+
 /// Extractor that will get captures from the URL and parse them using
 /// [`serde`].
 ///
 /// Any percent encoded parameters will be automatically decoded. The decoded
 /// parameters must be valid UTF-8, otherwise `Path` will fail and return a `400
-/// Bad Request` response.
+/// Bad Request` response. Use [`RawPath`] if you need the parameters as they
+/// appear in the URI, for example to tell an encoded slash (`%2F`) apart from
+/// a real one.
 ///
 /// # Example
 ///
@@ -171,23 +178,9 @@ where
     type Rejection = PathRejection;
 
     async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
-        let params = match req.extensions_mut().get::<UrlParams>() {
-            Some(UrlParams::Params(params)) => params,
-            Some(UrlParams::InvalidUtf8InPathParam { key }) => {
-                let err = PathDeserializationError {
-                    kind: ErrorKind::InvalidUtf8InPathParam {
-                        key: key.to_string(),
-                    },
-                };
-                let err = FailedToDeserializePathParams(err);
-                return Err(err.into());
-            }
-            None => {
-                return Err(MissingPathParams.into());
-            }
-        };
+        let params = url_params(req)?;
 
-        T::deserialize(de::PathDeserializer::new(&*params))
+        T::deserialize(de::PathDeserializer::new(params))
             .map_err(|err| {
                 PathRejection::FailedToDeserializePathParams(FailedToDeserializePathParams(err))
             })
@@ -195,6 +188,162 @@ where
     }
 }
 
+fn url_params<B>(req: &RequestParts<B>) -> Result<&[(Arc<str>, PercentDecodedStr)], PathRejection> {
+    match req.extensions().get::<UrlParams>() {
+        Some(UrlParams::Params(params)) => Ok(params),
+        Some(UrlParams::InvalidUtf8InPathParam { key }) => {
+            let err = PathDeserializationError {
+                kind: ErrorKind::InvalidUtf8InPathParam {
+                    key: key.to_string(),
+                },
+            };
+            Err(FailedToDeserializePathParams(err).into())
+        }
+        None => Err(MissingPathParams.into()),
+    }
+}
+
+/// Extractor that will get captures from the URL, without percent-decoding
+/// them, and parse them using [`serde`].
+///
+/// This works like [`Path`] except the parameters are given exactly as they
+/// appear in the URI, so reserved characters such as an encoded slash (`%2F`)
+/// are preserved. This is useful when encoded and literal slashes mean
+/// different things, for example when proxying requests.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::RawPath,
+///     routing::get,
+///     Router,
+/// };
+///
+/// async fn proxy(RawPath(key): RawPath<String>) {
+///     // for `GET /objects/a%2Fb` `key` is `a%2Fb`
+/// }
+///
+/// let app = Router::new().route("/objects/:key", get(proxy));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+///
+/// [`serde`]: https://crates.io/crates/serde
+#[derive(Debug)]
+pub struct RawPath<T>(pub T);
+
+impl<T> Deref for RawPath<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for RawPath<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[async_trait]
+impl<T, B> FromRequest<B> for RawPath<T>
+where
+    T: DeserializeOwned + Send,
+    B: Send,
+{
+    type Rejection = PathRejection;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let RawUrlParams(params) = req
+            .extensions()
+            .get::<RawUrlParams>()
+            .ok_or(MissingPathParams)?;
+
+        T::deserialize(de::PathDeserializer::new(params))
+            .map_err(|err| {
+                PathRejection::FailedToDeserializePathParams(FailedToDeserializePathParams(err))
+            })
+            .map(RawPath)
+    }
+}
+
+/// Extractor that deserializes the named path parameters and gives the
+/// wildcard capture separately.
+///
+/// The parameters other than the wildcard are deserialized into `T`, the same
+/// way as with [`Path`], and the wildcard capture, such as `*path` in
+/// `/users/:id/files/*path`, is given as a `String`.
+///
+/// If the matched route doesn't have a wildcard the request is rejected with
+/// [`MissingWildcardParam`](crate::extract::rejection::MissingWildcardParam).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::PathAndTail,
+///     routing::get,
+///     Router,
+/// };
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Params {
+///     user_id: u32,
+///     version: String,
+/// }
+///
+/// async fn file(PathAndTail(params, path): PathAndTail<Params>) {
+///     // for `GET /users/1/v2/files/docs/readme.md` `path` is `/docs/readme.md`
+/// }
+///
+/// let app = Router::new().route("/users/:user_id/:version/files/*path", get(file));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+#[derive(Debug)]
+pub struct PathAndTail<T>(pub T, pub String);
+
+#[async_trait]
+impl<T, B> FromRequest<B> for PathAndTail<T>
+where
+    T: DeserializeOwned + Send,
+    B: Send,
+{
+    type Rejection = PathRejection;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let params = url_params(req)?;
+        let WildcardParam(wildcard) = req
+            .extensions()
+            .get::<WildcardParam>()
+            .ok_or(MissingWildcardParam)?;
+
+        let mut tail = None;
+        let mut named = Vec::with_capacity(params.len());
+        for (key, value) in params {
+            if key == wildcard {
+                tail = Some(value.as_str().to_owned());
+            } else {
+                named.push((Arc::clone(key), value.clone()));
+            }
+        }
+        let tail = tail.ok_or(MissingWildcardParam)?;
+
+        let params = T::deserialize(de::PathDeserializer::new(&named)).map_err(|err| {
+            PathRejection::FailedToDeserializePathParams(FailedToDeserializePathParams(err))
+        })?;
+
+        Ok(PathAndTail(params, tail))
+    }
+}
+
 // this wrapper type is used as the deserializer error to hide the `serde::de::Error` impl which
 // would otherwise be public if we used `ErrorKind` as the error directly
 #[derive(Debug)]
@@ -414,6 +563,7 @@ mod tests {
     use crate::{routing::get, test_helpers::*, Router};
     use http::{Request, StatusCode};
     use hyper::Body;
+    use serde::Deserialize;
     use std::collections::HashMap;
 
     #[tokio::test]
@@ -505,6 +655,79 @@ mod tests {
         assert_eq!(res.text().await, "/baz/qux");
     }
 
+    #[tokio::test]
+    async fn raw_path() {
+        let app = Router::new()
+            .route(
+                "/objects/:key",
+                get(|RawPath(key): RawPath<String>| async move { key }),
+            )
+            .route(
+                "/decoded/:key",
+                get(|Path(key): Path<String>| async move { key }),
+            )
+            .nest(
+                "/nested/:a",
+                Router::new().route(
+                    "/:b",
+                    get(|RawPath(params): RawPath<(String, String)>| async move {
+                        format!("{} {}", params.0, params.1)
+                    }),
+                ),
+            );
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/objects/a%2Fb%20c").send().await;
+        assert_eq!(res.text().await, "a%2Fb%20c");
+
+        let res = client.get("/decoded/a%2Fb%20c").send().await;
+        assert_eq!(res.text().await, "a/b c");
+
+        // invalid UTF-8 once decoded, but fine as is
+        let res = client.get("/objects/%FF").send().await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "%FF");
+
+        let res = client.get("/nested/x%2Fy/z").send().await;
+        assert_eq!(res.text().await, "x%2Fy z");
+    }
+
+    #[tokio::test]
+    async fn path_and_tail() {
+        #[derive(Deserialize)]
+        struct Params {
+            user_id: u32,
+            version: String,
+        }
+
+        let app = Router::new()
+            .route(
+                "/users/:user_id/:version/files/*path",
+                get(
+                    |PathAndTail(params, path): PathAndTail<Params>| async move {
+                        format!("{} {} {}", params.user_id, params.version, path)
+                    },
+                ),
+            )
+            .route(
+                "/no-wildcard/:id",
+                get(|_: PathAndTail<HashMap<String, String>>| async {}),
+            );
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/users/1/v2/files/docs/readme.md").send().await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "1 v2 /docs/readme.md");
+
+        let res = client.get("/users/foo/v2/files/readme.md").send().await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        let res = client.get("/no-wildcard/1").send().await;
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
     #[tokio::test]
     async fn captures_dont_match_empty_segments() {
         let app = Router::new().route("/:key", get(|| async {}));
@@ -0,0 +1,272 @@
+use super::{url_params, FailedToDeserializePathParams, PathDeserializationError};
+use crate::extract::{rejection::*, FromRequest, RequestParts};
+use async_trait::async_trait;
+use axum_core::response::IntoResponse;
+use std::ops::{Deref, DerefMut};
+
+/// Types that can be parsed from a single path parameter.
+///
+/// This is used by [`ParsedPath`] and allows parsing parameters without going
+/// through [`serde`], for example for identifiers with a checksum or another
+/// domain specific format.
+///
+/// The value is given percent-decoded. If parsing fails the rejection is
+/// turned into the response for the request.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{
+///     extract::path::FromPathParam,
+///     http::StatusCode,
+/// };
+///
+/// /// An order number such as `1234-5`, where the last digit is the sum of
+/// /// the other digits modulo 10.
+/// struct OrderNumber(u32);
+///
+/// impl FromPathParam for OrderNumber {
+///     type Rejection = (StatusCode, &'static str);
+///
+///     fn from_path_param(value: &str) -> Result<Self, Self::Rejection> {
+///         let invalid = (StatusCode::BAD_REQUEST, "Invalid order number");
+///
+///         let (number, check) = value.split_once('-').ok_or(invalid)?;
+///         let digits = number
+///             .chars()
+///             .map(|c| c.to_digit(10))
+///             .collect::<Option<Vec<_>>>()
+///             .ok_or(invalid)?;
+///         if digits.is_empty() || check != (digits.iter().sum::<u32>() % 10).to_string() {
+///             return Err(invalid);
+///         }
+///
+///         number.parse().map(OrderNumber).map_err(|_| invalid)
+///     }
+/// }
+/// ```
+///
+/// [`serde`]: https://crates.io/crates/serde
+pub trait FromPathParam: Sized {
+    /// If parsing fails this is turned into the response.
+    type Rejection: IntoResponse;
+
+    /// Parse the value of a path parameter.
+    fn from_path_param(value: &str) -> Result<Self, Self::Rejection>;
+}
+
+/// Extractor that gets captures from the URL and parses them with
+/// [`FromPathParam`].
+///
+/// This works like [`Path`](super::Path) but uses [`FromPathParam`] instead
+/// of [`serde`]. `T` can either be a single type, if the route has one
+/// parameter, or a tuple with one type per parameter in the order they appear
+/// in the route.
+///
+/// If a parameter fails to parse, the request is rejected with the
+/// [`FromPathParam::Rejection`] of its type. If the number of parameters
+/// doesn't match, the request is rejected like it would be by
+/// [`Path`](super::Path).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::path::{FromPathParam, ParsedPath},
+///     http::StatusCode,
+///     routing::get,
+///     Router,
+/// };
+///
+/// struct OrderNumber(u32);
+///
+/// impl FromPathParam for OrderNumber {
+///     type Rejection = (StatusCode, &'static str);
+///
+///     fn from_path_param(value: &str) -> Result<Self, Self::Rejection> {
+///         // ...
+///         # value.parse().map(OrderNumber).map_err(|_| (StatusCode::BAD_REQUEST, "Invalid order number"))
+///     }
+/// }
+///
+/// async fn order(ParsedPath(order): ParsedPath<OrderNumber>) {
+///     // ...
+/// }
+///
+/// async fn order_item(
+///     ParsedPath((order, item)): ParsedPath<(OrderNumber, OrderNumber)>,
+/// ) {
+///     // ...
+/// }
+///
+/// let app = Router::new()
+///     .route("/orders/:order", get(order))
+///     .route("/orders/:order/items/:item", get(order_item));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+///
+/// [`serde`]: https://crates.io/crates/serde
+#[derive(Debug)]
+pub struct ParsedPath<T>(pub T);
+
+impl<T> Deref for ParsedPath<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for ParsedPath<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+fn parse<T>(key: &str, value: &str) -> Result<T, ParsedPathRejection>
+where
+    T: FromPathParam,
+{
+    T::from_path_param(value)
+        .map_err(|rejection| InvalidPathParam::new(key, rejection.into_response()).into())
+}
+
+fn wrong_number_of_parameters(got: usize, expected: usize) -> ParsedPathRejection {
+    let err = PathDeserializationError::wrong_number_of_parameters()
+        .got(got)
+        .expected(expected);
+    PathRejection::from(FailedToDeserializePathParams(err)).into()
+}
+
+#[async_trait]
+impl<T, B> FromRequest<B> for ParsedPath<T>
+where
+    T: FromPathParam + Send,
+    B: Send,
+{
+    type Rejection = ParsedPathRejection;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        match url_params(req)? {
+            [(key, value)] => parse(key, value).map(ParsedPath),
+            params => Err(wrong_number_of_parameters(params.len(), 1)),
+        }
+    }
+}
+
+macro_rules! impl_from_request {
+    ( $($ty:ident),* $(,)? ) => {
+        #[async_trait]
+        #[allow(non_snake_case)]
+        impl<B, $($ty,)*> FromRequest<B> for ParsedPath<($($ty,)*)>
+        where
+            $( $ty: FromPathParam + Send, )*
+            B: Send,
+        {
+            type Rejection = ParsedPathRejection;
+
+            async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+                let params = url_params(req)?;
+
+                let expected = [$(stringify!($ty),)*].len();
+                if params.len() != expected {
+                    return Err(wrong_number_of_parameters(params.len(), expected));
+                }
+
+                let mut params = params.iter();
+                $(
+                    let (key, value) = params.next().unwrap();
+                    let $ty = parse::<$ty>(key, value)?;
+                )*
+
+                Ok(ParsedPath(($($ty,)*)))
+            }
+        }
+    };
+}
+
+all_the_tuples!(impl_from_request);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{routing::get, test_helpers::*, Router};
+    use http::StatusCode;
+
+    #[derive(Debug)]
+    struct Even(u32);
+
+    impl FromPathParam for Even {
+        type Rejection = (StatusCode, &'static str);
+
+        fn from_path_param(value: &str) -> Result<Self, Self::Rejection> {
+            match value.parse() {
+                Ok(n) if n % 2 == 0 => Ok(Even(n)),
+                _ => Err((StatusCode::BAD_REQUEST, "not an even number")),
+            }
+        }
+    }
+
+    struct Name(String);
+
+    impl FromPathParam for Name {
+        type Rejection = StatusCode;
+
+        fn from_path_param(value: &str) -> Result<Self, Self::Rejection> {
+            Ok(Name(value.to_owned()))
+        }
+    }
+
+    #[tokio::test]
+    async fn parsed_path() {
+        let app = Router::new()
+            .route(
+                "/even/:n",
+                get(|ParsedPath(Even(n)): ParsedPath<Even>| async move { n.to_string() }),
+            )
+            .route(
+                "/pair/:name/:n",
+                get(
+                    |ParsedPath((Name(name), Even(n))): ParsedPath<(Name, Even)>| async move {
+                        format!("{} {}", name, n)
+                    },
+                ),
+            )
+            .route("/wrong/:a/:b", get(|_: ParsedPath<Even>| async {}));
+        let client = TestClient::new(app);
+
+        let res = client.get("/even/42").send().await;
+        assert_eq!(res.text().await, "42");
+
+        let res = client.get("/pair/a%20b/2").send().await;
+        assert_eq!(res.text().await, "a b 2");
+
+        let res = client.get("/even/41").send().await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(res.text().await, "not an even number");
+
+        let res = client.get("/pair/a/3").send().await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(res.text().await, "not an even number");
+
+        let res = client.get("/wrong/2/4").send().await;
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(res
+            .text()
+            .await
+            .starts_with("Wrong number of path arguments for `Path`. Expected 1 but got 2"));
+    }
+
+    #[test]
+    fn invalid_path_param_key() {
+        let err = parse::<Even>("n", "1").unwrap_err();
+        match err {
+            ParsedPathRejection::InvalidPathParam(err) => assert_eq!(err.key(), "n"),
+            _ => panic!("expected `InvalidPathParam`"),
+        }
+    }
+}
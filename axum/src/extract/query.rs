@@ -72,6 +72,72 @@ impl<T> Deref for Query<T> {
     }
 }
 
+/// Extractor that deserializes query strings like [`Query`], but rejects
+/// query strings with more than `MAX_PARAMS` parameters.
+///
+/// The parameters are counted before the query string is deserialized, so
+/// query strings with a huge number of parameters are rejected cheaply. That
+/// rejection is a `400 Bad Request` response.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::BoundedQuery,
+///     routing::get,
+///     Router,
+/// };
+/// use std::collections::HashMap;
+///
+/// async fn search(BoundedQuery(params): BoundedQuery<HashMap<String, String>, 16>) {
+///     // `params` has at most 16 entries
+/// }
+///
+/// let app = Router::new().route("/search", get(search));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "query")))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BoundedQuery<T, const MAX_PARAMS: usize>(pub T);
+
+#[async_trait]
+impl<T, B, const MAX_PARAMS: usize> FromRequest<B> for BoundedQuery<T, MAX_PARAMS>
+where
+    T: DeserializeOwned,
+    B: Send,
+{
+    type Rejection = QueryRejection;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let query = req.uri().query().unwrap_or_default();
+
+        // empty pairs, such as in `a=1&&b=2`, are skipped when deserializing
+        // so they don't count
+        let params = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .take(MAX_PARAMS + 1)
+            .count();
+        if params > MAX_PARAMS {
+            return Err(TooManyQueryParams.into());
+        }
+
+        let value = serde_urlencoded::from_str(query)
+            .map_err(FailedToDeserializeQueryString::__private_new)?;
+        Ok(BoundedQuery(value))
+    }
+}
+
+impl<T, const MAX_PARAMS: usize> Deref for BoundedQuery<T, MAX_PARAMS> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,4 +186,32 @@ mod tests {
         )
         .await;
     }
+
+    #[tokio::test]
+    async fn bounded_query() {
+        use crate::{routing::get, test_helpers::*, Router};
+        use http::StatusCode;
+        use std::collections::HashMap;
+
+        let app = Router::new().route(
+            "/",
+            get(
+                |BoundedQuery(params): BoundedQuery<HashMap<String, String>, 2>| async move {
+                    params.len().to_string()
+                },
+            ),
+        );
+        let client = TestClient::new(app);
+
+        let res = client.get("/?a=1&&b=2").send().await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "2");
+
+        let res = client.get("/").send().await;
+        assert_eq!(res.text().await, "0");
+
+        let res = client.get("/?a=1&b=2&c=3").send().await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(res.text().await, "Too many query parameters");
+    }
 }
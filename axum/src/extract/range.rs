@@ -0,0 +1,567 @@
+//! Extractor and responses for range requests.
+//!
+//! See [`Range`] for more details.
+
+use super::{rejection::*, FromRequest, RequestParts};
+use crate::{
+    body::{Bytes, Full},
+    response::{IntoResponse, Response},
+};
+use async_trait::async_trait;
+use http::{
+    header::{self, HeaderMap, HeaderValue},
+    StatusCode,
+};
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    hash::{BuildHasher, Hasher},
+    ops,
+};
+
+/// Extractor that parses the `Range` header into byte ranges.
+///
+/// Use [`Range::satisfiable`] to resolve the ranges against the length of the
+/// content, or [`Range::respond_with`] to respond with the requested parts of
+/// content that is already in memory.
+///
+/// Servers can ignore `Range` headers that are missing or invalid and respond
+/// with the full content instead, so this is usually extracted as
+/// `Option<Range>`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::Range,
+///     http::header,
+///     response::{IntoResponse, Response},
+///     routing::get,
+///     Router,
+/// };
+///
+/// async fn video(range: Option<Range>) -> Response {
+///     let content = load_video().await;
+///
+///     match range {
+///         Some(range) => range.respond_with(content),
+///         None => ([(header::ACCEPT_RANGES, "bytes")], content).into_response(),
+///     }
+/// }
+///
+/// async fn load_video() -> Vec<u8> {
+///     // ...
+///     # vec![]
+/// }
+///
+/// let app = Router::new().route("/video", get(video));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Range {
+    ranges: Vec<ByteRangeSpec>,
+}
+
+impl Range {
+    /// The maximum number of ranges, after merging, that are served.
+    ///
+    /// Requests for many small ranges are expensive to respond to, so they
+    /// are rejected by [`Range::satisfiable`] and answered with the complete
+    /// content by [`Range::respond_with`].
+    pub const MAX_RANGES: usize = 16;
+
+    /// Parse the `Range` header in a [`HeaderMap`].
+    ///
+    /// Returns `None` if the header is missing or invalid, or uses a unit
+    /// other than `bytes`.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let mut values = headers.get_all(header::RANGE).iter();
+        let value = values.next()?;
+        if values.next().is_some() {
+            return None;
+        }
+        value.to_str().ok()?.parse().ok()
+    }
+
+    /// Get the requested ranges, in the order they appear in the header.
+    pub fn ranges(&self) -> &[ByteRangeSpec] {
+        &self.ranges
+    }
+
+    /// Resolve the ranges against content of `complete_length` bytes.
+    ///
+    /// Ranges that don't overlap the content are skipped and the others are
+    /// clamped to the end of the content. Overlapping and adjacent ranges are
+    /// merged, and the result is sorted by position, so no byte is sent more
+    /// than once.
+    ///
+    /// Returns [`RangeNotSatisfiable`] if none of the ranges overlap the
+    /// content, or if more than [`Range::MAX_RANGES`] ranges remain after
+    /// merging.
+    pub fn satisfiable(
+        &self,
+        complete_length: u64,
+    ) -> Result<Vec<ops::Range<u64>>, RangeNotSatisfiable> {
+        match self.resolve(complete_length) {
+            Resolved::Ranges(ranges) => Ok(ranges),
+            Resolved::Unsatisfiable | Resolved::TooMany => {
+                Err(RangeNotSatisfiable::new(complete_length))
+            }
+        }
+    }
+
+    fn resolve(&self, complete_length: u64) -> Resolved {
+        let mut ranges = self
+            .ranges
+            .iter()
+            .filter_map(|spec| spec.resolve(complete_length))
+            .collect::<Vec<_>>();
+        ranges.sort_unstable_by_key(|range| range.start);
+
+        let mut merged: Vec<ops::Range<u64>> = Vec::new();
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        if merged.is_empty() {
+            Resolved::Unsatisfiable
+        } else if merged.len() > Self::MAX_RANGES {
+            Resolved::TooMany
+        } else {
+            Resolved::Ranges(merged)
+        }
+    }
+
+    /// Respond with the requested ranges of `content`.
+    ///
+    /// The ranges are merged as described in [`Range::satisfiable`]. A single
+    /// range is sent as a `206 Partial Content` response with
+    /// [`PartialContent`]. Several ranges are sent in a `multipart/byteranges`
+    /// body, without a `Content-Type` for each part. If none of the ranges are
+    /// satisfiable the response is `416 Range Not Satisfiable`, and if there
+    /// are more than [`Range::MAX_RANGES`] the complete content is sent with
+    /// `200 OK`.
+    pub fn respond_with<T>(&self, content: T) -> Response
+    where
+        T: Into<Bytes>,
+    {
+        let content = content.into();
+        let complete_length = content.len() as u64;
+
+        let ranges = match self.resolve(complete_length) {
+            Resolved::Ranges(ranges) => ranges,
+            Resolved::Unsatisfiable => {
+                return RangeNotSatisfiable::new(complete_length).into_response()
+            }
+            Resolved::TooMany => {
+                return ([(header::ACCEPT_RANGES, "bytes")], content).into_response()
+            }
+        };
+
+        if let [range] = &*ranges {
+            let body = content.slice(range.start as usize..range.end as usize);
+            return PartialContent::new(range.clone(), complete_length, body).into_response();
+        }
+
+        let boundary = boundary();
+        let mut body = Vec::new();
+        for range in &ranges {
+            body.extend_from_slice(
+                format!(
+                    "--{}\r\n{}: {}\r\n\r\n",
+                    boundary,
+                    header::CONTENT_RANGE,
+                    content_range(range, complete_length),
+                )
+                .as_bytes(),
+            );
+            body.extend_from_slice(&content[range.start as usize..range.end as usize]);
+            body.extend_from_slice(b"\r\n");
+        }
+        body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+
+        (
+            StatusCode::PARTIAL_CONTENT,
+            [
+                (header::ACCEPT_RANGES, HeaderValue::from_static("bytes")),
+                (
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_str(&format!("multipart/byteranges; boundary={}", boundary))
+                        .unwrap(),
+                ),
+            ],
+            body,
+        )
+            .into_response()
+    }
+}
+
+enum Resolved {
+    Ranges(Vec<ops::Range<u64>>),
+    Unsatisfiable,
+    TooMany,
+}
+
+impl std::str::FromStr for Range {
+    type Err = InvalidRange;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (unit, specs) = s.split_once('=').ok_or(InvalidRange { _priv: () })?;
+        if !unit.trim().eq_ignore_ascii_case("bytes") {
+            return Err(InvalidRange { _priv: () });
+        }
+
+        let ranges = specs
+            .split(',')
+            .map(str::trim)
+            // empty list elements are allowed and ignored
+            .filter(|spec| !spec.is_empty())
+            .map(|spec| spec.parse())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if ranges.is_empty() {
+            return Err(InvalidRange { _priv: () });
+        }
+
+        Ok(Self { ranges })
+    }
+}
+
+#[async_trait]
+impl<B> FromRequest<B> for Range
+where
+    B: Send,
+{
+    type Rejection = RangeRejection;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        if !req.headers().contains_key(header::RANGE) {
+            return Err(MissingRangeHeader.into());
+        }
+
+        Self::from_headers(req.headers()).ok_or_else(|| InvalidRangeHeader.into())
+    }
+}
+
+/// A single range in a `Range` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteRangeSpec {
+    /// The bytes from `first` to `last`, inclusive, such as `0-499`.
+    FromTo {
+        /// The position of the first byte.
+        first: u64,
+        /// The position of the last byte.
+        last: u64,
+    },
+    /// The bytes from `first` to the end, such as `9500-`.
+    From {
+        /// The position of the first byte.
+        first: u64,
+    },
+    /// The last `length` bytes, such as `-500`.
+    Suffix {
+        /// The number of bytes.
+        length: u64,
+    },
+}
+
+impl ByteRangeSpec {
+    /// Resolve the range against content of `complete_length` bytes.
+    ///
+    /// Returns `None` if the range doesn't overlap the content.
+    pub fn resolve(&self, complete_length: u64) -> Option<ops::Range<u64>> {
+        match *self {
+            Self::FromTo { first, last } => (first < complete_length)
+                .then(|| first..last.saturating_add(1).min(complete_length)),
+            Self::From { first } => (first < complete_length).then(|| first..complete_length),
+            Self::Suffix { length } => (length > 0 && complete_length > 0)
+                .then(|| complete_length.saturating_sub(length)..complete_length),
+        }
+    }
+}
+
+impl std::str::FromStr for ByteRangeSpec {
+    type Err = InvalidRange;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn parse_pos(s: &str) -> Result<u64, InvalidRange> {
+            if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(InvalidRange { _priv: () });
+            }
+            s.parse().map_err(|_| InvalidRange { _priv: () })
+        }
+
+        let (first, last) = s.split_once('-').ok_or(InvalidRange { _priv: () })?;
+
+        match (first, last) {
+            ("", length) => Ok(Self::Suffix {
+                length: parse_pos(length)?,
+            }),
+            (first, "") => Ok(Self::From {
+                first: parse_pos(first)?,
+            }),
+            (first, last) => {
+                let first = parse_pos(first)?;
+                let last = parse_pos(last)?;
+                if first > last {
+                    return Err(InvalidRange { _priv: () });
+                }
+                Ok(Self::FromTo { first, last })
+            }
+        }
+    }
+}
+
+impl fmt::Display for ByteRangeSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FromTo { first, last } => write!(f, "{}-{}", first, last),
+            Self::From { first } => write!(f, "{}-", first),
+            Self::Suffix { length } => write!(f, "-{}", length),
+        }
+    }
+}
+
+/// Error returned when parsing a [`Range`] or [`ByteRangeSpec`] fails.
+#[derive(Debug)]
+pub struct InvalidRange {
+    _priv: (),
+}
+
+impl fmt::Display for InvalidRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid range")
+    }
+}
+
+impl std::error::Error for InvalidRange {}
+
+/// Response with part of some content.
+///
+/// The status is `206 Partial Content` and the `Content-Range` and
+/// `Accept-Ranges` headers are set. `body` must contain exactly the bytes in
+/// `range`, which is usually one of the ranges returned by
+/// [`Range::satisfiable`]. This makes it possible to only read the requested
+/// part of a file from disk.
+#[derive(Debug, Clone)]
+pub struct PartialContent<T> {
+    range: ops::Range<u64>,
+    complete_length: u64,
+    body: T,
+}
+
+impl<T> PartialContent<T> {
+    /// Create a new `PartialContent` response for `range` of content with
+    /// `complete_length` bytes.
+    pub fn new(range: ops::Range<u64>, complete_length: u64, body: T) -> Self {
+        Self {
+            range,
+            complete_length,
+            body,
+        }
+    }
+}
+
+impl<T> IntoResponse for PartialContent<T>
+where
+    T: IntoResponse,
+{
+    fn into_response(self) -> Response {
+        (
+            StatusCode::PARTIAL_CONTENT,
+            [
+                (header::ACCEPT_RANGES, HeaderValue::from_static("bytes")),
+                (
+                    header::CONTENT_RANGE,
+                    HeaderValue::from_str(&content_range(&self.range, self.complete_length))
+                        .unwrap(),
+                ),
+            ],
+            self.body,
+        )
+            .into_response()
+    }
+}
+
+/// Response for requests where none of the ranges overlap the content, or that
+/// have too many ranges.
+///
+/// The status is `416 Range Not Satisfiable` and the `Content-Range` header is
+/// set to the length of the content, as in `bytes */1000`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeNotSatisfiable {
+    complete_length: u64,
+}
+
+impl RangeNotSatisfiable {
+    /// Create a new `RangeNotSatisfiable` response for content with
+    /// `complete_length` bytes.
+    pub fn new(complete_length: u64) -> Self {
+        Self { complete_length }
+    }
+}
+
+impl IntoResponse for RangeNotSatisfiable {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::RANGE_NOT_SATISFIABLE,
+            [
+                (header::ACCEPT_RANGES, HeaderValue::from_static("bytes")),
+                (
+                    header::CONTENT_RANGE,
+                    HeaderValue::from_str(&format!("bytes */{}", self.complete_length)).unwrap(),
+                ),
+            ],
+            Full::default(),
+        )
+            .into_response()
+    }
+}
+
+fn content_range(range: &ops::Range<u64>, complete_length: u64) -> String {
+    format!(
+        "bytes {}-{}/{}",
+        range.start,
+        range.end - 1,
+        complete_length
+    )
+}
+
+/// A random boundary for `multipart/byteranges` bodies.
+fn boundary() -> String {
+    format!("{:016x}", RandomState::new().build_hasher().finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(s: &str) -> Range {
+        s.parse().unwrap()
+    }
+
+    async fn body_bytes(res: Response) -> Bytes {
+        hyper::body::to_bytes(res.into_body()).await.unwrap()
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!(
+            range("bytes=0-499, -500,9500-,").ranges(),
+            [
+                ByteRangeSpec::FromTo {
+                    first: 0,
+                    last: 499
+                },
+                ByteRangeSpec::Suffix { length: 500 },
+                ByteRangeSpec::From { first: 9500 },
+            ]
+        );
+
+        assert!("bytes=".parse::<Range>().is_err());
+        assert!("bytes=5-1".parse::<Range>().is_err());
+        assert!("bytes=-".parse::<Range>().is_err());
+        assert!("bytes=+1-2".parse::<Range>().is_err());
+        assert!("items=0-1".parse::<Range>().is_err());
+    }
+
+    #[test]
+    fn resolving() {
+        let r = range("bytes=0-499,-500,9500-,10000-");
+        assert_eq!(r.satisfiable(10000).unwrap(), [0..500, 9500..10000]);
+        assert_eq!(
+            range("bytes=0-99999").satisfiable(10).unwrap(),
+            [ops::Range { start: 0, end: 10 }]
+        );
+        assert_eq!(
+            range("bytes=-500").satisfiable(10).unwrap(),
+            [ops::Range { start: 0, end: 10 }]
+        );
+        assert_eq!(
+            range("bytes=10-,-0").satisfiable(10),
+            Err(RangeNotSatisfiable::new(10))
+        );
+    }
+
+    #[tokio::test]
+    async fn single_range() {
+        let res = range("bytes=-3").respond_with("hello world");
+
+        assert_eq!(res.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(res.headers()[header::CONTENT_RANGE], "bytes 8-10/11");
+        assert_eq!(res.headers()[header::ACCEPT_RANGES], "bytes");
+        assert_eq!(body_bytes(res).await, "rld");
+    }
+
+    #[tokio::test]
+    async fn multiple_ranges() {
+        let res = range("bytes=0-1,6-").respond_with("hello world");
+
+        assert_eq!(res.status(), StatusCode::PARTIAL_CONTENT);
+        let content_type = res.headers()[header::CONTENT_TYPE].to_str().unwrap();
+        let boundary = content_type
+            .strip_prefix("multipart/byteranges; boundary=")
+            .unwrap()
+            .to_owned();
+
+        let expected = format!(
+            "--{b}\r\ncontent-range: bytes 0-1/11\r\n\r\nhe\r\n\
+             --{b}\r\ncontent-range: bytes 6-10/11\r\n\r\nworld\r\n\
+             --{b}--\r\n",
+            b = boundary
+        );
+        assert_eq!(body_bytes(res).await, expected);
+    }
+
+    #[test]
+    fn merging() {
+        assert_eq!(
+            range("bytes=50-99,0-9,5-19,20-29")
+                .satisfiable(100)
+                .unwrap(),
+            [0..30, 50..100]
+        );
+        assert_eq!(
+            range("bytes=0-,0-,-100").satisfiable(100).unwrap(),
+            [ops::Range { start: 0, end: 100 }]
+        );
+    }
+
+    #[test]
+    fn too_many_ranges() {
+        let specs = (0..=Range::MAX_RANGES)
+            .map(|i| format!("{}-{}", i * 2, i * 2))
+            .collect::<Vec<_>>()
+            .join(",");
+        let r = range(&format!("bytes={}", specs));
+
+        assert_eq!(r.satisfiable(1000), Err(RangeNotSatisfiable::new(1000)));
+
+        let res = r.respond_with(vec![0; 1000]);
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()[header::ACCEPT_RANGES], "bytes");
+    }
+
+    #[tokio::test]
+    async fn repeated_ranges_are_sent_once() {
+        let specs = vec!["0-"; 1000].join(",");
+        let res = range(&format!("bytes={}", specs)).respond_with("hello world");
+
+        assert_eq!(res.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(res.headers()[header::CONTENT_RANGE], "bytes 0-10/11");
+        assert_eq!(body_bytes(res).await, "hello world");
+    }
+
+    #[tokio::test]
+    async fn unsatisfiable() {
+        let res = range("bytes=20-").respond_with("hello world");
+
+        assert_eq!(res.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(res.headers()[header::CONTENT_RANGE], "bytes */11");
+    }
+}
@@ -0,0 +1,266 @@
+//! Extractor for limiting how many requests each client can make.
+//!
+//! See [`RateLimited`] for more details.
+
+use super::{client_ip::ClientIp, rejection::*, Extension, FromRequest, RequestParts};
+use async_trait::async_trait;
+use std::{
+    collections::HashMap,
+    fmt,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::time::Instant;
+
+/// Extractor that limits how many requests each client can make.
+///
+/// Every client, identified by its [`ClientIp`], has a bucket that holds up
+/// to `burst` tokens and is refilled at a rate of `burst` tokens per `period`,
+/// as set by [`RateLimitConfig::new`]. Each request takes one token. Requests
+/// made while the bucket is empty are rejected with
+/// `429 Too Many Requests` and a `Retry-After` header saying when the next
+/// token becomes available.
+///
+/// The buckets are kept in memory by the [`RateLimitConfig`], which has to be
+/// added as an [`Extension`]. Clones of the config share their buckets so
+/// every instance of the app enforces the same limits, but separate processes
+/// don't. Buckets of clients that haven't made a request for a full `period`
+/// are full again and so are dropped, which keeps memory use bounded by the
+/// number of recently active clients.
+///
+/// Since clients are identified with [`ClientIp`] the app has to be run with
+/// [`Router::into_make_service_with_connect_info`]. Requests without
+/// [`ConnectInfo`] are rejected with `500 Internal Server Error`.
+///
+/// `RateLimited` is usually applied to a whole router with
+/// [`Router::rate_limit`] but can also be used as a handler argument, or with
+/// [`middleware::from_extractor`], to limit only some routes.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::rate_limit::{RateLimitConfig, RateLimited},
+///     routing::{get, post},
+///     Extension, Router,
+/// };
+/// use std::{net::SocketAddr, time::Duration};
+///
+/// // only logins are limited, to 5 attempts per minute
+/// async fn login(_: RateLimited) {}
+///
+/// async fn index() {}
+///
+/// let app = Router::new()
+///     .route("/", get(index))
+///     .route("/login", post(login))
+///     .layer(Extension(RateLimitConfig::new(5, Duration::from_secs(60))));
+///
+/// # async {
+/// axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
+///     .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+///     .await
+///     .unwrap();
+/// # };
+/// ```
+///
+/// [`Router::into_make_service_with_connect_info`]: crate::routing::Router::into_make_service_with_connect_info
+/// [`Router::rate_limit`]: crate::routing::Router::rate_limit
+/// [`ConnectInfo`]: super::ConnectInfo
+/// [`middleware::from_extractor`]: crate::middleware::from_extractor
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimited;
+
+#[async_trait]
+impl<B> FromRequest<B> for RateLimited
+where
+    B: Send,
+{
+    type Rejection = RateLimitedRejection;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let Extension(config) = Extension::<RateLimitConfig>::from_request(req).await?;
+        let ClientIp(ip) = ClientIp::from_request(req).await?;
+
+        config.acquire(ip, Instant::now())?;
+
+        Ok(Self)
+    }
+}
+
+/// Configuration and state for [`RateLimited`].
+///
+/// Clones share the same buckets.
+#[derive(Clone)]
+pub struct RateLimitConfig {
+    burst: u32,
+    period: Duration,
+    buckets: Arc<Mutex<Buckets>>,
+}
+
+struct Buckets {
+    buckets: HashMap<IpAddr, Bucket>,
+    last_sweep: Instant,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimitConfig {
+    /// Allow each client `burst` requests per `period`.
+    ///
+    /// Clients can make up to `burst` requests at once, after which they
+    /// regain one request every `period / burst`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `burst` or `period` is zero.
+    pub fn new(burst: u32, period: Duration) -> Self {
+        assert!(burst > 0, "rate limit burst must be greater than zero");
+        assert!(
+            !period.is_zero(),
+            "rate limit period must be greater than zero"
+        );
+
+        Self {
+            burst,
+            period,
+            buckets: Arc::new(Mutex::new(Buckets {
+                buckets: HashMap::new(),
+                last_sweep: Instant::now(),
+            })),
+        }
+    }
+
+    fn acquire(&self, ip: IpAddr, now: Instant) -> Result<(), TooManyRequests> {
+        let burst = f64::from(self.burst);
+        let per_token = self.period.as_secs_f64() / burst;
+
+        let mut buckets = self.buckets.lock().unwrap();
+
+        // a bucket that hasn't been used for a full period is full and so no
+        // different from a new one
+        if now.saturating_duration_since(buckets.last_sweep) >= self.period {
+            let period = self.period;
+            buckets
+                .buckets
+                .retain(|_, bucket| now.saturating_duration_since(bucket.updated) < period);
+            buckets.last_sweep = now;
+        }
+
+        let bucket = buckets.buckets.entry(ip).or_insert(Bucket {
+            tokens: burst,
+            updated: now,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed / per_token).min(burst);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let wait = (1.0 - bucket.tokens) * per_token;
+            Err(TooManyRequests::new(Duration::from_secs_f64(wait)))
+        }
+    }
+}
+
+impl fmt::Debug for RateLimitConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimitConfig")
+            .field("burst", &self.burst)
+            .field("period", &self.period)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{extract::ConnectInfo, routing::get, test_helpers::*, Router};
+    use http::{header::RETRY_AFTER, StatusCode};
+    use std::net::{Ipv4Addr, SocketAddr};
+
+    #[test]
+    fn traits() {
+        assert_send::<RateLimitConfig>();
+        assert_sync::<RateLimitConfig>();
+    }
+
+    fn ip(last: u8) -> IpAddr {
+        IpAddr::V4(Ipv4Addr::new(10, 0, 0, last))
+    }
+
+    #[test]
+    fn token_bucket() {
+        let config = RateLimitConfig::new(2, Duration::from_secs(8));
+        let start = Instant::now();
+
+        assert!(config.acquire(ip(1), start).is_ok());
+        assert!(config.acquire(ip(1), start).is_ok());
+        let err = config.acquire(ip(1), start).unwrap_err();
+        assert_eq!(err.retry_after(), Duration::from_secs(4));
+
+        // other clients have their own bucket
+        assert!(config.acquire(ip(2), start).is_ok());
+
+        // one token is refilled every 4 seconds
+        let later = start + Duration::from_secs(3);
+        let err = config.acquire(ip(1), later).unwrap_err();
+        assert_eq!(err.retry_after(), Duration::from_secs(1));
+        assert!(config
+            .acquire(ip(1), later + Duration::from_secs(1))
+            .is_ok());
+    }
+
+    #[test]
+    fn idle_buckets_are_evicted() {
+        let config = RateLimitConfig::new(1, Duration::from_secs(10));
+        let start = Instant::now();
+
+        assert!(config.acquire(ip(1), start).is_ok());
+        assert!(config
+            .acquire(ip(2), start + Duration::from_secs(5))
+            .is_ok());
+        assert_eq!(config.buckets.lock().unwrap().buckets.len(), 2);
+
+        // the bucket of `ip(1)` has been idle for a full period and is
+        // dropped, the one of `ip(2)` hasn't been refilled yet
+        let later = start + Duration::from_secs(10);
+        assert!(config.acquire(ip(2), later).is_err());
+        assert_eq!(config.buckets.lock().unwrap().buckets.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn rate_limit_router() {
+        let app = Router::new()
+            .route("/", get(|| async {}))
+            .rate_limit(RateLimitConfig::new(1, Duration::from_secs(60)))
+            .layer(Extension(ConnectInfo(SocketAddr::from(([10, 0, 0, 1], 0)))));
+        let client = TestClient::new(app);
+
+        let res = client.get("/").send().await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = client.get("/").send().await;
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(res.headers()[RETRY_AFTER], "60");
+        assert_eq!(res.text().await, "Too many requests");
+    }
+
+    #[tokio::test]
+    async fn missing_config() {
+        let app = Router::new()
+            .route("/", get(|_: RateLimited| async {}))
+            .layer(Extension(ConnectInfo(SocketAddr::from(([10, 0, 0, 1], 0)))));
+        let client = TestClient::new(app);
+
+        let res = client.get("/").send().await;
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
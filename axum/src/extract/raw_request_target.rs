@@ -0,0 +1,140 @@
+use super::{FromRequest, RequestParts};
+use async_trait::async_trait;
+use std::convert::Infallible;
+
+/// Extractor that extracts the request target, the part of the request line
+/// between the method and the HTTP version, as a string.
+///
+/// Unlike [`Uri`](http::Uri) extraction this is not affected by
+/// [`Router::nest`](crate::Router::nest) stripping the path prefix, if the
+/// `original-uri` feature is enabled, so it can be forwarded as is by proxies. Use [`RawRequestTarget::form`] to tell
+/// which of the forms from [RFC 7230] the target uses.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::{RawRequestTarget, RequestTargetForm},
+///     routing::any,
+///     Router,
+/// };
+///
+/// async fn proxy(target: RawRequestTarget) {
+///     if target.form() == RequestTargetForm::Absolute {
+///         // `target.0` is something like `http://example.com/foo?bar=baz`
+///     }
+/// }
+///
+/// let app = Router::new().route("/*path", any(proxy));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+///
+/// [RFC 7230]: https://datatracker.ietf.org/doc/html/rfc7230#section-5.3
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawRequestTarget(pub String);
+
+impl RawRequestTarget {
+    /// Get the form of the request target.
+    pub fn form(&self) -> RequestTargetForm {
+        if self.0 == "*" {
+            RequestTargetForm::Asterisk
+        } else if self.0.starts_with('/') {
+            RequestTargetForm::Origin
+        } else if self.0.contains("://") {
+            RequestTargetForm::Absolute
+        } else {
+            RequestTargetForm::Authority
+        }
+    }
+}
+
+/// The form of a [`RawRequestTarget`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RequestTargetForm {
+    /// The path and query, such as `/foo?bar=baz`. Used for most requests.
+    Origin,
+    /// A complete URI, such as `http://example.com/foo`. Used for requests to
+    /// proxies.
+    Absolute,
+    /// Only the authority, such as `example.com:443`. Used for `CONNECT`
+    /// requests.
+    Authority,
+    /// A single `*`. Used for server wide `OPTIONS` requests.
+    Asterisk,
+}
+
+#[async_trait]
+impl<B> FromRequest<B> for RawRequestTarget
+where
+    B: Send,
+{
+    type Rejection = Infallible;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        #[cfg(feature = "original-uri")]
+        let uri = match req.extensions().get::<super::OriginalUri>() {
+            Some(original_uri) => &original_uri.0,
+            None => req.uri(),
+        };
+        #[cfg(not(feature = "original-uri"))]
+        let uri = req.uri();
+
+        Ok(Self(uri.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{routing::get, test_helpers::TestClient, Router};
+    use http::{Method, Request};
+
+    async fn extract(uri: &str) -> RawRequestTarget {
+        let req = Request::builder()
+            .method(Method::OPTIONS)
+            .uri(uri)
+            .body(())
+            .unwrap();
+        RawRequestTarget::from_request(&mut RequestParts::new(req))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn forms() {
+        let target = extract("/foo?bar=baz").await;
+        assert_eq!(target.0, "/foo?bar=baz");
+        assert_eq!(target.form(), RequestTargetForm::Origin);
+
+        let target = extract("http://example.com/foo?bar=baz").await;
+        assert_eq!(target.0, "http://example.com/foo?bar=baz");
+        assert_eq!(target.form(), RequestTargetForm::Absolute);
+
+        let target = extract("example.com:443").await;
+        assert_eq!(target.0, "example.com:443");
+        assert_eq!(target.form(), RequestTargetForm::Authority);
+
+        let target = extract("*").await;
+        assert_eq!(target.0, "*");
+        assert_eq!(target.form(), RequestTargetForm::Asterisk);
+    }
+
+    #[cfg(feature = "original-uri")]
+    #[tokio::test]
+    async fn not_affected_by_nesting() {
+        let app = Router::new().nest(
+            "/api",
+            Router::new().route(
+                "/users",
+                get(|target: RawRequestTarget| async move { target.0 }),
+            ),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/api/users?page=2").send().await;
+        assert_eq!(res.text().await, "/api/users?page=2");
+    }
+}
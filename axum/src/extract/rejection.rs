@@ -29,6 +29,18 @@ define_rejection! {
     pub struct JsonSyntaxError(Error);
 }
 
+#[cfg(feature = "json")]
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Failed to parse the request body as JSON: maximum nesting depth exceeded"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    /// Rejection type for [`BoundedJson`](super::BoundedJson).
+    ///
+    /// This rejection is used if the request body contains arrays or objects
+    /// nested deeper than the allowed maximum.
+    pub struct JsonTooDeep;
+}
+
 #[cfg(feature = "json")]
 define_rejection! {
     #[status = UNSUPPORTED_MEDIA_TYPE]
@@ -39,6 +51,14 @@ define_rejection! {
     pub struct MissingJsonContentType;
 }
 
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Header contains a value that isn't visible ASCII"]
+    /// Rejection type for [`HeaderList`](super::HeaderList) used if a value
+    /// of the header isn't visible ASCII.
+    pub struct InvalidHeaderList;
+}
+
 define_rejection! {
     #[status = INTERNAL_SERVER_ERROR]
     #[body = "Missing request extension"]
@@ -80,6 +100,14 @@ define_rejection! {
     pub struct MissingPathParams;
 }
 
+define_rejection! {
+    #[status = INTERNAL_SERVER_ERROR]
+    #[body = "No wildcard path parameter found for matched route"]
+    /// Rejection type used by [`PathAndTail`](super::PathAndTail) if the
+    /// matched route doesn't have a wildcard, such as `*rest`.
+    pub struct MissingWildcardParam;
+}
+
 define_rejection! {
     #[status = UNSUPPORTED_MEDIA_TYPE]
     #[body = "Form requests must have `Content-Type: application/x-www-form-urlencoded`"]
@@ -128,6 +156,73 @@ impl std::fmt::Display for FailedToDeserializeQueryString {
 
 impl std::error::Error for FailedToDeserializeQueryString {}
 
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Too many query parameters"]
+    /// Rejection type for [`BoundedQuery`](super::BoundedQuery) used if the
+    /// query string has more parameters than the allowed maximum.
+    pub struct TooManyQueryParams;
+}
+
+/// Rejection type for [`RequireContentType`](super::content_type::RequireContentType)
+/// used if the `Content-Type` header is missing or doesn't match the required
+/// media type.
+#[derive(Debug)]
+pub struct UnsupportedContentType {
+    expected: &'static str,
+}
+
+impl UnsupportedContentType {
+    pub(crate) fn new(expected: &'static str) -> Self {
+        Self { expected }
+    }
+
+    /// The media type that was required.
+    pub fn expected(&self) -> &'static str {
+        self.expected
+    }
+}
+
+impl IntoResponse for UnsupportedContentType {
+    fn into_response(self) -> Response {
+        (http::StatusCode::UNSUPPORTED_MEDIA_TYPE, self.to_string()).into_response()
+    }
+}
+
+impl std::fmt::Display for UnsupportedContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Expected request with `Content-Type: {}`", self.expected)
+    }
+}
+
+impl std::error::Error for UnsupportedContentType {}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Missing `Range` header"]
+    /// Rejection type for [`Range`](super::Range) used if the `Range` header
+    /// is missing.
+    pub struct MissingRangeHeader;
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Invalid `Range` header"]
+    /// Rejection type for [`Range`](super::Range) used if the `Range` header
+    /// couldn't be parsed or doesn't use the `bytes` unit.
+    pub struct InvalidRangeHeader;
+}
+
+#[cfg(feature = "query")]
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Invalid pagination parameters"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "query")))]
+    /// Rejection type for [`Pagination`](super::Pagination) used if the
+    /// pagination parameters in the query string are invalid.
+    pub struct InvalidPagination(Error);
+}
+
 composite_rejection! {
     /// Rejection used for [`Query`](super::Query).
     ///
@@ -135,6 +230,7 @@ composite_rejection! {
     /// can fail.
     pub enum QueryRejection {
         FailedToDeserializeQueryString,
+        TooManyQueryParams,
     }
 }
 
@@ -160,6 +256,7 @@ composite_rejection! {
     pub enum JsonRejection {
         JsonDataError,
         JsonSyntaxError,
+        JsonTooDeep,
         MissingJsonContentType,
         BytesRejection,
     }
@@ -175,6 +272,210 @@ composite_rejection! {
     }
 }
 
+#[cfg(feature = "authorization")]
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Missing `Authorization` header"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "authorization")))]
+    /// Rejection type for [`Authorization`](super::authorization::Authorization)
+    /// used if the request has no credentials for the scheme and no
+    /// [`Challenge`](super::authorization::Challenge) is configured.
+    pub struct MissingAuthorization;
+}
+
+#[cfg(feature = "authorization")]
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Invalid `Authorization` header"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "authorization")))]
+    /// Rejection type for [`Authorization`](super::authorization::Authorization)
+    /// used if the credentials in the `Authorization` header are malformed.
+    pub struct InvalidAuthorizationHeader;
+}
+
+/// Rejection type for [`Authorization`](super::authorization::Authorization)
+/// used if the request has no credentials for the scheme and a
+/// [`Challenge`](super::authorization::Challenge) is configured.
+///
+/// Responds with `401 Unauthorized` and a `WWW-Authenticate` header.
+#[cfg(feature = "authorization")]
+#[cfg_attr(docsrs, doc(cfg(feature = "authorization")))]
+#[derive(Debug)]
+pub struct AuthenticationRequired {
+    challenge: http::HeaderValue,
+}
+
+#[cfg(feature = "authorization")]
+impl AuthenticationRequired {
+    pub(crate) fn new(challenge: http::HeaderValue) -> Self {
+        Self { challenge }
+    }
+}
+
+#[cfg(feature = "authorization")]
+impl IntoResponse for AuthenticationRequired {
+    fn into_response(self) -> Response {
+        (
+            http::StatusCode::UNAUTHORIZED,
+            [(http::header::WWW_AUTHENTICATE, self.challenge)],
+            "Authentication required",
+        )
+            .into_response()
+    }
+}
+
+#[cfg(feature = "authorization")]
+impl std::fmt::Display for AuthenticationRequired {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Authentication required")
+    }
+}
+
+#[cfg(feature = "authorization")]
+impl std::error::Error for AuthenticationRequired {}
+
+/// Rejection type for [`RateLimited`](super::rate_limit::RateLimited) used if
+/// the client has made too many requests.
+///
+/// Responds with `429 Too Many Requests` and a `Retry-After` header with the
+/// time until the client may make another request.
+#[derive(Debug)]
+pub struct TooManyRequests {
+    retry_after: std::time::Duration,
+}
+
+impl TooManyRequests {
+    pub(crate) fn new(retry_after: std::time::Duration) -> Self {
+        Self { retry_after }
+    }
+
+    /// The time until the client may make another request.
+    pub fn retry_after(&self) -> std::time::Duration {
+        self.retry_after
+    }
+}
+
+impl IntoResponse for TooManyRequests {
+    fn into_response(self) -> Response {
+        (
+            http::StatusCode::TOO_MANY_REQUESTS,
+            crate::response::RetryAfter(self.retry_after),
+            "Too many requests",
+        )
+            .into_response()
+    }
+}
+
+impl std::fmt::Display for TooManyRequests {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Too many requests")
+    }
+}
+
+impl std::error::Error for TooManyRequests {}
+
+composite_rejection! {
+    /// Rejection used for [`RateLimited`](super::rate_limit::RateLimited).
+    ///
+    /// Contains one variant for each way the
+    /// [`RateLimited`](super::rate_limit::RateLimited) extractor can fail.
+    pub enum RateLimitedRejection {
+        ExtensionRejection,
+        TooManyRequests,
+    }
+}
+
+#[cfg(feature = "authorization")]
+composite_rejection! {
+    /// Rejection used for [`Authorization`](super::authorization::Authorization).
+    ///
+    /// Contains one variant for each way the
+    /// [`Authorization`](super::authorization::Authorization) extractor can
+    /// fail.
+    #[cfg_attr(docsrs, doc(cfg(feature = "authorization")))]
+    pub enum AuthorizationRejection {
+        MissingAuthorization,
+        InvalidAuthorizationHeader,
+        AuthenticationRequired,
+    }
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Missing API version"]
+    /// Rejection type for [`ApiVersion`](super::api_version::ApiVersion) used
+    /// if the request doesn't specify a version and there is no default.
+    pub struct MissingApiVersion;
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Unsupported API version"]
+    /// Rejection type for [`ApiVersion`](super::api_version::ApiVersion) used
+    /// if the version read from the path or a header isn't supported.
+    pub struct UnsupportedApiVersion;
+}
+
+define_rejection! {
+    #[status = NOT_ACCEPTABLE]
+    #[body = "Unsupported API version in `Accept` header"]
+    /// Rejection type for [`ApiVersion`](super::api_version::ApiVersion) used
+    /// if the version read from the `Accept` header isn't supported.
+    pub struct NotAcceptableApiVersion;
+}
+
+composite_rejection! {
+    /// Rejection used for [`ApiVersion`](super::api_version::ApiVersion).
+    ///
+    /// Contains one variant for each way the
+    /// [`ApiVersion`](super::api_version::ApiVersion) extractor can fail.
+    pub enum ApiVersionRejection {
+        MissingExtension,
+        MissingApiVersion,
+        UnsupportedApiVersion,
+        NotAcceptableApiVersion,
+    }
+}
+
+define_rejection! {
+    #[status = INTERNAL_SERVER_ERROR]
+    #[body = "Missing TLS information. Is the app served with a TLS acceptor that provides `TlsInfo`?"]
+    /// Rejection type for [`TlsInfo`](super::tls::TlsInfo) and
+    /// [`PeerCertificate`](super::tls::PeerCertificate) used if the
+    /// connection's TLS information wasn't provided.
+    pub struct MissingTlsInfo;
+}
+
+define_rejection! {
+    #[status = FORBIDDEN]
+    #[body = "Client certificate required"]
+    /// Rejection type for [`PeerCertificate`](super::tls::PeerCertificate)
+    /// used if the client didn't present a certificate.
+    pub struct MissingPeerCertificate;
+}
+
+composite_rejection! {
+    /// Rejection used for [`PeerCertificate`](super::tls::PeerCertificate).
+    ///
+    /// Contains one variant for each way the
+    /// [`PeerCertificate`](super::tls::PeerCertificate) extractor can fail.
+    pub enum PeerCertificateRejection {
+        MissingTlsInfo,
+        MissingPeerCertificate,
+    }
+}
+
+composite_rejection! {
+    /// Rejection used for [`Range`](super::Range).
+    ///
+    /// Contains one variant for each way the [`Range`](super::Range) extractor
+    /// can fail.
+    pub enum RangeRejection {
+        MissingRangeHeader,
+        InvalidRangeHeader,
+    }
+}
+
 composite_rejection! {
     /// Rejection used for [`Path`](super::Path).
     ///
@@ -183,6 +484,58 @@ composite_rejection! {
     pub enum PathRejection {
         FailedToDeserializePathParams,
         MissingPathParams,
+        MissingWildcardParam,
+    }
+}
+
+/// Rejection type for [`ParsedPath`](super::ParsedPath) used if a path
+/// parameter couldn't be parsed.
+///
+/// Responds with the response of the
+/// [`FromPathParam::Rejection`](super::path::FromPathParam::Rejection) of the
+/// parameter's type.
+#[derive(Debug)]
+pub struct InvalidPathParam {
+    key: String,
+    response: Box<Response>,
+}
+
+impl InvalidPathParam {
+    pub(crate) fn new(key: &str, response: Response) -> Self {
+        Self {
+            key: key.to_owned(),
+            response: Box::new(response),
+        }
+    }
+
+    /// The name of the parameter that couldn't be parsed.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl IntoResponse for InvalidPathParam {
+    fn into_response(self) -> Response {
+        *self.response
+    }
+}
+
+impl std::fmt::Display for InvalidPathParam {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid value for path parameter `{}`", self.key)
+    }
+}
+
+impl std::error::Error for InvalidPathParam {}
+
+composite_rejection! {
+    /// Rejection used for [`ParsedPath`](super::ParsedPath).
+    ///
+    /// Contains one variant for each way the [`ParsedPath`](super::ParsedPath)
+    /// extractor can fail.
+    pub enum ParsedPathRejection {
+        PathRejection,
+        InvalidPathParam,
     }
 }
 
@@ -5,7 +5,8 @@ use crate::{
 };
 use async_trait::async_trait;
 use futures_util::stream::Stream;
-use http::Uri;
+use http::{header, Uri};
+use http_body::SizeHint;
 use std::{
     convert::Infallible,
     fmt,
@@ -208,6 +209,107 @@ where
     }
 }
 
+/// Extractor that gets the size of the request body, if known, without
+/// consuming it.
+///
+/// This can be used to reject requests that are too large, or to pre-allocate
+/// buffers, before reading the body with another extractor such as
+/// [`BodyStream`]. It must therefore come before the body extractor in the
+/// handler's arguments.
+///
+/// The size is only a hint since clients can send a body that doesn't match
+/// the `Content-Length` header. hyper will fail the body in that case.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::{BodySizeHint, BodyStream},
+///     http::StatusCode,
+///     routing::post,
+///     Router,
+/// };
+///
+/// async fn upload(hint: BodySizeHint, body: BodyStream) -> Result<(), StatusCode> {
+///     match hint.content_length() {
+///         Some(length) if length > 1024 * 1024 => return Err(StatusCode::PAYLOAD_TOO_LARGE),
+///         Some(length) => {
+///             let buf = Vec::<u8>::with_capacity(length as usize);
+///             // ...
+///         }
+///         None => {
+///             // the size is unknown, for example because the body is chunked
+///         }
+///     }
+///
+///     Ok(())
+/// }
+///
+/// let app = Router::new().route("/upload", post(upload));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+#[derive(Debug, Clone)]
+pub struct BodySizeHint {
+    size_hint: SizeHint,
+    content_length: Option<u64>,
+    chunked: bool,
+}
+
+impl BodySizeHint {
+    /// The [`SizeHint`] of the request body.
+    pub fn size_hint(&self) -> &SizeHint {
+        &self.size_hint
+    }
+
+    /// The value of the `Content-Length` header, if it's present and valid.
+    pub fn content_length(&self) -> Option<u64> {
+        self.content_length
+    }
+
+    /// Whether the request uses `Transfer-Encoding: chunked`, in which case
+    /// the size of the body isn't known up front.
+    pub fn is_chunked(&self) -> bool {
+        self.chunked
+    }
+}
+
+#[async_trait]
+impl<B> FromRequest<B> for BodySizeHint
+where
+    B: HttpBody + Send,
+{
+    type Rejection = BodyAlreadyExtracted;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let size_hint = req
+            .body()
+            .ok_or_else(BodyAlreadyExtracted::default)?
+            .size_hint();
+
+        let content_length = req
+            .headers()
+            .get(header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+
+        let chunked = req
+            .headers()
+            .get_all(header::TRANSFER_ENCODING)
+            .into_iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|encoding| encoding.trim().eq_ignore_ascii_case("chunked"));
+
+        Ok(Self {
+            size_hint,
+            content_length,
+            chunked,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -268,4 +370,79 @@ mod tests {
         let res = client.get("/").body("foo").send().await;
         assert_eq!(res.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn body_size_hint() {
+        use super::BodySizeHint;
+        use crate::extract::{FromRequest, RequestParts};
+        use futures_util::stream;
+        use std::io;
+
+        let req = Request::post("/")
+            .header("content-length", "5")
+            .body(Body::from("hello"))
+            .unwrap();
+        let hint = BodySizeHint::from_request(&mut RequestParts::new(req))
+            .await
+            .unwrap();
+        assert_eq!(hint.content_length(), Some(5));
+        assert_eq!(hint.size_hint().exact(), Some(5));
+        assert!(!hint.is_chunked());
+
+        let chunks: Vec<io::Result<_>> = vec![Ok("hel"), Ok("lo")];
+        let req = Request::post("/")
+            .header("transfer-encoding", "gzip, chunked")
+            .body(Body::wrap_stream(stream::iter(chunks)))
+            .unwrap();
+        let mut req = RequestParts::new(req);
+        let hint = BodySizeHint::from_request(&mut req).await.unwrap();
+        assert_eq!(hint.content_length(), None);
+        assert_eq!(hint.size_hint().exact(), None);
+        assert!(hint.is_chunked());
+
+        // the body is still there for other extractors
+        let body = String::from_request(&mut req).await.unwrap();
+        assert_eq!(body, "hello");
+    }
+
+    #[tokio::test]
+    async fn trailers() {
+        use crate::extract::{FromRequest, RequestParts, Trailers};
+        use bytes::Bytes;
+        use http::HeaderMap;
+
+        async fn request_with_trailers() -> RequestParts<Body> {
+            let (mut tx, body) = Body::channel();
+            tokio::spawn(async move {
+                tx.send_data(Bytes::from("hello")).await.unwrap();
+                let mut trailers = HeaderMap::new();
+                trailers.insert("x-checksum", "abc".parse().unwrap());
+                tx.send_trailers(trailers).await.unwrap();
+            });
+            RequestParts::new(Request::post("/").body(body).unwrap())
+        }
+
+        let mut req = request_with_trailers().await;
+        let Trailers(trailers) = Trailers::from_request(&mut req).await.unwrap();
+        assert_eq!(trailers["x-checksum"], "abc");
+        let body = String::from_request(&mut req).await.unwrap();
+        assert_eq!(body, "hello");
+        assert!(String::from_request(&mut req).await.is_err());
+
+        // without trailers
+        let mut req = RequestParts::new(Request::post("/").body(Body::from("hello")).unwrap());
+        let Trailers(trailers) = Trailers::from_request(&mut req).await.unwrap();
+        assert!(trailers.is_empty());
+
+        // after the body, which doesn't keep the trailers
+        let mut req = request_with_trailers().await;
+        let body = String::from_request(&mut req).await.unwrap();
+        assert_eq!(body, "hello");
+        assert!(Trailers::from_request(&mut req).await.is_err());
+
+        // after the body was streamed
+        let mut req = request_with_trailers().await;
+        let _ = Request::<Body>::from_request(&mut req).await.unwrap();
+        assert!(Trailers::from_request(&mut req).await.is_err());
+    }
 }
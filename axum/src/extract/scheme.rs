@@ -0,0 +1,139 @@
+use super::{FromRequest, RequestParts};
+use async_trait::async_trait;
+use http::header::{HeaderMap, FORWARDED};
+use std::convert::Infallible;
+
+const X_FORWARDED_PROTO_HEADER_KEY: &str = "X-Forwarded-Proto";
+
+/// Extractor that resolves the scheme of the request, such as `http` or `https`.
+///
+/// The scheme is resolved through the following, in order:
+/// - `Forwarded` header
+/// - `X-Forwarded-Proto` header
+/// - request target / URI
+///
+/// Requests received directly by hyper over HTTP/1 usually don't include the
+/// scheme in the request target so this will be `None` unless one of the
+/// headers is set by a proxy.
+///
+/// Note that user agents can set `Forwarded` and `X-Forwarded-Proto` headers to
+/// arbitrary values so make sure to validate them to avoid security issues.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::Scheme,
+///     http::{Method, Version},
+///     routing::get,
+///     Router,
+/// };
+///
+/// async fn handler(method: Method, version: Version, Scheme(scheme): Scheme) {
+///     // ...
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+#[derive(Debug, Clone)]
+pub struct Scheme(pub Option<String>);
+
+#[async_trait]
+impl<B> FromRequest<B> for Scheme
+where
+    B: Send,
+{
+    type Rejection = Infallible;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        if let Some(scheme) = parse_forwarded(req.headers()) {
+            return Ok(Scheme(Some(scheme.to_owned())));
+        }
+
+        if let Some(scheme) = req
+            .headers()
+            .get(X_FORWARDED_PROTO_HEADER_KEY)
+            .and_then(|scheme| scheme.to_str().ok())
+        {
+            return Ok(Scheme(Some(scheme.trim().to_owned())));
+        }
+
+        Ok(Scheme(req.uri().scheme_str().map(ToOwned::to_owned)))
+    }
+}
+
+fn parse_forwarded(headers: &HeaderMap) -> Option<&str> {
+    // if there are multiple `Forwarded` `HeaderMap::get` will return the first one
+    let forwarded_values = headers.get(FORWARDED)?.to_str().ok()?;
+
+    // get the first set of values
+    let first_value = forwarded_values.split(',').next()?;
+
+    // find the value of the `proto` field
+    first_value.split(';').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case("proto")
+            .then(|| value.trim().trim_matches('"'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{routing::get, test_helpers::TestClient, Router};
+    use http::{Method, Version};
+
+    fn test_client() -> TestClient {
+        async fn scheme_as_body(Scheme(scheme): Scheme) -> String {
+            scheme.unwrap_or_else(|| "none".to_owned())
+        }
+
+        TestClient::new(Router::new().route("/", get(scheme_as_body)))
+    }
+
+    #[tokio::test]
+    async fn no_scheme() {
+        let scheme = test_client().get("/").send().await.text().await;
+        assert_eq!(scheme, "none");
+    }
+
+    #[tokio::test]
+    async fn x_forwarded_proto_header() {
+        let scheme = test_client()
+            .get("/")
+            .header(X_FORWARDED_PROTO_HEADER_KEY, "https")
+            .send()
+            .await
+            .text()
+            .await;
+        assert_eq!(scheme, "https");
+    }
+
+    #[tokio::test]
+    async fn forwarded_precedence_over_x_forwarded_proto() {
+        let scheme = test_client()
+            .get("/")
+            .header(FORWARDED, "for=192.0.2.60;proto=https;by=203.0.113.43")
+            .header(X_FORWARDED_PROTO_HEADER_KEY, "http")
+            .send()
+            .await
+            .text()
+            .await;
+        assert_eq!(scheme, "https");
+    }
+
+    #[tokio::test]
+    async fn method_and_version() {
+        async fn handler(method: Method, version: Version) -> String {
+            format!("{} {:?}", method, version)
+        }
+
+        let client = TestClient::new(Router::new().route("/", get(handler)));
+        let res = client.get("/").send().await;
+        assert_eq!(res.text().await, "GET HTTP/1.1");
+    }
+}
@@ -0,0 +1,227 @@
+use super::{FromRequest, RequestParts};
+use async_trait::async_trait;
+use std::{
+    any::type_name,
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Extractor that measures how long another extractor takes.
+///
+/// `Timed<E>` runs `E` and records how long it took, whether or not it
+/// succeeded, in the request's [`ExtractionTimings`] under the type name of
+/// `E`. If the request doesn't have `ExtractionTimings` yet they're added, so
+/// extractors that run later can get them with
+/// [`Extension<ExtractionTimings>`](crate::Extension).
+///
+/// The rejection is the same as the rejection of `E`.
+///
+/// See [`ExtractionTimings`] for how to log the timings of every request in a
+/// middleware.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     async_trait,
+///     extract::{ExtractionTimings, FromRequest, RequestParts, Timed},
+///     http::StatusCode,
+///     routing::get,
+///     Extension, Router,
+/// };
+///
+/// struct CurrentUser {
+///     // ...
+/// }
+///
+/// #[async_trait]
+/// impl<B: Send> FromRequest<B> for CurrentUser {
+///     type Rejection = StatusCode;
+///
+///     async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+///         // look up the user in the database
+///         # unimplemented!()
+///     }
+/// }
+///
+/// async fn handler(
+///     Timed(user): Timed<CurrentUser>,
+///     Extension(timings): Extension<ExtractionTimings>,
+/// ) {
+///     for (extractor, elapsed) in timings.get() {
+///         tracing::debug!(extractor, ?elapsed, "extracted");
+///     }
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timed<E>(pub E);
+
+#[async_trait]
+impl<E, B> FromRequest<B> for Timed<E>
+where
+    E: FromRequest<B>,
+    B: Send,
+{
+    type Rejection = E::Rejection;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let start = Instant::now();
+        let result = E::from_request(req).await;
+        let elapsed = start.elapsed();
+
+        let extensions = req.extensions_mut();
+        if extensions.get::<ExtractionTimings>().is_none() {
+            extensions.insert(ExtractionTimings::new());
+        }
+        extensions
+            .get::<ExtractionTimings>()
+            .unwrap()
+            .record(type_name::<E>(), elapsed);
+
+        result.map(Timed)
+    }
+}
+
+impl<E> Deref for Timed<E> {
+    type Target = E;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E> DerefMut for Timed<E> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// How long the [`Timed`] extractors of a request took.
+///
+/// Clones share the same timings, so a middleware can add
+/// `ExtractionTimings` to the request and read them once the response has
+/// been produced, after all extractors have run.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{
+///     extract::{ExtractionTimings, Timed},
+///     http::{Request, Uri},
+///     middleware::{self, Next},
+///     response::Response,
+///     routing::get,
+///     Router,
+/// };
+///
+/// async fn log_extraction_timings<B>(mut req: Request<B>, next: Next<B>) -> Response {
+///     let timings = ExtractionTimings::new();
+///     req.extensions_mut().insert(timings.clone());
+///
+///     let res = next.run(req).await;
+///
+///     for (extractor, elapsed) in timings.get() {
+///         tracing::debug!(extractor, ?elapsed, "extracted");
+///     }
+///
+///     res
+/// }
+///
+/// async fn handler(Timed(uri): Timed<Uri>) {}
+///
+/// let app = Router::new()
+///     .route("/", get(handler))
+///     .layer(middleware::from_fn(log_extraction_timings));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ExtractionTimings {
+    timings: Arc<Mutex<Vec<(&'static str, Duration)>>>,
+}
+
+impl ExtractionTimings {
+    /// Create new empty `ExtractionTimings`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the type name of each [`Timed`] extractor that has run and how long
+    /// it took, in the order they ran.
+    pub fn get(&self) -> Vec<(&'static str, Duration)> {
+        self.timings.lock().unwrap().clone()
+    }
+
+    fn record(&self, extractor: &'static str, elapsed: Duration) {
+        self.timings.lock().unwrap().push((extractor, elapsed));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        middleware::{self, Next},
+        routing::get,
+        test_helpers::*,
+        Extension, Router,
+    };
+    use http::{Method, Request, StatusCode, Uri};
+
+    #[tokio::test]
+    async fn records_timings() {
+        let recorded = ExtractionTimings::new();
+
+        let app = Router::new()
+            .route(
+                "/",
+                get(
+                    |_: Timed<Method>,
+                     Timed(uri): Timed<Uri>,
+                     Extension(timings): Extension<ExtractionTimings>| async move {
+                        assert_eq!(timings.get().len(), 2);
+                        uri.to_string()
+                    },
+                ),
+            )
+            .route("/rejected", get(|_: Timed<Extension<()>>| async {}))
+            .layer(middleware::from_fn({
+                let recorded = recorded.clone();
+                move |mut req: Request<_>, next: Next<_>| {
+                    let recorded = recorded.clone();
+                    async move {
+                        req.extensions_mut().insert(recorded);
+                        next.run(req).await
+                    }
+                }
+            }));
+        let client = TestClient::new(app);
+
+        let res = client.get("/").send().await;
+        assert_eq!(res.text().await, "/");
+
+        let res = client.get("/rejected").send().await;
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let extractors = recorded
+            .get()
+            .into_iter()
+            .map(|(extractor, _)| extractor)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            extractors,
+            [
+                type_name::<Method>(),
+                type_name::<Uri>(),
+                type_name::<Extension<()>>()
+            ]
+        );
+    }
+}
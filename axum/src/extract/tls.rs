@@ -0,0 +1,207 @@
+//! Extractors for information about TLS connections.
+//!
+//! See [`TlsInfo`] for more details.
+
+use super::{rejection::*, ConnectInfo, FromRequest, RequestParts};
+use async_trait::async_trait;
+
+/// Information about the TLS connection a request was received on, such as
+/// the server name the client asked for and the client's certificates.
+///
+/// axum doesn't terminate TLS itself so this has to be provided by the TLS
+/// acceptor. An acceptor can do so in two ways:
+///
+/// - By inserting a `TlsInfo` into the extensions of every request received
+///   on the connection.
+/// - By implementing [`Connected`] for `TlsInfo` with its connection type, so
+///   it's available when the app is run with
+///   [`Router::into_make_service_with_connect_info::<TlsInfo>`]. That's the
+///   same way [`ConnectInfo`] is provided.
+///
+/// `TlsInfo` can then be extracted in handlers. Requests without TLS
+/// information are rejected with `500 Internal Server Error` since that means
+/// the app isn't served the way it expects.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{
+///     extract::tls::{PeerCertificate, TlsInfo},
+///     routing::get,
+///     Router,
+/// };
+///
+/// async fn handler(tls: TlsInfo) -> String {
+///     format!("hello {}", tls.server_name().unwrap_or("unknown"))
+/// }
+///
+/// // requires the client to present a certificate
+/// async fn admin(PeerCertificate(der): PeerCertificate) {
+///     // parse the certificate and check the client is allowed access
+/// }
+///
+/// let app = Router::new()
+///     .route("/", get(handler))
+///     .route("/admin", get(admin));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+///
+/// [`Connected`]: super::connect_info::Connected
+/// [`Router::into_make_service_with_connect_info::<TlsInfo>`]: crate::routing::Router::into_make_service_with_connect_info
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlsInfo {
+    server_name: Option<String>,
+    peer_certificates: Vec<Vec<u8>>,
+}
+
+impl TlsInfo {
+    /// Create a new `TlsInfo` without a server name or peer certificates.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the server name the client asked for with the SNI extension.
+    pub fn with_server_name(mut self, server_name: impl Into<String>) -> Self {
+        self.server_name = Some(server_name.into());
+        self
+    }
+
+    /// Set the DER encoded certificate chain the client presented, starting
+    /// with the client's own certificate.
+    pub fn with_peer_certificates<I>(mut self, certificates: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Vec<u8>>,
+    {
+        self.peer_certificates = certificates.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The server name the client asked for with the SNI extension, if any.
+    pub fn server_name(&self) -> Option<&str> {
+        self.server_name.as_deref()
+    }
+
+    /// The DER encoded certificate chain the client presented, starting with
+    /// the client's own certificate.
+    ///
+    /// Empty if the client didn't present a certificate.
+    pub fn peer_certificates(&self) -> &[Vec<u8>] {
+        &self.peer_certificates
+    }
+}
+
+#[async_trait]
+impl<B> FromRequest<B> for TlsInfo
+where
+    B: Send,
+{
+    type Rejection = MissingTlsInfo;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let extensions = req.extensions();
+
+        extensions
+            .get::<TlsInfo>()
+            .or_else(|| {
+                extensions
+                    .get::<ConnectInfo<TlsInfo>>()
+                    .map(|ConnectInfo(tls)| tls)
+            })
+            .cloned()
+            .ok_or(MissingTlsInfo)
+    }
+}
+
+/// Extractor for the DER encoded certificate the client presented for mutual
+/// TLS.
+///
+/// This is the first certificate of [`TlsInfo::peer_certificates`]. Requests
+/// without a client certificate are rejected with `403 Forbidden`. See
+/// [`TlsInfo`] for how the TLS information is provided and an example.
+///
+/// The certificate is only verified to the extent the TLS acceptor verifies
+/// it, checking whether the client is allowed access is up to the
+/// application.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerCertificate(pub Vec<u8>);
+
+#[async_trait]
+impl<B> FromRequest<B> for PeerCertificate
+where
+    B: Send,
+{
+    type Rejection = PeerCertificateRejection;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let tls = TlsInfo::from_request(req).await?;
+
+        let certificate = tls
+            .peer_certificates
+            .into_iter()
+            .next()
+            .ok_or(MissingPeerCertificate)?;
+
+        Ok(Self(certificate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{routing::get, test_helpers::*, Extension, Router};
+    use http::StatusCode;
+
+    async fn server_name(tls: TlsInfo) -> String {
+        tls.server_name().unwrap_or_default().to_owned()
+    }
+
+    async fn peer_certificate(PeerCertificate(der): PeerCertificate) -> Vec<u8> {
+        der
+    }
+
+    fn app() -> Router {
+        Router::new()
+            .route("/server-name", get(server_name))
+            .route("/peer-certificate", get(peer_certificate))
+    }
+
+    #[tokio::test]
+    async fn extension() {
+        let tls = TlsInfo::new()
+            .with_server_name("example.com")
+            .with_peer_certificates([b"leaf".to_vec(), b"intermediate".to_vec()]);
+        let client = TestClient::new(app().layer(Extension(tls)));
+
+        let res = client.get("/server-name").send().await;
+        assert_eq!(res.text().await, "example.com");
+
+        let res = client.get("/peer-certificate").send().await;
+        assert_eq!(res.text().await, "leaf");
+    }
+
+    #[tokio::test]
+    async fn connect_info() {
+        let tls = TlsInfo::new().with_server_name("example.com");
+        let client = TestClient::new(app().layer(Extension(ConnectInfo(tls))));
+
+        let res = client.get("/server-name").send().await;
+        assert_eq!(res.text().await, "example.com");
+
+        let res = client.get("/peer-certificate").send().await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn missing() {
+        let client = TestClient::new(app());
+
+        let res = client.get("/server-name").send().await;
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let res = client.get("/peer-certificate").send().await;
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
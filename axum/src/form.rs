@@ -1,5 +1,5 @@
 use crate::body::{Bytes, HttpBody};
-use crate::extract::{has_content_type, rejection::*, FromRequest, RequestParts};
+use crate::extract::{has_content_type, rejection::*, FromRequest, RawQuery, RequestParts};
 use crate::BoxError;
 use async_trait::async_trait;
 use axum_core::response::{IntoResponse, Response};
@@ -109,11 +109,76 @@ impl<T> Deref for Form<T> {
     }
 }
 
+/// Extractor that deserializes URL encoded data from either the query string or
+/// the request body, depending on the method.
+///
+/// `GET` and `HEAD` requests are read from the query string and all other
+/// requests from an `application/x-www-form-urlencoded` body, like [`Form`].
+/// This is useful for endpoints that accept the same data from both a search
+/// form submitted with `GET` and one submitted with `POST`.
+///
+/// ```rust
+/// use axum::{extract::FormOrQuery, routing::get, Router};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Search {
+///     q: String,
+/// }
+///
+/// async fn search(FormOrQuery(search): FormOrQuery<Search>) {
+///     // ...
+/// }
+///
+/// let app = Router::new().route("/search", get(search).post(search));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "form")))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FormOrQuery<T>(pub T);
+
+#[async_trait]
+impl<T, B> FromRequest<B> for FormOrQuery<T>
+where
+    T: DeserializeOwned,
+    B: HttpBody + Send,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Rejection = FormRejection;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        if req.method() == Method::GET || req.method() == Method::HEAD {
+            let RawQuery(query) = match RawQuery::from_request(req).await {
+                Ok(query) => query,
+                Err(err) => match err {},
+            };
+            let value = serde_urlencoded::from_str(query.as_deref().unwrap_or_default())
+                .map_err(FailedToDeserializeQueryString::__private_new)?;
+            Ok(FormOrQuery(value))
+        } else {
+            let Form(value) = Form::from_request(req).await?;
+            Ok(FormOrQuery(value))
+        }
+    }
+}
+
+impl<T> Deref for FormOrQuery<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::body::{Empty, Full};
     use crate::extract::RequestParts;
+    use crate::{routing::get, test_helpers::*, Router};
     use http::Request;
     use serde::{Deserialize, Serialize};
     use std::fmt::Debug;
@@ -226,4 +291,37 @@ mod tests {
             FormRejection::InvalidFormContentType(InvalidFormContentType)
         ));
     }
+
+    #[tokio::test]
+    async fn form_or_query() {
+        async fn handler(FormOrQuery(pagination): FormOrQuery<Pagination>) -> String {
+            format!("{:?} {:?}", pagination.size, pagination.page)
+        }
+
+        let app = Router::new().route("/", get(handler).head(handler).post(handler));
+        let client = TestClient::new(app);
+
+        let res = client.get("/?size=10&page=2").send().await;
+        assert_eq!(res.text().await, "Some(10) Some(2)");
+
+        let res = client.head("/?size=10").send().await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = client
+            .post("/?size=1")
+            .header(
+                http::header::CONTENT_TYPE,
+                mime::APPLICATION_WWW_FORM_URLENCODED.as_ref(),
+            )
+            .body("page=3")
+            .send()
+            .await;
+        assert_eq!(res.text().await, "None Some(3)");
+
+        let res = client.post("/?size=10").body("page=3").send().await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let res = client.get("/?size=abc").send().await;
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
 }
@@ -17,6 +17,12 @@ opaque_future! {
         >;
 }
 
+#[cfg(feature = "tracing")]
+opaque_future! {
+    /// The response future for [`Instrumented`](super::Instrumented).
+    pub type InstrumentedFuture<F> = tracing::instrument::Instrumented<F>;
+}
+
 pin_project! {
     /// The response future for [`Layered`](super::Layered).
     pub struct LayeredFuture<S, ReqBody>
@@ -110,6 +110,143 @@ pub trait Handler<T, B = Body>: Clone + Send + Sized + 'static {
         Layered::new(layer.layer(self.into_service()))
     }
 
+    /// Wrap the handler's future in a [`tracing`] span with the given name.
+    ///
+    /// The span is an `INFO` level span called `handler` with a `name` field.
+    /// If the `matched-path` feature is enabled the span also has a
+    /// `matched_path` field containing the [`MatchedPath`] of the request.
+    ///
+    /// Unlike [`tower_http::trace::TraceLayer`] this doesn't record anything
+    /// about the request or response on its own, it only makes events emitted
+    /// while the handler runs, including those from its extractors, part of the
+    /// span.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum::{
+    ///     routing::get,
+    ///     handler::Handler,
+    ///     Router,
+    /// };
+    ///
+    /// async fn list_users() {
+    ///     tracing::info!("listing users");
+    /// }
+    ///
+    /// let app = Router::new().route("/users", get(list_users.instrument("list_users")));
+    /// # async {
+    /// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+    /// # };
+    /// ```
+    ///
+    /// [`tracing`]: https://crates.io/crates/tracing
+    /// [`MatchedPath`]: crate::extract::MatchedPath
+    /// [`tower_http::trace::TraceLayer`]: https://docs.rs/tower-http/latest/tower_http/trace/struct.TraceLayer.html
+    #[cfg(feature = "tracing")]
+    fn instrument(self, name: &'static str) -> Instrumented<Self, T, B> {
+        Instrumented {
+            handler: self,
+            name,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Run the extractor `E` before the handler and reject the request if it
+    /// fails.
+    ///
+    /// This is useful for guards, such as extractors that check
+    /// authentication, that should run for a handler but whose value the
+    /// handler doesn't need. If `E` fails its rejection is returned and the
+    /// handler isn't called. The extracted value is dropped.
+    ///
+    /// `E` must not consume the request body.
+    ///
+    /// To run an extractor for several routes use
+    /// [`from_extractor`](crate::middleware::from_extractor) instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum::{
+    ///     async_trait,
+    ///     extract::{FromRequest, RequestParts},
+    ///     handler::Handler,
+    ///     http::StatusCode,
+    ///     routing::get,
+    ///     Router,
+    /// };
+    ///
+    /// struct AuthUser;
+    ///
+    /// #[async_trait]
+    /// impl<B> FromRequest<B> for AuthUser
+    /// where
+    ///     B: Send,
+    /// {
+    ///     type Rejection = StatusCode;
+    ///
+    ///     async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+    ///         // ...
+    ///         # unimplemented!()
+    ///     }
+    /// }
+    ///
+    /// async fn admin_dashboard() { /* ... */ }
+    ///
+    /// let app = Router::new().route("/admin", get(admin_dashboard.require::<AuthUser>()));
+    /// # async {
+    /// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+    /// # };
+    /// ```
+    fn require<E>(self) -> Require<Self, E, T, B> {
+        Require {
+            handler: self,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Check that successful responses of the handler deserialize as the
+    /// JSON type `R`.
+    ///
+    /// In builds with `debug_assertions` enabled the body of every `2xx`
+    /// response is buffered and parsed as `R`, panicking if that fails. This
+    /// catches handlers drifting from the type their clients expect while
+    /// running tests. The response is passed on unchanged.
+    ///
+    /// Without `debug_assertions` this does nothing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum::{handler::Handler, routing::get, Json, Router};
+    /// use serde::{Deserialize, Serialize};
+    /// use serde_json::{json, Value};
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// struct User {
+    ///     id: u64,
+    ///     name: String,
+    /// }
+    ///
+    /// // returns untyped JSON which should still match `User`
+    /// async fn get_user() -> Json<Value> {
+    ///     Json(json!({ "id": 1, "name": "alice" }))
+    /// }
+    ///
+    /// let app = Router::new().route("/user", get(get_user.assert_response::<User>()));
+    /// # async {
+    /// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+    /// # };
+    /// ```
+    #[cfg(feature = "json")]
+    fn assert_response<R>(self) -> AssertResponse<Self, R, T, B> {
+        AssertResponse {
+            handler: self,
+            _marker: PhantomData,
+        }
+    }
+
     /// Convert the handler into a [`Service`].
     ///
     /// This is commonly used together with [`Router::fallback`]:
@@ -315,6 +452,212 @@ impl<S, T> Layered<S, T> {
     }
 }
 
+/// A [`Handler`] that runs an extractor before calling another handler.
+///
+/// Created with [`Handler::require`]. See that method for more details.
+pub struct Require<H, E, T, B> {
+    handler: H,
+    _marker: PhantomData<fn() -> (E, T, B)>,
+}
+
+impl<H, E, T, B> fmt::Debug for Require<H, E, T, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Require")
+            .field("extractor", &std::any::type_name::<E>())
+            .finish()
+    }
+}
+
+impl<H, E, T, B> Clone for Require<H, E, T, B>
+where
+    H: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            handler: self.handler.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<H, E, T, B> Handler<T, B> for Require<H, E, T, B>
+where
+    H: Handler<T, B>,
+    E: FromRequest<B> + Send + 'static,
+    T: 'static,
+    B: Send + 'static,
+{
+    type Future = Pin<Box<dyn Future<Output = Response> + Send>>;
+
+    fn call(self, req: Request<B>) -> Self::Future {
+        Box::pin(async move {
+            let mut req = RequestParts::new(req);
+
+            if let Err(rejection) = E::from_request(&mut req).await {
+                return rejection.into_response();
+            }
+
+            match req.try_into_request() {
+                Ok(req) => self.handler.call(req).await,
+                Err(err) => err.into_response(),
+            }
+        })
+    }
+}
+
+/// A [`Handler`] that checks its responses deserialize as `R` in debug
+/// builds.
+///
+/// Created with [`Handler::assert_response`]. See that method for more details.
+#[cfg(feature = "json")]
+pub struct AssertResponse<H, R, T, B> {
+    handler: H,
+    _marker: PhantomData<fn() -> (R, T, B)>,
+}
+
+#[cfg(feature = "json")]
+impl<H, R, T, B> fmt::Debug for AssertResponse<H, R, T, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AssertResponse")
+            .field("response", &std::any::type_name::<R>())
+            .finish()
+    }
+}
+
+#[cfg(feature = "json")]
+impl<H, R, T, B> Clone for AssertResponse<H, R, T, B>
+where
+    H: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            handler: self.handler.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl<H, R, T, B> Handler<T, B> for AssertResponse<H, R, T, B>
+where
+    H: Handler<T, B>,
+    R: serde::de::DeserializeOwned + 'static,
+    T: 'static,
+    B: Send + 'static,
+{
+    type Future = Pin<Box<dyn Future<Output = Response> + Send>>;
+
+    fn call(self, req: Request<B>) -> Self::Future {
+        let future = self.handler.call(req);
+
+        Box::pin(async move {
+            let res = future.await;
+
+            if cfg!(debug_assertions) {
+                assert_response::<R>(res).await
+            } else {
+                res
+            }
+        })
+    }
+}
+
+#[cfg(feature = "json")]
+async fn assert_response<R>(res: Response) -> Response
+where
+    R: serde::de::DeserializeOwned,
+{
+    use crate::body::{self, Bytes, Full, StreamBody};
+
+    if !res.status().is_success() {
+        return res;
+    }
+
+    let (parts, body) = res.into_parts();
+    let bytes = match hyper::body::to_bytes(body).await {
+        Ok(bytes) => bytes,
+        // the body can't be checked so pass the error on to the client
+        Err(err) => {
+            let body = StreamBody::new(futures_util::stream::once(
+                async move { Err::<Bytes, _>(err) },
+            ));
+            return Response::from_parts(parts, body::boxed(body));
+        }
+    };
+
+    if let Err(err) = serde_json::from_slice::<R>(&bytes) {
+        panic!(
+            "response doesn't deserialize as `{}`: {}",
+            std::any::type_name::<R>(),
+            err
+        );
+    }
+
+    Response::from_parts(parts, body::boxed(Full::new(bytes)))
+}
+
+/// A [`Handler`] that runs inside a [`tracing`] span.
+///
+/// Created with [`Handler::instrument`]. See that method for more details.
+///
+/// [`tracing`]: https://crates.io/crates/tracing
+#[cfg(feature = "tracing")]
+pub struct Instrumented<H, T, B> {
+    handler: H,
+    name: &'static str,
+    _marker: PhantomData<fn() -> (T, B)>,
+}
+
+#[cfg(feature = "tracing")]
+impl<H, T, B> fmt::Debug for Instrumented<H, T, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Instrumented")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl<H, T, B> Clone for Instrumented<H, T, B>
+where
+    H: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            handler: self.handler.clone(),
+            name: self.name,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl<H, T, B> Handler<T, B> for Instrumented<H, T, B>
+where
+    H: Handler<T, B>,
+    T: 'static,
+    B: 'static,
+{
+    type Future = future::InstrumentedFuture<H::Future>;
+
+    fn call(self, req: Request<B>) -> Self::Future {
+        use tracing::Instrument;
+
+        let span = tracing::info_span!(
+            "handler",
+            name = self.name,
+            matched_path = tracing::field::Empty,
+        );
+
+        #[cfg(feature = "matched-path")]
+        if let Some(matched_path) = req.extensions().get::<crate::extract::MatchedPath>() {
+            span.record("matched_path", matched_path.as_str());
+        }
+
+        future::InstrumentedFuture::new(self.handler.call(req).instrument(span))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -333,4 +676,121 @@ mod tests {
         assert_eq!(res.status(), StatusCode::OK);
         assert_eq!(res.text().await, "you said: hi there!");
     }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn instrumented_handler() {
+        use crate::{routing::post, Router};
+
+        async fn handle(body: String) -> impl IntoResponse {
+            format!("you said: {}", body)
+        }
+
+        let app = Router::new().route("/:id", post(handle.instrument("handle")));
+        let client = TestClient::new(app);
+
+        let res = client.post("/1").body("hi there!").send().await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "you said: hi there!");
+    }
+
+    #[tokio::test]
+    async fn require() {
+        struct RequireAuth;
+
+        #[async_trait::async_trait]
+        impl<B> FromRequest<B> for RequireAuth
+        where
+            B: Send,
+        {
+            type Rejection = StatusCode;
+
+            async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+                if req.headers().contains_key("authorization") {
+                    Ok(Self)
+                } else {
+                    Err(StatusCode::UNAUTHORIZED)
+                }
+            }
+        }
+
+        async fn handle(body: String) -> impl IntoResponse {
+            format!("you said: {}", body)
+        }
+
+        let client = TestClient::new(handle.require::<RequireAuth>().into_service());
+
+        let res = client.post("/").body("hi there!").send().await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let res = client
+            .post("/")
+            .header("authorization", "secret")
+            .body("hi there!")
+            .send()
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "you said: hi there!");
+    }
+
+    #[cfg(all(feature = "json", debug_assertions))]
+    #[tokio::test]
+    async fn assert_response() {
+        use crate::Json;
+        use serde::Deserialize;
+        use serde_json::json;
+
+        #[derive(Deserialize)]
+        struct User {
+            #[allow(dead_code)]
+            name: String,
+        }
+
+        async fn valid() -> impl IntoResponse {
+            Json(json!({ "name": "alice" }))
+        }
+
+        async fn not_found() -> impl IntoResponse {
+            StatusCode::NOT_FOUND
+        }
+
+        let res = valid
+            .assert_response::<User>()
+            .call(Request::new(Body::empty()))
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(body, r#"{"name":"alice"}"#);
+
+        // error responses aren't checked
+        let res = not_found
+            .assert_response::<User>()
+            .call(Request::new(Body::empty()))
+            .await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[cfg(all(feature = "json", debug_assertions))]
+    #[tokio::test]
+    #[should_panic(expected = "response doesn't deserialize as")]
+    async fn assert_response_mismatch() {
+        use crate::Json;
+        use serde::Deserialize;
+        use serde_json::json;
+
+        #[derive(Deserialize)]
+        struct User {
+            #[allow(dead_code)]
+            name: String,
+        }
+
+        async fn handle() -> impl IntoResponse {
+            Json(json!({ "username": "alice" }))
+        }
+
+        handle
+            .assert_response::<User>()
+            .call(Request::new(Body::empty()))
+            .await;
+    }
 }
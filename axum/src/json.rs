@@ -106,36 +106,146 @@ where
     async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
         if json_content_type(req) {
             let bytes = Bytes::from_request(req).await?;
+            Ok(Json(from_bytes(&bytes)?))
+        } else {
+            Err(MissingJsonContentType.into())
+        }
+    }
+}
 
-            let value = match serde_json::from_slice(&bytes) {
-                Ok(value) => value,
-                Err(err) => {
-                    let rejection = match err.classify() {
-                        serde_json::error::Category::Data => JsonDataError::from_err(err).into(),
-                        serde_json::error::Category::Syntax | serde_json::error::Category::Eof => {
-                            JsonSyntaxError::from_err(err).into()
-                        }
-                        serde_json::error::Category::Io => {
-                            if cfg!(debug_assertions) {
-                                // we don't use `serde_json::from_reader` and instead always buffer
-                                // bodies first, so we shouldn't encounter any IO errors
-                                unreachable!()
-                            } else {
-                                JsonSyntaxError::from_err(err).into()
-                            }
-                        }
-                    };
-                    return Err(rejection);
-                }
-            };
+/// JSON Extractor that rejects bodies nested deeper than `MAX_DEPTH`.
+///
+/// Works like [`Json`] but before deserializing checks that arrays and objects
+/// are nested at most `MAX_DEPTH` levels deep, without recursing. Bodies
+/// nested deeper are rejected with [`JsonTooDeep`] and a `400 Bad Request`
+/// response. This protects public endpoints from bodies crafted to exhaust the
+/// stack while deserializing.
+///
+/// A scalar such as `1` has a depth of 0, `[1]` has a depth of 1 and
+/// `{"a": [1]}` has a depth of 2.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::BoundedJson,
+///     routing::post,
+///     Router,
+/// };
+/// use serde_json::Value;
+///
+/// async fn handler(BoundedJson(payload): BoundedJson<Value, 32>) {
+///     // `payload` is nested at most 32 levels deep
+/// }
+///
+/// let app = Router::new().route("/", post(handler));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+///
+/// [`JsonTooDeep`]: crate::extract::rejection::JsonTooDeep
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub struct BoundedJson<T, const MAX_DEPTH: usize>(pub T);
+
+#[async_trait]
+impl<T, B, const MAX_DEPTH: usize> FromRequest<B> for BoundedJson<T, MAX_DEPTH>
+where
+    T: DeserializeOwned,
+    B: HttpBody + Send,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Rejection = JsonRejection;
 
-            Ok(Json(value))
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        if json_content_type(req) {
+            let bytes = Bytes::from_request(req).await?;
+
+            if exceeds_depth(&bytes, MAX_DEPTH) {
+                return Err(JsonTooDeep.into());
+            }
+
+            Ok(BoundedJson(from_bytes(&bytes)?))
         } else {
             Err(MissingJsonContentType.into())
         }
     }
 }
 
+impl<T, const MAX_DEPTH: usize> Deref for BoundedJson<T, MAX_DEPTH> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const MAX_DEPTH: usize> DerefMut for BoundedJson<T, MAX_DEPTH> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+fn from_bytes<T>(bytes: &[u8]) -> Result<T, JsonRejection>
+where
+    T: DeserializeOwned,
+{
+    serde_json::from_slice(bytes).map_err(|err| match err.classify() {
+        serde_json::error::Category::Data => JsonDataError::from_err(err).into(),
+        serde_json::error::Category::Syntax | serde_json::error::Category::Eof => {
+            JsonSyntaxError::from_err(err).into()
+        }
+        serde_json::error::Category::Io => {
+            if cfg!(debug_assertions) {
+                // we don't use `serde_json::from_reader` and instead always buffer
+                // bodies first, so we shouldn't encounter any IO errors
+                unreachable!()
+            } else {
+                JsonSyntaxError::from_err(err).into()
+            }
+        }
+    })
+}
+
+/// Check if arrays and objects in `bytes` are nested deeper than `max_depth`.
+///
+/// Brackets inside strings are ignored. Invalid JSON is left for
+/// `serde_json` to reject.
+fn exceeds_depth(bytes: &[u8], max_depth: usize) -> bool {
+    let mut depth = 0_usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for &byte in bytes {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if byte == b'\\' {
+                escaped = true;
+            } else if byte == b'"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        match byte {
+            b'"' => in_string = true,
+            b'[' | b'{' => {
+                depth += 1;
+                if depth > max_depth {
+                    return true;
+                }
+            }
+            b']' | b'}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    false
+}
+
 fn json_content_type<B>(req: &RequestParts<B>) -> bool {
     let content_type = if let Some(content_type) = req.headers().get(header::CONTENT_TYPE) {
         content_type
@@ -186,35 +296,111 @@ where
     T: Serialize,
 {
     fn into_response(self) -> Response {
-        // Use a small initial capacity of 128 bytes like serde_json::to_vec
-        // https://docs.rs/serde_json/1.0.82/src/serde_json/ser.rs.html#2189
-        let mut buf = BytesMut::with_capacity(128).writer();
-        match serde_json::to_writer(&mut buf, &self.0) {
-            Ok(()) => (
-                [(
-                    header::CONTENT_TYPE,
-                    HeaderValue::from_static(mime::APPLICATION_JSON.as_ref()),
-                )],
-                buf.into_inner().freeze(),
-            )
-                .into_response(),
-            Err(err) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                [(
-                    header::CONTENT_TYPE,
-                    HeaderValue::from_static(mime::TEXT_PLAIN_UTF_8.as_ref()),
-                )],
-                err.to_string(),
-            )
-                .into_response(),
-        }
+        json_response(&self.0, false)
+    }
+}
+
+/// JSON response that is pretty-printed.
+///
+/// This works like [`Json`] as a response, but the JSON is indented to make it
+/// easier for humans to read, for example for debug endpoints or API
+/// explorers. Use [`Json`] when the response is read by programs since the
+/// compact form is smaller and faster to produce.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{
+///     response::PrettyJson,
+///     routing::get,
+///     Router,
+/// };
+/// use serde_json::{json, Value};
+///
+/// async fn debug_info() -> PrettyJson<Value> {
+///     PrettyJson(json!({ "version": "1.0", "uptime_seconds": 42 }))
+/// }
+///
+/// let app = Router::new().route("/debug", get(debug_info));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub struct PrettyJson<T>(pub T);
+
+impl<T> Deref for PrettyJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for PrettyJson<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for PrettyJson<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
+
+impl<T> IntoResponse for PrettyJson<T>
+where
+    T: Serialize,
+{
+    fn into_response(self) -> Response {
+        json_response(&self.0, true)
+    }
+}
+
+fn json_response<T>(value: &T, pretty: bool) -> Response
+where
+    T: Serialize,
+{
+    // Use a small initial capacity of 128 bytes like serde_json::to_vec
+    // https://docs.rs/serde_json/1.0.82/src/serde_json/ser.rs.html#2189
+    let mut buf = BytesMut::with_capacity(128).writer();
+    let result = if pretty {
+        serde_json::to_writer_pretty(&mut buf, value)
+    } else {
+        serde_json::to_writer(&mut buf, value)
+    };
+
+    match result {
+        Ok(()) => (
+            [(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(mime::APPLICATION_JSON.as_ref()),
+            )],
+            buf.into_inner().freeze(),
+        )
+            .into_response(),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            [(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(mime::TEXT_PLAIN_UTF_8.as_ref()),
+            )],
+            err.to_string(),
+        )
+            .into_response(),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{routing::post, test_helpers::*, Router};
+    use crate::{
+        routing::{get, post},
+        test_helpers::*,
+        Router,
+    };
     use serde::Deserialize;
     use serde_json::{json, Value};
 
@@ -290,4 +476,57 @@ mod tests {
 
         assert_eq!(res.status(), StatusCode::BAD_REQUEST);
     }
+
+    #[test]
+    fn depth() {
+        assert!(!exceeds_depth(b"1", 0));
+        assert!(exceeds_depth(b"[1]", 0));
+        assert!(!exceeds_depth(br#"{"a": [1]}"#, 2));
+        assert!(exceeds_depth(br#"{"a": [[1]]}"#, 2));
+        assert!(!exceeds_depth(br#"["[[[[", "\"[[["]"#, 1));
+    }
+
+    #[tokio::test]
+    async fn bounded_json_rejects_deep_bodies() {
+        let app = Router::new().route(
+            "/",
+            post(|BoundedJson(value): BoundedJson<Value, 4>| async move { value.to_string() }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client.post("/").json(&json!([[["foo"]]])).send().await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, r#"[[["foo"]]]"#);
+
+        let body = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+        let res = client
+            .post("/")
+            .header("content-type", "application/json")
+            .body(body)
+            .send()
+            .await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            res.text().await,
+            "Failed to parse the request body as JSON: maximum nesting depth exceeded"
+        );
+    }
+
+    #[tokio::test]
+    async fn pretty_json() {
+        let app = Router::new()
+            .route("/compact", get(|| async { Json(json!({ "a": [1] })) }))
+            .route("/pretty", get(|| async { PrettyJson(json!({ "a": [1] })) }));
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/compact").send().await;
+        assert_eq!(res.headers()["content-type"], "application/json");
+        assert_eq!(res.text().await, r#"{"a":[1]}"#);
+
+        let res = client.get("/pretty").send().await;
+        assert_eq!(res.headers()["content-type"], "application/json");
+        assert_eq!(res.text().await, "{\n  \"a\": [\n    1\n  ]\n}");
+    }
 }
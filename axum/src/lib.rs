@@ -209,6 +209,43 @@
 //! an extension that doesn't exist, perhaps because you forgot to add the
 //! middleware or because you're extracting the wrong type.
 //!
+//! Since the state is provided by a layer and not baked into the routes, the
+//! same routes can be served with different state, for example one per
+//! tenant, by adding a different [`Extension`](crate::extract::Extension) to
+//! clones of the router:
+//!
+//! ```rust,no_run
+//! use axum::{
+//!     extract::Extension,
+//!     routing::get,
+//!     Router,
+//! };
+//! use std::sync::Arc;
+//!
+//! struct Tenant {
+//!     name: String,
+//! }
+//!
+//! async fn handler(Extension(tenant): Extension<Arc<Tenant>>) -> String {
+//!     format!("Hello from {}", tenant.name)
+//! }
+//!
+//! let tenant_routes = Router::new().route("/", get(handler));
+//!
+//! let app = ["acme", "globex"]
+//!     .into_iter()
+//!     .fold(Router::new(), |app, name| {
+//!         let tenant = Arc::new(Tenant { name: name.to_owned() });
+//!         app.nest(
+//!             &format!("/{}", name),
+//!             tenant_routes.clone().layer(Extension(tenant)),
+//!         )
+//!     });
+//! # async {
+//! # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+//! # };
+//! ```
+//!
 //! ## Using closure captures
 //!
 //! State can also be passed directly to handlers using closure captures:
@@ -306,6 +343,12 @@
 //!
 //! Name | Description | Default?
 //! ---|---|---
+//! `authorization` | Enables the [`Authorization`](crate::extract::Authorization) extractor | No
+//! `catch-panic` | Enables [`Router::catch_panic`] | No
+//! `compression` | Enables [`Router::auto_compress`] | No
+//! `cors` | Enables [`MethodRouter::cors`](crate::routing::MethodRouter::cors) | No
+//! `decompression` | Enables [`Router::decompress_requests`] | No
+//! `error-details` | Includes the error message in responses for [`BoxError`] | No
 //! `headers` | Enables extracting typed headers via [`TypedHeader`] | No
 //! `http1` | Enables hyper's `http1` feature | Yes
 //! `http2` | Enables hyper's `http2` feature | No
@@ -315,15 +358,20 @@
 //! `multipart` | Enables parsing `multipart/form-data` requests with [`Multipart`] | No
 //! `original-uri` | Enables capturing of every request's original URI and the [`OriginalUri`] extractor | Yes
 //! `tower-log` | Enables `tower`'s `log` feature | Yes
+//! `tracing` | Enables [`Handler::instrument`] and logs warnings about discarded routes using [`tracing`] | No
+//! `tracing-errors` | Enables `tracing` and also logs errors returned as `Box<dyn Error>` and invalid response headers | No
 //! `ws` | Enables WebSockets support via [`extract::ws`] | No
 //! `form` | Enables the `Form` extractor | Yes
 //! `query` | Enables the `Query` extractor | Yes
+//! `test-util` | Enables utilities for testing applications in [`test_util`] | No
 //!
 //! [`TypedHeader`]: crate::extract::TypedHeader
 //! [`MatchedPath`]: crate::extract::MatchedPath
 //! [`Multipart`]: crate::extract::Multipart
 //! [`OriginalUri`]: crate::extract::OriginalUri
 //! [`tower`]: https://crates.io/crates/tower
+//! [`tracing`]: https://crates.io/crates/tracing
+//! [`Handler::instrument`]: crate::handler::Handler::instrument
 //! [`tower-http`]: https://crates.io/crates/tower-http
 //! [`tokio`]: http://crates.io/crates/tokio
 //! [`hyper`]: http://crates.io/crates/hyper
@@ -412,6 +460,10 @@ pub mod middleware;
 pub mod response;
 pub mod routing;
 
+#[cfg(feature = "test-util")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+pub mod test_util;
+
 #[cfg(test)]
 mod test_helpers;
 
@@ -446,3 +498,6 @@ pub use axum_core::{BoxError, Error};
 
 #[cfg(feature = "macros")]
 pub use axum_macros::debug_handler;
+
+#[cfg(feature = "macros")]
+pub use axum_macros::route;
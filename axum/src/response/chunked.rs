@@ -0,0 +1,106 @@
+use crate::{
+    body::{self, Bytes, StreamBody},
+    BoxError,
+};
+use axum_core::response::{IntoResponse, Response};
+use futures_util::stream::TryStream;
+use http::{header::TRANSFER_ENCODING, HeaderValue};
+
+/// Response that streams the items of a [`Stream`] as a chunked body.
+///
+/// Each item is sent to the client as soon as the stream yields it, without
+/// buffering, and `Transfer-Encoding: chunked` is set. The header only
+/// applies to HTTP/1.1; for HTTP/2 the stream is sent as data frames and the
+/// header is dropped by hyper.
+///
+/// Unlike [`StreamBody`] this can be returned directly from handlers without
+/// picking a body type.
+///
+/// # Errors
+///
+/// The status code and headers are sent before the stream is polled, so if
+/// the stream yields an error it is too late to respond with an error status.
+/// Instead the body is terminated and the connection is closed, leaving the
+/// client with an incomplete body.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{
+///     body::Bytes,
+///     routing::get,
+///     response::Chunked,
+///     Router,
+/// };
+/// use futures::stream::{self, Stream};
+/// use std::io;
+///
+/// async fn handler() -> Chunked<impl Stream<Item = io::Result<Bytes>>> {
+///     Chunked(stream::iter(vec![
+///         Ok(Bytes::from("Hello, ")),
+///         Ok(Bytes::from("world!")),
+///     ]))
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # async {
+/// # hyper::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+///
+/// [`Stream`]: futures_util::stream::Stream
+#[derive(Debug, Clone, Copy)]
+pub struct Chunked<S>(pub S);
+
+impl<S> IntoResponse for Chunked<S>
+where
+    S: TryStream + Send + 'static,
+    S::Ok: Into<Bytes>,
+    S::Error: Into<BoxError>,
+{
+    fn into_response(self) -> Response {
+        let mut res = Response::new(body::boxed(StreamBody::new(self.0)));
+        res.headers_mut()
+            .insert(TRANSFER_ENCODING, HeaderValue::from_static("chunked"));
+        res
+    }
+}
+
+impl<S> From<S> for Chunked<S> {
+    fn from(stream: S) -> Self {
+        Self(stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::stream;
+    use http::header::CONTENT_LENGTH;
+    use std::io;
+
+    #[tokio::test]
+    async fn streams_chunks() {
+        let chunks: Vec<io::Result<_>> = vec![Ok("foo"), Ok("bar")];
+        let res = Chunked(stream::iter(chunks)).into_response();
+
+        assert_eq!(res.headers()[TRANSFER_ENCODING], "chunked");
+        assert!(res.headers().get(CONTENT_LENGTH).is_none());
+
+        let bytes = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(&bytes[..], b"foobar");
+    }
+
+    #[tokio::test]
+    async fn error_terminates_body() {
+        let chunks: Vec<io::Result<_>> = vec![
+            Ok("foo"),
+            Err(io::Error::new(io::ErrorKind::Other, "oh no")),
+            Ok("bar"),
+        ];
+        let res = Chunked(stream::iter(chunks)).into_response();
+
+        let err = hyper::body::to_bytes(res.into_body()).await.unwrap_err();
+        assert_eq!(err.to_string(), "oh no");
+    }
+}
@@ -0,0 +1,227 @@
+use axum_core::response::{IntoResponse, Response};
+use bytes::{BufMut, BytesMut};
+use http::{header, HeaderValue, StatusCode};
+use serde::Serialize;
+use std::{fmt, io, marker::PhantomData};
+
+/// A serialization format used by [`Formatted`].
+///
+/// Implement this to support formats other than the built-in ones, such as
+/// MessagePack or CBOR.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::response::Format;
+/// use serde::Serialize;
+/// use std::io;
+///
+/// struct Csv;
+///
+/// impl Format for Csv {
+///     const CONTENT_TYPE: &'static str = "text/csv";
+///
+///     type Error = io::Error;
+///
+///     fn to_writer<W, T>(mut writer: W, value: &T) -> Result<(), Self::Error>
+///     where
+///         W: io::Write,
+///         T: Serialize + ?Sized,
+///     {
+///         // call into your serializer of choice here
+///         # let _ = value;
+///         writer.write_all(b"")
+///     }
+/// }
+/// ```
+pub trait Format {
+    /// The `Content-Type` of responses using this format.
+    ///
+    /// Must be a valid header value.
+    const CONTENT_TYPE: &'static str;
+
+    /// The error returned if serialization fails.
+    type Error: fmt::Display;
+
+    /// Serialize `value` into `writer`.
+    fn to_writer<W, T>(writer: W, value: &T) -> Result<(), Self::Error>
+    where
+        W: io::Write,
+        T: Serialize + ?Sized;
+}
+
+/// [`Format`] that serializes to JSON with `Content-Type: application/json`.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct JsonFormat;
+
+#[cfg(feature = "json")]
+impl Format for JsonFormat {
+    const CONTENT_TYPE: &'static str = "application/json";
+
+    type Error = serde_json::Error;
+
+    fn to_writer<W, T>(writer: W, value: &T) -> Result<(), Self::Error>
+    where
+        W: io::Write,
+        T: Serialize + ?Sized,
+    {
+        serde_json::to_writer(writer, value)
+    }
+}
+
+/// Response that serializes a value using the format `F`.
+///
+/// This generalizes [`Json`](crate::Json) over the serialization format,
+/// which is selected at compile time. The `Content-Type` header is set to
+/// [`F::CONTENT_TYPE`](Format::CONTENT_TYPE). If serialization fails a `500
+/// Internal Server Error` response with the error as a plain text body is
+/// returned instead.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{
+///     routing::get,
+///     response::{Formatted, JsonFormat},
+///     Router,
+/// };
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     id: u32,
+/// }
+///
+/// async fn user() -> Formatted<User, JsonFormat> {
+///     Formatted::new(User { id: 1 })
+/// }
+///
+/// let app = Router::new().route("/user", get(user));
+/// # async {
+/// # hyper::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+pub struct Formatted<T, F> {
+    value: T,
+    _format: PhantomData<fn() -> F>,
+}
+
+impl<T, F> Formatted<T, F> {
+    /// Create a new `Formatted` response.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            _format: PhantomData,
+        }
+    }
+
+    /// Get the value back.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, F> From<T> for Formatted<T, F> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T, F> Clone for Formatted<T, F>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone())
+    }
+}
+
+impl<T, F> fmt::Debug for Formatted<T, F>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Formatted").field(&self.value).finish()
+    }
+}
+
+impl<T, F> IntoResponse for Formatted<T, F>
+where
+    T: Serialize,
+    F: Format,
+{
+    fn into_response(self) -> Response {
+        let mut buf = BytesMut::with_capacity(128).writer();
+        match F::to_writer(&mut buf, &self.value) {
+            Ok(()) => (
+                [(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static(F::CONTENT_TYPE),
+                )],
+                buf.into_inner().freeze(),
+            )
+                .into_response(),
+            Err(err) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                [(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static(mime::TEXT_PLAIN_UTF_8.as_ref()),
+                )],
+                err.to_string(),
+            )
+                .into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::ser::Error as _;
+
+    struct Failing;
+
+    impl Format for Failing {
+        const CONTENT_TYPE: &'static str = "application/x-failing";
+
+        type Error = serde_json::Error;
+
+        fn to_writer<W, T>(_writer: W, _value: &T) -> Result<(), Self::Error>
+        where
+            W: io::Write,
+            T: Serialize + ?Sized,
+        {
+            Err(serde_json::Error::custom("oh no"))
+        }
+    }
+
+    async fn body_text(res: Response) -> String {
+        let bytes = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[cfg(feature = "json")]
+    #[tokio::test]
+    async fn json() {
+        let res = Formatted::<_, JsonFormat>::new(serde_json::json!({ "a": 1 })).into_response();
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(body_text(res).await, r#"{"a":1}"#);
+    }
+
+    #[tokio::test]
+    async fn serialization_error() {
+        let res = Formatted::<_, Failing>::new(1).into_response();
+
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(
+            res.headers()[header::CONTENT_TYPE],
+            mime::TEXT_PLAIN_UTF_8.as_ref()
+        );
+        assert_eq!(body_text(res).await, "oh no");
+    }
+}
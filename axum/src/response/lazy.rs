@@ -0,0 +1,187 @@
+use crate::body::{self, BoxBody, Bytes, HttpBody};
+use axum_core::response::{IntoResponse, Response};
+use http::HeaderMap;
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Response whose body is only created once it's about to be sent.
+///
+/// The closure is called when the body is first polled, rather than when the
+/// response is created. If the body is never sent, for example because the
+/// request was a `HEAD` request or a middleware replaced the response, the
+/// closure isn't called at all. That avoids wasting work on expensive bodies.
+///
+/// Only the body of the response returned by the closure is used. The status
+/// code and headers have already been sent when the closure is called so they
+/// have to be set outside of `Lazy`, such as with a tuple.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{
+///     http::header::CONTENT_TYPE,
+///     response::{IntoResponse, Lazy},
+///     routing::get,
+///     Router,
+/// };
+///
+/// fn render_report() -> String {
+///     // ...
+///     # String::new()
+/// }
+///
+/// async fn report() -> impl IntoResponse {
+///     ([(CONTENT_TYPE, "text/csv")], Lazy(render_report))
+/// }
+///
+/// // `HEAD /report` doesn't render the report
+/// let app = Router::new().route("/report", get(report));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+#[derive(Clone, Copy)]
+pub struct Lazy<F>(pub F);
+
+impl<F, R> IntoResponse for Lazy<F>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: IntoResponse,
+{
+    fn into_response(self) -> Response {
+        Response::new(body::boxed(LazyBody {
+            state: State::Pending(Some(self.0)),
+        }))
+    }
+}
+
+impl<F> fmt::Debug for Lazy<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Lazy").finish()
+    }
+}
+
+struct LazyBody<F> {
+    state: State<F>,
+}
+
+enum State<F> {
+    Pending(Option<F>),
+    Body(BoxBody),
+}
+
+impl<F, R> LazyBody<F>
+where
+    F: FnOnce() -> R,
+    R: IntoResponse,
+{
+    fn body(&mut self) -> Pin<&mut BoxBody> {
+        if let State::Pending(f) = &mut self.state {
+            let f = f.take().expect("body created twice");
+            self.state = State::Body(f().into_response().into_body());
+        }
+
+        match &mut self.state {
+            State::Body(body) => Pin::new(body),
+            State::Pending(_) => unreachable!(),
+        }
+    }
+}
+
+// `F` is never pinned
+impl<F> Unpin for LazyBody<F> {}
+
+impl<F, R> HttpBody for LazyBody<F>
+where
+    F: FnOnce() -> R,
+    R: IntoResponse,
+{
+    type Data = Bytes;
+    type Error = crate::Error;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        self.body().poll_data(cx)
+    }
+
+    fn poll_trailers(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        self.body().poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match &self.state {
+            State::Pending(_) => false,
+            State::Body(body) => body.is_end_stream(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{routing::get, test_helpers::*, Router};
+    use http::{header::CONTENT_TYPE, StatusCode};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[tokio::test]
+    async fn body_created_when_polled() {
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let res = Lazy({
+            let calls = Arc::clone(&calls);
+            move || {
+                calls.fetch_add(1, Ordering::SeqCst);
+                // only the body is used
+                (StatusCode::NOT_FOUND, "expensive")
+            }
+        })
+        .into_response();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(body, "expensive");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn not_created_for_head_requests() {
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let app = Router::new().route(
+            "/",
+            get({
+                let calls = Arc::clone(&calls);
+                || async move {
+                    let lazy = Lazy(move || {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        "expensive"
+                    });
+                    ([(CONTENT_TYPE, "text/plain")], lazy)
+                }
+            }),
+        );
+        let client = TestClient::new(app);
+
+        let res = client.head("/").send().await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()[CONTENT_TYPE], "text/plain");
+        assert_eq!(res.text().await, "");
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let res = client.get("/").send().await;
+        assert_eq!(res.text().await, "expensive");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
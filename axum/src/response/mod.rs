@@ -3,7 +3,15 @@
 use crate::body::{Bytes, Full};
 use http::{header, HeaderValue};
 
+mod chunked;
+mod formatted;
+mod lazy;
 mod redirect;
+mod retry_after;
+mod router_error;
+mod status_with_reason;
+mod template;
+mod with_status;
 
 pub mod sse;
 
@@ -24,11 +32,33 @@ pub use crate::Extension;
 
 #[doc(inline)]
 pub use axum_core::response::{
-    AppendHeaders, ErrorResponse, IntoResponse, IntoResponseParts, Response, ResponseParts, Result,
+    AppendHeaders, ErrorResponse, Headers, IntoResponse, IntoResponseParts, Response,
+    ResponseParts, Result,
 };
 
+pub(crate) use self::router_error::PendingError;
+
 #[doc(inline)]
-pub use self::{redirect::Redirect, sse::Sse};
+#[cfg(feature = "json")]
+pub use self::formatted::JsonFormat;
+
+#[doc(inline)]
+#[cfg(feature = "json")]
+pub use crate::json::PrettyJson;
+
+#[doc(inline)]
+pub use self::{
+    chunked::Chunked,
+    formatted::{Format, Formatted},
+    lazy::Lazy,
+    redirect::Redirect,
+    retry_after::RetryAfter,
+    router_error::RouterError,
+    sse::Sse,
+    status_with_reason::StatusWithReason,
+    template::{RenderTemplate, Template},
+    with_status::{HttpError, WithStatus},
+};
 
 /// An HTML response.
 ///
@@ -62,7 +92,7 @@ impl<T> From<T> for Html<T> {
 mod tests {
     use crate::extract::Extension;
     use crate::{body::Body, routing::get, Router};
-    use axum_core::response::IntoResponse;
+    use axum_core::response::{Headers, IntoResponse};
     use http::HeaderMap;
     use http::{StatusCode, Uri};
 
@@ -222,4 +252,58 @@ mod tests {
             .route("/", get(header_array_extension_body))
             .route("/", get(header_array_extension_mixed_body));
     }
+
+    #[test]
+    fn headers_from_iterator() {
+        let res = (
+            [("x-foo", "old")],
+            Headers(vec![("x-foo", "1"), ("x-foo", "2"), ("x-bar", "3")]),
+            (),
+        )
+            .into_response();
+
+        assert_eq!(res.status(), StatusCode::OK);
+        let foo = res.headers().get_all("x-foo").iter().collect::<Vec<_>>();
+        assert_eq!(foo, ["1", "2"]);
+        assert_eq!(res.headers()["x-bar"], "3");
+
+        let res = Headers(vec![("invalid header", "value")]).into_response();
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn status_header_map_and_body() {
+        let body = (
+            [("content-type", "text/plain"), ("x-body", "body")],
+            [("x-both", "body")],
+            "text",
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", "text/html".parse().unwrap());
+        headers.append("x-both", "1".parse().unwrap());
+        headers.append("x-both", "2".parse().unwrap());
+
+        let res = (StatusCode::CREATED, headers, body).into_response();
+
+        assert_eq!(res.status(), StatusCode::CREATED);
+        assert_eq!(res.headers()["content-type"], "text/html");
+        assert_eq!(res.headers()["x-body"], "body");
+        let both = res.headers().get_all("x-both").iter().collect::<Vec<_>>();
+        assert_eq!(both, ["1", "2"]);
+    }
+
+    #[tokio::test]
+    async fn box_error_responds_with_internal_server_error() {
+        let err: crate::BoxError = "something went wrong".into();
+        let res = err.into_response();
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        if cfg!(feature = "error-details") {
+            assert_eq!(body, "something went wrong");
+        } else {
+            assert_eq!(body, "Internal Server Error");
+        }
+    }
 }
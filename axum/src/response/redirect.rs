@@ -1,5 +1,22 @@
 use axum_core::response::{IntoResponse, Response};
 use http::{header::LOCATION, HeaderValue, StatusCode};
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+
+/// Characters that aren't allowed in a URI and therefore get percent-encoded
+/// in the `Location` header.
+///
+/// `%` isn't included so URIs that are already percent-encoded are left as is.
+const LOCATION_ENCODE_SET: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'<')
+    .add(b'>')
+    .add(b'\\')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
 
 /// Response that redirects the request to another location.
 ///
@@ -19,6 +36,34 @@ use http::{header::LOCATION, HeaderValue, StatusCode};
 /// # hyper::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
 /// # };
 /// ```
+///
+/// # Choosing a constructor
+///
+/// Constructor | Status | Method and body of the next request
+/// ---|---|---
+/// [`Redirect::see_other`], [`Redirect::to`] | `303 See Other` | Changed to `GET` without a body
+/// [`Redirect::temporary`] | `307 Temporary Redirect` | Preserved
+/// [`Redirect::permanent`] | `308 Permanent Redirect` | Preserved
+///
+/// `301 Moved Permanently` and `302 Found` aren't supported since clients
+/// are allowed to change the method of the next request from `POST` to `GET`,
+/// and many do, which makes their behavior inconsistent.
+///
+/// # Encoding
+///
+/// Characters that aren't allowed in a URI, such as spaces and non-ASCII
+/// characters, are percent-encoded so any `&str` can be used as the location.
+/// Existing percent-encoded sequences are left as is:
+///
+/// ```rust
+/// use axum::response::{IntoResponse, Redirect};
+///
+/// let res = Redirect::to("/search?q=crème brûlée").into_response();
+/// assert_eq!(
+///     res.headers()["location"],
+///     "/search?q=cr%C3%A8me%20br%C3%BBl%C3%A9e",
+/// );
+/// ```
 #[derive(Debug, Clone)]
 pub struct Redirect {
     status_code: StatusCode,
@@ -34,12 +79,20 @@ impl Redirect {
     /// body (if non-empty). If you want to preserve the request method and body,
     /// [`Redirect::temporary`] should be used instead.
     ///
-    /// # Panics
-    ///
-    /// If `uri` isn't a valid [`HeaderValue`].
+    /// This is the same as [`Redirect::see_other`].
     ///
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/303
     pub fn to(uri: &str) -> Self {
+        Self::see_other(uri)
+    }
+
+    /// Create a new [`Redirect`] that uses a [`303 See Other`][mdn] status code.
+    ///
+    /// This is the same as [`Redirect::to`] but named after the status code,
+    /// which can make it clearer that the method changes to `GET`.
+    ///
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/303
+    pub fn see_other(uri: &str) -> Self {
         Self::with_status_code(StatusCode::SEE_OTHER, uri)
     }
 
@@ -48,10 +101,6 @@ impl Redirect {
     /// This has the same behavior as [`Redirect::to`], except it will preserve the original HTTP
     /// method and body.
     ///
-    /// # Panics
-    ///
-    /// If `uri` isn't a valid [`HeaderValue`].
-    ///
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/307
     pub fn temporary(uri: &str) -> Self {
         Self::with_status_code(StatusCode::TEMPORARY_REDIRECT, uri)
@@ -59,9 +108,8 @@ impl Redirect {
 
     /// Create a new [`Redirect`] that uses a [`308 Permanent Redirect`][mdn] status code.
     ///
-    /// # Panics
-    ///
-    /// If `uri` isn't a valid [`HeaderValue`].
+    /// This preserves the original HTTP method and body, like
+    /// [`Redirect::temporary`].
     ///
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/308
     pub fn permanent(uri: &str) -> Self {
@@ -78,9 +126,12 @@ impl Redirect {
             "not a redirection status code"
         );
 
+        let location = utf8_percent_encode(uri, LOCATION_ENCODE_SET).to_string();
+
         Self {
             status_code,
-            location: HeaderValue::try_from(uri).expect("URI isn't a valid header value"),
+            location: HeaderValue::try_from(location)
+                .expect("percent-encoded URI is always a valid header value"),
         }
     }
 }
@@ -90,3 +141,39 @@ impl IntoResponse for Redirect {
         (self.status_code, [(LOCATION, self.location)]).into_response()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_codes() {
+        let cases = [
+            (Redirect::to("/"), StatusCode::SEE_OTHER),
+            (Redirect::see_other("/"), StatusCode::SEE_OTHER),
+            (Redirect::temporary("/"), StatusCode::TEMPORARY_REDIRECT),
+            (Redirect::permanent("/"), StatusCode::PERMANENT_REDIRECT),
+        ];
+
+        for (redirect, status) in cases {
+            let res = redirect.into_response();
+            assert_eq!(res.status(), status);
+            assert_eq!(res.headers()[LOCATION], "/");
+        }
+    }
+
+    #[test]
+    fn encodes_location() {
+        let res = Redirect::to("https://example.com/a b/ø?q=\"x\"#top").into_response();
+        assert_eq!(
+            res.headers()[LOCATION],
+            "https://example.com/a%20b/%C3%B8?q=%22x%22#top"
+        );
+
+        let res = Redirect::to("/already%20encoded\nand\r\nnewlines").into_response();
+        assert_eq!(
+            res.headers()[LOCATION],
+            "/already%20encoded%0Aand%0D%0Anewlines"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use axum_core::response::{IntoResponse, IntoResponseParts, Response, ResponseParts};
+use http::{header::RETRY_AFTER, HeaderValue};
+use std::{convert::Infallible, time::Duration};
+
+/// Response that sets the `Retry-After` header.
+///
+/// The header is sent in the delta-seconds form, which unlike an HTTP-date
+/// doesn't depend on the client's clock being in sync with the server's.
+/// Durations with a fractional part are rounded up to the next whole second
+/// so clients never retry too early, and durations longer than 2<sup>31</sup>
+/// seconds are clamped to 2<sup>31</sup> as recommended by [RFC 9111].
+///
+/// `RetryAfter` is most often combined with a status code such as
+/// `503 Service Unavailable` or `429 Too Many Requests`.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{
+///     routing::get,
+///     response::{IntoResponse, RetryAfter},
+///     http::StatusCode,
+///     Router,
+/// };
+/// use std::time::Duration;
+///
+/// async fn handler() -> impl IntoResponse {
+///     (
+///         StatusCode::SERVICE_UNAVAILABLE,
+///         RetryAfter(Duration::from_secs(120)),
+///         "Down for maintenance",
+///     )
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # async {
+/// # hyper::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+///
+/// [RFC 9111]: https://www.rfc-editor.org/rfc/rfc9111#section-1.2.2
+#[derive(Debug, Clone, Copy)]
+pub struct RetryAfter(pub Duration);
+
+impl RetryAfter {
+    const MAX_DELTA_SECONDS: u64 = 1 << 31;
+
+    fn delta_seconds(&self) -> u64 {
+        let secs = if self.0.subsec_nanos() == 0 {
+            self.0.as_secs()
+        } else {
+            self.0.as_secs().saturating_add(1)
+        };
+        secs.min(Self::MAX_DELTA_SECONDS)
+    }
+}
+
+impl IntoResponseParts for RetryAfter {
+    type Error = Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        res.headers_mut()
+            .insert(RETRY_AFTER, HeaderValue::from(self.delta_seconds()));
+        Ok(res)
+    }
+}
+
+impl IntoResponse for RetryAfter {
+    fn into_response(self) -> Response {
+        (self, ()).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::StatusCode;
+
+    fn retry_after(duration: Duration) -> Response {
+        (StatusCode::SERVICE_UNAVAILABLE, RetryAfter(duration)).into_response()
+    }
+
+    #[test]
+    fn whole_seconds() {
+        let res = retry_after(Duration::from_secs(120));
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(res.headers()[RETRY_AFTER], "120");
+    }
+
+    #[test]
+    fn rounds_up_fractional_seconds() {
+        let res = retry_after(Duration::from_millis(1500));
+        assert_eq!(res.headers()[RETRY_AFTER], "2");
+
+        let res = retry_after(Duration::from_millis(1));
+        assert_eq!(res.headers()[RETRY_AFTER], "1");
+
+        let res = retry_after(Duration::ZERO);
+        assert_eq!(res.headers()[RETRY_AFTER], "0");
+    }
+
+    #[test]
+    fn clamps_large_durations() {
+        let res = retry_after(Duration::MAX);
+        assert_eq!(res.headers()[RETRY_AFTER], "2147483648");
+    }
+}
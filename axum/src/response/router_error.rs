@@ -0,0 +1,120 @@
+use axum_core::response::{IntoResponse, Response};
+use http::StatusCode;
+
+/// An error that is converted into a response by the router's error handler.
+///
+/// Handlers can return `Result<T, RouterError<E>>` and use `?` to propagate
+/// errors of type `E`. The response is then produced by the error handler
+/// registered with [`Router::error_handler`], so the rendering of errors is
+/// kept in one place rather than spread over every handler.
+///
+/// If no error handler for `E` has been registered the response is an empty
+/// `500 Internal Server Error`.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{
+///     Router,
+///     routing::get,
+///     response::{IntoResponse, RouterError},
+///     http::StatusCode,
+/// };
+///
+/// enum AppError {
+///     NotFound,
+///     Database(String),
+/// }
+///
+/// async fn handler() -> Result<String, RouterError<AppError>> {
+///     let user = find_user()?;
+///     Ok(user)
+/// }
+///
+/// fn find_user() -> Result<String, AppError> {
+///     // ...
+///     # Err(AppError::NotFound)
+/// }
+///
+/// let app: Router = Router::new()
+///     .route("/", get(handler))
+///     .error_handler(|err: AppError| match err {
+///         AppError::NotFound => (StatusCode::NOT_FOUND, "Not found".to_owned()),
+///         AppError::Database(err) => (StatusCode::INTERNAL_SERVER_ERROR, err),
+///     });
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+///
+/// [`Router::error_handler`]: crate::routing::Router::error_handler
+#[derive(Debug, Clone, Copy)]
+pub struct RouterError<E>(pub E);
+
+impl<E> From<E> for RouterError<E> {
+    fn from(err: E) -> Self {
+        Self(err)
+    }
+}
+
+impl<E> IntoResponse for RouterError<E>
+where
+    E: Send + Sync + 'static,
+{
+    fn into_response(self) -> Response {
+        let mut res = StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        res.extensions_mut().insert(PendingError(self.0));
+        res
+    }
+}
+
+/// Response extension holding an error that hasn't been handled yet.
+pub(crate) struct PendingError<E>(pub(crate) E);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{routing::get, test_helpers::TestClient, Router};
+
+    #[derive(Debug)]
+    struct AppError(&'static str);
+
+    #[derive(Debug)]
+    struct OtherError;
+
+    fn fails() -> Result<(), AppError> {
+        Err(AppError("oh no"))
+    }
+
+    #[tokio::test]
+    async fn error_handler() {
+        async fn handler() -> Result<(), RouterError<AppError>> {
+            fails()?;
+            Ok(())
+        }
+
+        async fn other() -> Result<(), RouterError<OtherError>> {
+            Err(RouterError(OtherError))
+        }
+
+        let app = Router::new()
+            .route("/", get(handler))
+            .route("/other", get(other))
+            .route("/ok", get(|| async { "ok" }))
+            .error_handler(|AppError(msg)| (StatusCode::BAD_REQUEST, msg));
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/").send().await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(res.text().await, "oh no");
+
+        // no handler registered for `OtherError`
+        let res = client.get("/other").send().await;
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(res.text().await, "");
+
+        let res = client.get("/ok").send().await;
+        assert_eq!(res.text().await, "ok");
+    }
+}
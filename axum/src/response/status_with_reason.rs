@@ -0,0 +1,76 @@
+use axum_core::response::{IntoResponse, Response};
+use http::StatusCode;
+
+/// Response with a status code and its canonical reason phrase as the body.
+///
+/// A bare [`StatusCode`] produces a response with an empty body.
+/// `StatusWithReason` instead sets the body to the reason phrase, such as
+/// `Not Found`, with `content-type: text/plain; charset=utf-8`. Status codes
+/// without a canonical reason phrase get an empty body.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{
+///     routing::get,
+///     response::StatusWithReason,
+///     http::StatusCode,
+///     Router,
+/// };
+///
+/// // responds with `404 Not Found` and the body `Not Found`
+/// async fn handler() -> StatusWithReason {
+///     StatusWithReason(StatusCode::NOT_FOUND)
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # async {
+/// # hyper::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusWithReason(pub StatusCode);
+
+impl IntoResponse for StatusWithReason {
+    fn into_response(self) -> Response {
+        match self.0.canonical_reason() {
+            Some(reason) => (self.0, reason).into_response(),
+            None => self.0.into_response(),
+        }
+    }
+}
+
+impl From<StatusCode> for StatusWithReason {
+    fn from(status: StatusCode) -> Self {
+        Self(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::HttpBody;
+    use http::header::CONTENT_TYPE;
+
+    async fn body_text(res: Response) -> String {
+        let bytes = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn known_status() {
+        let res = StatusWithReason(StatusCode::NOT_FOUND).into_response();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        assert_eq!(res.headers()[CONTENT_TYPE], "text/plain; charset=utf-8");
+        assert_eq!(body_text(res).await, "Not Found");
+    }
+
+    #[tokio::test]
+    async fn unknown_status() {
+        let status = StatusCode::from_u16(599).unwrap();
+        let res = StatusWithReason(status).into_response();
+        assert_eq!(res.status(), status);
+        assert!(res.headers().get(CONTENT_TYPE).is_none());
+        assert!(res.body().is_end_stream());
+    }
+}
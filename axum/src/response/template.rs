@@ -0,0 +1,128 @@
+use super::Html;
+use axum_core::response::{IntoResponse, Response};
+use http::StatusCode;
+use std::fmt;
+
+/// A template that can be rendered to HTML.
+///
+/// Implement this for the templates of your template engine to return them
+/// from handlers with [`RenderTemplate`].
+pub trait Template {
+    /// The error returned if rendering fails.
+    type Error: fmt::Display;
+
+    /// Render the template.
+    fn render(&self) -> Result<String, Self::Error>;
+}
+
+/// Response that renders a [`Template`] as HTML.
+///
+/// The template is rendered when the response is created. If rendering
+/// succeeds the response has `Content-Type: text/html`, otherwise it's a
+/// `500 Internal Server Error` and, if the `tracing` feature is enabled, the
+/// error is logged. The error isn't included in the response since it might
+/// contain details about the template that shouldn't be exposed to clients.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{
+///     response::{RenderTemplate, Template},
+///     routing::get,
+///     Router,
+/// };
+/// use std::convert::Infallible;
+///
+/// struct HelloTemplate {
+///     name: String,
+/// }
+///
+/// // this would usually be done by the template engine
+/// impl Template for HelloTemplate {
+///     type Error = Infallible;
+///
+///     fn render(&self) -> Result<String, Self::Error> {
+///         Ok(format!("<h1>Hello, {}!</h1>", self.name))
+///     }
+/// }
+///
+/// async fn handler() -> RenderTemplate<HelloTemplate> {
+///     RenderTemplate(HelloTemplate {
+///         name: "World".to_owned(),
+///     })
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # async {
+/// # hyper::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct RenderTemplate<T>(pub T);
+
+impl<T> IntoResponse for RenderTemplate<T>
+where
+    T: Template,
+{
+    fn into_response(self) -> Response {
+        match self.0.render() {
+            Ok(html) => Html(html).into_response(),
+            Err(err) => {
+                #[cfg(feature = "tracing")]
+                tracing::error!(error = %err, "failed to render template");
+                #[cfg(not(feature = "tracing"))]
+                let _ = err;
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to render template",
+                )
+                    .into_response()
+            }
+        }
+    }
+}
+
+impl<T> From<T> for RenderTemplate<T> {
+    fn from(template: T) -> Self {
+        Self(template)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::header::CONTENT_TYPE;
+
+    struct Greeting(Option<&'static str>);
+
+    impl Template for Greeting {
+        type Error = &'static str;
+
+        fn render(&self) -> Result<String, Self::Error> {
+            match self.0 {
+                Some(name) => Ok(format!("<p>Hello, {}</p>", name)),
+                None => Err("missing name"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn renders_html() {
+        let res = RenderTemplate(Greeting(Some("Alice"))).into_response();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()[CONTENT_TYPE], "text/html; charset=utf-8");
+
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(body, "<p>Hello, Alice</p>");
+    }
+
+    #[tokio::test]
+    async fn render_error() {
+        let res = RenderTemplate(Greeting(None)).into_response();
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(body, "Failed to render template");
+    }
+}
@@ -0,0 +1,159 @@
+use axum_core::response::{IntoResponse, Response};
+use http::StatusCode;
+
+/// An error that knows which HTTP status it should be reported with.
+///
+/// Implement this for domain errors to keep the mapping from errors to
+/// statuses in one place, and return them from handlers wrapped in
+/// [`WithStatus`].
+pub trait HttpError {
+    /// The body of the response.
+    type Body: IntoResponse;
+
+    /// The status of the response.
+    fn status(&self) -> StatusCode;
+
+    /// Convert the error into the body of the response.
+    ///
+    /// The status of the body is overridden by [`HttpError::status`], but
+    /// headers it sets, such as `content-type`, are kept.
+    fn body(self) -> Self::Body;
+}
+
+/// Response for errors that implement [`HttpError`].
+///
+/// Handlers can return `Result<T, WithStatus<E>>` and use `?` to propagate
+/// errors of type `E`. The response has the status from
+/// [`HttpError::status`] and the body from [`HttpError::body`].
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{
+///     Router,
+///     routing::get,
+///     extract::Path,
+///     response::{HttpError, WithStatus},
+///     http::StatusCode,
+/// };
+///
+/// enum UserError {
+///     NotFound,
+///     Banned { reason: String },
+/// }
+///
+/// impl HttpError for UserError {
+///     type Body = String;
+///
+///     fn status(&self) -> StatusCode {
+///         match self {
+///             UserError::NotFound => StatusCode::NOT_FOUND,
+///             UserError::Banned { .. } => StatusCode::FORBIDDEN,
+///         }
+///     }
+///
+///     fn body(self) -> String {
+///         match self {
+///             UserError::NotFound => "User not found".to_owned(),
+///             UserError::Banned { reason } => format!("User is banned: {}", reason),
+///         }
+///     }
+/// }
+///
+/// async fn handler(Path(id): Path<u32>) -> Result<String, WithStatus<UserError>> {
+///     let user = find_user(id)?;
+///     Ok(user)
+/// }
+///
+/// fn find_user(id: u32) -> Result<String, UserError> {
+///     // ...
+///     # Err(UserError::NotFound)
+/// }
+///
+/// let app = Router::new().route("/users/:id", get(handler));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct WithStatus<E>(pub E);
+
+impl<E> From<E> for WithStatus<E> {
+    fn from(err: E) -> Self {
+        Self(err)
+    }
+}
+
+impl<E> IntoResponse for WithStatus<E>
+where
+    E: HttpError,
+{
+    fn into_response(self) -> Response {
+        let status = self.0.status();
+        (status, self.0.body()).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{response::Html, routing::get, test_helpers::TestClient, Router};
+    use http::header::CONTENT_TYPE;
+
+    enum AppError {
+        NotFound,
+        Conflict(&'static str),
+    }
+
+    impl HttpError for AppError {
+        type Body = Html<String>;
+
+        fn status(&self) -> StatusCode {
+            match self {
+                AppError::NotFound => StatusCode::NOT_FOUND,
+                AppError::Conflict(_) => StatusCode::CONFLICT,
+            }
+        }
+
+        fn body(self) -> Self::Body {
+            match self {
+                AppError::NotFound => Html("<p>Not found</p>".to_owned()),
+                AppError::Conflict(what) => Html(format!("<p>{} already exists</p>", what)),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn with_status() {
+        fn fails(err: AppError) -> Result<&'static str, AppError> {
+            Err(err)
+        }
+
+        async fn not_found() -> Result<&'static str, WithStatus<AppError>> {
+            Ok(fails(AppError::NotFound)?)
+        }
+
+        async fn conflict() -> Result<&'static str, WithStatus<AppError>> {
+            Ok(fails(AppError::Conflict("user"))?)
+        }
+
+        let app = Router::new()
+            .route("/ok", get(|| async { Ok::<_, WithStatus<AppError>>("ok") }))
+            .route("/not-found", get(not_found))
+            .route("/conflict", get(conflict));
+        let client = TestClient::new(app);
+
+        let res = client.get("/ok").send().await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "ok");
+
+        let res = client.get("/not-found").send().await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        assert_eq!(res.headers()[CONTENT_TYPE], "text/html; charset=utf-8");
+        assert_eq!(res.text().await, "<p>Not found</p>");
+
+        let res = client.get("/conflict").send().await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        assert_eq!(res.text().await, "<p>user already exists</p>");
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{
+    body::{self, BoxBody, Bytes, HttpBody},
+    response::{IntoResponse, Response},
+    BoxError,
+};
+use futures_util::ready;
+use http::{header, HeaderMap, HeaderValue, Request, StatusCode};
+use pin_project_lite::pin_project;
+use std::{
+    convert::Infallible,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::{Instant, Sleep};
+use tower_service::Service;
+
+/// Service that fails the request body if no data is received within
+/// `duration` and responds with `408 Request Timeout` if that happens.
+///
+/// Used by [`Router::body_read_timeout`](super::Router::body_read_timeout).
+#[derive(Clone)]
+pub(super) struct BodyReadTimeout<S> {
+    inner: S,
+    duration: Duration,
+}
+
+impl<S> BodyReadTimeout<S> {
+    pub(super) fn new(inner: S, duration: Duration) -> Self {
+        Self { inner, duration }
+    }
+}
+
+impl<S, B> Service<Request<B>> for BodyReadTimeout<S>
+where
+    S: Service<Request<BoxBody>, Response = Response, Error = Infallible>,
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = ResponseFuture<S::Future>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let timed_out = Arc::new(AtomicBool::new(false));
+        let req = req.map(|body| {
+            body::boxed(TimeoutBody {
+                inner: body,
+                duration: self.duration,
+                sleep: None,
+                timed_out: Arc::clone(&timed_out),
+            })
+        });
+
+        ResponseFuture {
+            inner: self.inner.call(req),
+            timed_out,
+        }
+    }
+}
+
+pin_project! {
+    pub(super) struct ResponseFuture<F> {
+        #[pin]
+        inner: F,
+        timed_out: Arc<AtomicBool>,
+    }
+}
+
+impl<F> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<Response, Infallible>>,
+{
+    type Output = Result<Response, Infallible>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let res = ready!(this.inner.poll(cx))?;
+
+        if this.timed_out.load(Ordering::SeqCst) {
+            // the rest of the body is never read so the connection cannot be
+            // reused
+            let headers = [(header::CONNECTION, HeaderValue::from_static("close"))];
+            return Poll::Ready(Ok((StatusCode::REQUEST_TIMEOUT, headers).into_response()));
+        }
+
+        Poll::Ready(Ok(res))
+    }
+}
+
+pin_project! {
+    struct TimeoutBody<B> {
+        #[pin]
+        inner: B,
+        duration: Duration,
+        // only started when the body is first polled so time spent before the
+        // handler reads the body doesn't count
+        sleep: Option<Pin<Box<Sleep>>>,
+        timed_out: Arc<AtomicBool>,
+    }
+}
+
+impl<B> HttpBody for TimeoutBody<B>
+where
+    B: HttpBody<Data = Bytes>,
+    B::Error: Into<BoxError>,
+{
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let this = self.project();
+        let duration = *this.duration;
+        let sleep = this
+            .sleep
+            .get_or_insert_with(|| Box::pin(tokio::time::sleep(duration)));
+
+        if let Poll::Ready(chunk) = this.inner.poll_data(cx) {
+            sleep.as_mut().reset(Instant::now() + duration);
+            return Poll::Ready(chunk.map(|chunk| chunk.map_err(Into::into)));
+        }
+
+        ready!(sleep.as_mut().poll(cx));
+        this.timed_out.store(true, Ordering::SeqCst);
+        Poll::Ready(Some(Err(Box::new(BodyReadTimedOut))))
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        self.project().inner.poll_trailers(cx).map_err(Into::into)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[derive(Debug)]
+struct BodyReadTimedOut;
+
+impl fmt::Display for BodyReadTimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Timed out reading the request body")
+    }
+}
+
+impl std::error::Error for BodyReadTimedOut {}
@@ -0,0 +1,160 @@
+use http::{header::CONTENT_TYPE, Response};
+use http_body::Body;
+use std::sync::Arc;
+use tower_http::compression::{
+    predicate::{Predicate, SizeAbove},
+    CompressionLayer,
+};
+
+/// Configuration for [`Router::auto_compress`](super::Router::auto_compress).
+///
+/// By default responses are compressed with gzip, brotli or deflate, depending
+/// on the request's `Accept-Encoding` header, if they're at least 1 KiB large
+/// and have one of these content types:
+///
+/// - `text/*`
+/// - `application/json`
+/// - `application/javascript`
+/// - `application/xml`
+/// - `image/svg+xml`
+#[derive(Debug, Clone)]
+pub struct CompressionConfig {
+    min_size: u16,
+    content_types: Vec<Arc<str>>,
+    gzip: bool,
+    br: bool,
+    deflate: bool,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            min_size: 1024,
+            content_types: [
+                "text/",
+                "application/json",
+                "application/javascript",
+                "application/xml",
+                "image/svg+xml",
+            ]
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+            gzip: true,
+            br: true,
+            deflate: true,
+        }
+    }
+}
+
+impl CompressionConfig {
+    /// Create a new `CompressionConfig` with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the minimum size, in bytes, of responses that are compressed.
+    ///
+    /// Responses without a `Content-Length` header, such as streaming
+    /// responses, are always considered large enough.
+    pub fn min_size(mut self, bytes: u16) -> Self {
+        self.min_size = bytes;
+        self
+    }
+
+    /// Add a content type to the allowlist.
+    ///
+    /// Content types ending with `/`, such as `text/`, allow every subtype.
+    /// Otherwise the content type must match exactly, ignoring parameters
+    /// such as `charset`.
+    pub fn allow_content_type(mut self, content_type: &str) -> Self {
+        self.content_types.push(content_type.into());
+        self
+    }
+
+    /// Replace the allowlist of content types.
+    ///
+    /// See [`CompressionConfig::allow_content_type`] for how content types are
+    /// matched.
+    pub fn content_types<I>(mut self, content_types: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.content_types = content_types
+            .into_iter()
+            .map(|content_type| content_type.as_ref().into())
+            .collect();
+        self
+    }
+
+    /// Enable or disable gzip compression.
+    pub fn gzip(mut self, enabled: bool) -> Self {
+        self.gzip = enabled;
+        self
+    }
+
+    /// Enable or disable brotli compression.
+    pub fn br(mut self, enabled: bool) -> Self {
+        self.br = enabled;
+        self
+    }
+
+    /// Enable or disable deflate compression.
+    pub fn deflate(mut self, enabled: bool) -> Self {
+        self.deflate = enabled;
+        self
+    }
+
+    pub(super) fn into_layer(self) -> CompressionLayer<ShouldCompress> {
+        let predicate = ShouldCompress {
+            size: SizeAbove::new(self.min_size),
+            content_types: self.content_types.into(),
+        };
+
+        CompressionLayer::new()
+            .gzip(self.gzip)
+            .br(self.br)
+            .deflate(self.deflate)
+            .compress_when(predicate)
+    }
+}
+
+/// Compresses responses that are large enough and have an allowed content
+/// type.
+///
+/// Responses that already have a `Content-Encoding` are never compressed by
+/// `Compression` so that doesn't have to be checked here.
+#[derive(Debug, Clone)]
+pub(super) struct ShouldCompress {
+    size: SizeAbove,
+    content_types: Arc<[Arc<str>]>,
+}
+
+impl Predicate for ShouldCompress {
+    fn should_compress<B>(&self, response: &Response<B>) -> bool
+    where
+        B: Body,
+    {
+        let content_type = match response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+        {
+            Some(content_type) => content_type,
+            None => return false,
+        };
+        let essence = content_type.split(';').next().unwrap_or_default().trim();
+
+        let allowed = self.content_types.iter().any(|allowed| {
+            if allowed.ends_with('/') {
+                essence.len() > allowed.len()
+                    && essence[..allowed.len()].eq_ignore_ascii_case(allowed)
+            } else {
+                essence.eq_ignore_ascii_case(allowed)
+            }
+        });
+
+        allowed && self.size.should_compress(response)
+    }
+}
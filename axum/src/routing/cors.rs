@@ -0,0 +1,100 @@
+use http::{header::HeaderName, HeaderValue};
+use std::time::Duration;
+use tower_http::cors::{AllowMethods, AllowOrigin, CorsLayer};
+
+/// Configuration for [`MethodRouter::cors`](super::MethodRouter::cors).
+///
+/// By default no origins are allowed, no request headers other than the
+/// [CORS-safelisted] ones are allowed, no response headers are exposed,
+/// credentials aren't allowed and preflight responses aren't cached.
+///
+/// The allowed methods aren't configured here since they're the methods of
+/// the [`MethodRouter`](super::MethodRouter).
+///
+/// [CORS-safelisted]: https://developer.mozilla.org/en-US/docs/Glossary/CORS-safelisted_request_header
+#[derive(Debug, Clone, Default)]
+pub struct CorsConfig {
+    any_origin: bool,
+    origins: Vec<HeaderValue>,
+    allow_headers: Vec<HeaderName>,
+    expose_headers: Vec<HeaderName>,
+    allow_credentials: bool,
+    max_age: Option<Duration>,
+}
+
+impl CorsConfig {
+    /// Create a new `CorsConfig` with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow requests from `origin`, such as `https://example.com`.
+    ///
+    /// Can be called several times to allow several origins.
+    pub fn allow_origin(mut self, origin: HeaderValue) -> Self {
+        self.origins.push(origin);
+        self
+    }
+
+    /// Allow requests from any origin.
+    pub fn allow_any_origin(mut self) -> Self {
+        self.any_origin = true;
+        self
+    }
+
+    /// Allow requests to send the given headers.
+    pub fn allow_headers<I>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = HeaderName>,
+    {
+        self.allow_headers.extend(headers);
+        self
+    }
+
+    /// Allow scripts to read the given response headers.
+    pub fn expose_headers<I>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = HeaderName>,
+    {
+        self.expose_headers.extend(headers);
+        self
+    }
+
+    /// Allow requests with credentials, such as cookies.
+    ///
+    /// Can't be combined with [`CorsConfig::allow_any_origin`].
+    pub fn allow_credentials(mut self, allow: bool) -> Self {
+        self.allow_credentials = allow;
+        self
+    }
+
+    /// Set how long browsers may cache preflight responses.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub(super) fn into_layer(self, methods: AllowMethods) -> CorsLayer {
+        if self.any_origin && self.allow_credentials {
+            panic!("Invalid CORS configuration: cannot allow credentials from any origin");
+        }
+
+        let origin = if self.any_origin {
+            AllowOrigin::any()
+        } else {
+            AllowOrigin::list(self.origins)
+        };
+
+        let layer = CorsLayer::new()
+            .allow_origin(origin)
+            .allow_methods(methods)
+            .allow_headers(self.allow_headers)
+            .expose_headers(self.expose_headers)
+            .allow_credentials(self.allow_credentials);
+
+        match self.max_age {
+            Some(max_age) => layer.max_age(max_age),
+            None => layer,
+        }
+    }
+}
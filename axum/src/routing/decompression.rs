@@ -0,0 +1,229 @@
+use crate::{
+    body::{self, BoxBody, Bytes, HttpBody, StreamBody},
+    response::{IntoResponse, Response},
+    BoxError,
+};
+use async_compression::tokio::bufread::{BrotliDecoder, GzipDecoder, ZlibDecoder};
+use futures_util::{
+    ready,
+    stream::{self, Stream, StreamExt, TryStreamExt},
+};
+use http::{
+    header::{ACCEPT_ENCODING, CONNECTION, CONTENT_ENCODING, CONTENT_LENGTH},
+    HeaderValue, Request, StatusCode,
+};
+use pin_project_lite::pin_project;
+use std::{
+    convert::Infallible,
+    future::Future,
+    io,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+use tokio::io::AsyncRead;
+use tokio_util::io::{ReaderStream, StreamReader};
+use tower_service::Service;
+
+/// The default maximum size of decompressed request bodies.
+pub(super) const DEFAULT_DECOMPRESSED_SIZE_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Service that decompresses request bodies according to their
+/// `Content-Encoding`.
+///
+/// Requests with encodings that aren't supported are rejected with
+/// `415 Unsupported Media Type`, and requests whose decompressed body is larger
+/// than `limit` with `413 Payload Too Large`.
+///
+/// Used by [`Router::decompress_requests`](super::Router::decompress_requests).
+#[derive(Clone)]
+pub(super) struct Decompression<S> {
+    inner: S,
+    limit: usize,
+}
+
+impl<S> Decompression<S> {
+    pub(super) fn new(inner: S, limit: usize) -> Self {
+        Self { inner, limit }
+    }
+}
+
+impl<S, B> Service<Request<B>> for Decompression<S>
+where
+    S: Service<Request<BoxBody>, Response = Response, Error = Infallible>,
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = ResponseFuture<S::Future>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let encoding = match req.headers().get(CONTENT_ENCODING) {
+            Some(encoding) => encoding
+                .to_str()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase(),
+            None => return ResponseFuture::pass_through(self.inner.call(req.map(body::boxed))),
+        };
+
+        let decompress: fn(BodyStream) -> BodyStream = match &*encoding {
+            "gzip" | "x-gzip" => |stream| decoded(GzipDecoder::new(StreamReader::new(stream))),
+            "deflate" => |stream| decoded(ZlibDecoder::new(StreamReader::new(stream))),
+            "br" => |stream| decoded(BrotliDecoder::new(StreamReader::new(stream))),
+            "identity" => {
+                let mut req = req.map(body::boxed);
+                req.headers_mut().remove(CONTENT_ENCODING);
+                return ResponseFuture::pass_through(self.inner.call(req));
+            }
+            // including several encodings, such as `gzip, br`
+            _ => return ResponseFuture::unsupported(),
+        };
+
+        let too_large = Arc::new(AtomicBool::new(false));
+        let (mut parts, body) = req.into_parts();
+        let body = limited(decompress(into_stream(body)), self.limit, &too_large);
+
+        // the headers describe the compressed body
+        parts.headers.remove(CONTENT_ENCODING);
+        parts.headers.remove(CONTENT_LENGTH);
+
+        ResponseFuture {
+            kind: Kind::Inner {
+                inner: self.inner.call(Request::from_parts(parts, body)),
+                too_large: Some(too_large),
+            },
+        }
+    }
+}
+
+pin_project! {
+    pub(super) struct ResponseFuture<F> {
+        #[pin]
+        kind: Kind<F>,
+    }
+}
+
+pin_project! {
+    #[project = KindProj]
+    enum Kind<F> {
+        Inner {
+            #[pin]
+            inner: F,
+            too_large: Option<Arc<AtomicBool>>,
+        },
+        Unsupported,
+    }
+}
+
+impl<F> ResponseFuture<F> {
+    fn pass_through(inner: F) -> Self {
+        Self {
+            kind: Kind::Inner {
+                inner,
+                too_large: None,
+            },
+        }
+    }
+
+    fn unsupported() -> Self {
+        Self {
+            kind: Kind::Unsupported,
+        }
+    }
+}
+
+impl<F> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<Response, Infallible>>,
+{
+    type Output = Result<Response, Infallible>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project().kind.project() {
+            KindProj::Inner { inner, too_large } => {
+                let res = ready!(inner.poll(cx))?;
+
+                if too_large
+                    .as_ref()
+                    .map_or(false, |too_large| too_large.load(Ordering::SeqCst))
+                {
+                    // the rest of the body is never read so the connection
+                    // cannot be reused
+                    let headers = [(CONNECTION, HeaderValue::from_static("close"))];
+                    return Poll::Ready(Ok((
+                        StatusCode::PAYLOAD_TOO_LARGE,
+                        headers,
+                        "Decompressed request body is too large",
+                    )
+                        .into_response()));
+                }
+
+                Poll::Ready(Ok(res))
+            }
+            KindProj::Unsupported => {
+                let headers = [(
+                    ACCEPT_ENCODING,
+                    HeaderValue::from_static("gzip, deflate, br"),
+                )];
+                Poll::Ready(Ok((
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                    headers,
+                    "Unsupported `Content-Encoding`",
+                )
+                    .into_response()))
+            }
+        }
+    }
+}
+
+type BodyStream = Pin<Box<dyn Stream<Item = io::Result<Bytes>> + Send>>;
+
+fn into_stream<B>(body: B) -> BodyStream
+where
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    let body = Box::pin(body);
+    let stream = stream::unfold(body, |mut body| async move {
+        let chunk = body.data().await?;
+        Some((chunk, body))
+    });
+    Box::pin(stream.map_err(|err| io::Error::new(io::ErrorKind::Other, err.into())))
+}
+
+fn decoded<R>(reader: R) -> BodyStream
+where
+    R: AsyncRead + Send + 'static,
+{
+    Box::pin(ReaderStream::new(reader))
+}
+
+/// Fail the body once more than `limit` bytes have been decompressed, so
+/// small bodies that decompress to huge sizes can't exhaust memory.
+fn limited(stream: BodyStream, limit: usize, too_large: &Arc<AtomicBool>) -> BoxBody {
+    let too_large = Arc::clone(too_large);
+    let mut size = 0;
+    let stream = stream.map(move |chunk| {
+        let chunk = chunk?;
+        size += chunk.len();
+        if size > limit {
+            too_large.store(true, Ordering::SeqCst);
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "decompressed request body is too large",
+            ));
+        }
+        Ok(chunk)
+    });
+    body::boxed(StreamBody::new(stream))
+}
@@ -0,0 +1,212 @@
+use crate::response::{IntoResponse, Response};
+use futures_util::future::{join_all, BoxFuture};
+use http::StatusCode;
+use std::{fmt, future::Future, sync::Arc, time::Duration};
+
+/// Configuration for [`Router::with_health_checks`](super::Router::with_health_checks).
+///
+/// By default the liveness endpoint is `/healthz`, the readiness endpoint is
+/// `/readyz`, neither has any probes, probes time out after 1 second and the
+/// responses have empty bodies.
+#[derive(Clone)]
+pub struct HealthConfig {
+    pub(super) health_path: Arc<str>,
+    pub(super) ready_path: Arc<str>,
+    liveness: Vec<Probe>,
+    readiness: Vec<Probe>,
+    timeout: Duration,
+    details: bool,
+}
+
+#[derive(Clone)]
+struct Probe {
+    name: Arc<str>,
+    check: Arc<dyn Fn() -> BoxFuture<'static, bool> + Send + Sync>,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            health_path: "/healthz".into(),
+            ready_path: "/readyz".into(),
+            liveness: Vec::new(),
+            readiness: Vec::new(),
+            timeout: Duration::from_secs(1),
+            details: false,
+        }
+    }
+}
+
+impl HealthConfig {
+    /// Create a new `HealthConfig` with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the path of the liveness endpoint.
+    pub fn health_path(mut self, path: &str) -> Self {
+        self.health_path = path.into();
+        self
+    }
+
+    /// Set the path of the readiness endpoint.
+    pub fn ready_path(mut self, path: &str) -> Self {
+        self.ready_path = path.into();
+        self
+    }
+
+    /// Add a probe that must succeed for the service to be considered alive.
+    pub fn liveness<F, Fut>(mut self, name: &str, probe: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        self.liveness.push(Probe::new(name, probe));
+        self
+    }
+
+    /// Add a probe that must succeed for the service to be considered ready
+    /// to receive traffic.
+    pub fn readiness<F, Fut>(mut self, name: &str, probe: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        self.readiness.push(Probe::new(name, probe));
+        self
+    }
+
+    /// Set how long a probe may take before it's considered failed.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Include the status of every probe in a JSON body, such as
+    /// `{"status":"unavailable","checks":{"database":"ok","cache":"timeout"}}`.
+    #[cfg(feature = "json")]
+    pub fn details(mut self, enabled: bool) -> Self {
+        self.details = enabled;
+        self
+    }
+
+    pub(super) fn liveness_check(&self) -> HealthCheck {
+        HealthCheck {
+            probes: self.liveness.clone().into(),
+            timeout: self.timeout,
+            details: self.details,
+        }
+    }
+
+    pub(super) fn readiness_check(&self) -> HealthCheck {
+        HealthCheck {
+            probes: self.readiness.clone().into(),
+            timeout: self.timeout,
+            details: self.details,
+        }
+    }
+}
+
+impl fmt::Debug for HealthConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = |probes: &[Probe]| {
+            probes
+                .iter()
+                .map(|probe| probe.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        f.debug_struct("HealthConfig")
+            .field("health_path", &self.health_path)
+            .field("ready_path", &self.ready_path)
+            .field("liveness", &names(&self.liveness))
+            .field("readiness", &names(&self.readiness))
+            .field("timeout", &self.timeout)
+            .field("details", &self.details)
+            .finish()
+    }
+}
+
+impl Probe {
+    fn new<F, Fut>(name: &str, probe: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            check: Arc::new(move || Box::pin(probe())),
+        }
+    }
+}
+
+/// The probes behind one of the health endpoints.
+#[derive(Clone)]
+pub(super) struct HealthCheck {
+    probes: Arc<[Probe]>,
+    timeout: Duration,
+    #[cfg_attr(not(feature = "json"), allow(dead_code))]
+    details: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ProbeStatus {
+    Ok,
+    Failing,
+    Timeout,
+}
+
+impl ProbeStatus {
+    #[cfg_attr(not(feature = "json"), allow(dead_code))]
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Failing => "failing",
+            Self::Timeout => "timeout",
+        }
+    }
+}
+
+impl HealthCheck {
+    pub(super) async fn run(self) -> Response {
+        let timeout = self.timeout;
+
+        // run the probes concurrently so one slow probe doesn't delay the
+        // others
+        let statuses = join_all(self.probes.iter().map(|probe| {
+            let check = (probe.check)();
+            async move {
+                match tokio::time::timeout(timeout, check).await {
+                    Ok(true) => ProbeStatus::Ok,
+                    Ok(false) => ProbeStatus::Failing,
+                    Err(_) => ProbeStatus::Timeout,
+                }
+            }
+        }))
+        .await;
+
+        let healthy = statuses.iter().all(|status| *status == ProbeStatus::Ok);
+        let status = if healthy {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        };
+
+        #[cfg(feature = "json")]
+        if self.details {
+            let checks = self
+                .probes
+                .iter()
+                .zip(&statuses)
+                .map(|(probe, status)| (probe.name.to_string(), status.as_str().into()))
+                .collect::<serde_json::Map<_, _>>();
+            let body = serde_json::json!({
+                "status": if healthy { "ok" } else { "unavailable" },
+                "checks": checks,
+            });
+            return (status, crate::Json(body)).into_response();
+        }
+
+        status.into_response()
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    body::{self, Bytes, Full, HttpBody, StreamBody},
+    response::Response,
+};
+use futures_util::future::BoxFuture;
+use http::{
+    header::{CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE},
+    HeaderMap, Request,
+};
+use std::{
+    convert::Infallible,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower_service::Service;
+
+/// HTML bodies larger than this, or of unknown size, aren't transformed.
+const MAX_HTML_BODY_SIZE: u64 = 1024 * 1024;
+
+pub(super) type HtmlTransform = Arc<dyn Fn(String) -> String + Send + Sync>;
+
+/// Service that transforms the bodies of HTML responses.
+///
+/// Used by [`Router::map_html_body`](super::Router::map_html_body).
+#[derive(Clone)]
+pub(super) struct MapHtmlBody<S> {
+    inner: S,
+    transform: HtmlTransform,
+}
+
+impl<S> MapHtmlBody<S> {
+    pub(super) fn new(inner: S, transform: HtmlTransform) -> Self {
+        Self { inner, transform }
+    }
+}
+
+impl<S, B> Service<Request<B>> for MapHtmlBody<S>
+where
+    S: Service<Request<B>, Response = Response, Error = Infallible>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let future = self.inner.call(req);
+        let transform = Arc::clone(&self.transform);
+
+        Box::pin(async move {
+            let res = future.await?;
+
+            if !is_html(res.headers()) || res.headers().contains_key(CONTENT_ENCODING) {
+                return Ok(res);
+            }
+
+            // streaming bodies are passed through rather than buffered
+            match res.body().size_hint().upper() {
+                Some(size) if size <= MAX_HTML_BODY_SIZE => {}
+                _ => return Ok(res),
+            }
+
+            let (mut parts, body) = res.into_parts();
+            let bytes = match hyper::body::to_bytes(body).await {
+                Ok(bytes) => bytes,
+                // pass the error on to the client
+                Err(err) => {
+                    let body =
+                        StreamBody::new(futures_util::stream::once(
+                            async move { Err::<Bytes, _>(err) },
+                        ));
+                    return Ok(Response::from_parts(parts, body::boxed(body)));
+                }
+            };
+
+            let html = match String::from_utf8(bytes.to_vec()) {
+                Ok(html) => transform(html),
+                Err(_) => return Ok(Response::from_parts(parts, body::boxed(Full::new(bytes)))),
+            };
+
+            // the length has most likely changed
+            parts.headers.remove(CONTENT_LENGTH);
+
+            Ok(Response::from_parts(parts, body::boxed(Full::from(html))))
+        })
+    }
+}
+
+fn is_html(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<mime::Mime>().ok())
+        .map_or(false, |mime| {
+            mime.type_() == mime::TEXT && mime.subtype() == mime::HTML
+        })
+}
@@ -0,0 +1,29 @@
+/// What a request would be routed to, as returned by
+/// [`Router::match_path`](super::Router::match_path).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MatchOutcome {
+    /// The request is handled by a route.
+    Route {
+        /// The path of the route, such as `/users/:id`.
+        path: String,
+        /// The percent-decoded values of the captures in the path, such as
+        /// `("id", "1")`.
+        params: Vec<(String, String)>,
+    },
+    /// The path matches a route but the route doesn't handle the method, so
+    /// the response is `405 Method Not Allowed`.
+    MethodNotAllowed {
+        /// The path of the route.
+        path: String,
+    },
+    /// The path only matches a route once the trailing slash is added or
+    /// removed, and the request is redirected to `location` because of
+    /// [`TrailingSlashPolicy::Redirect`](super::TrailingSlashPolicy::Redirect).
+    Redirect {
+        /// The path the request is redirected to.
+        location: String,
+    },
+    /// No route matches the path and the request is handled by the fallback.
+    Fallback,
+}
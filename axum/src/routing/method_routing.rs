@@ -6,13 +6,14 @@ use crate::{
     handler::Handler,
     http::{Method, Request, StatusCode},
     response::Response,
-    routing::{future::RouteFuture, Fallback, MethodFilter, Route},
+    routing::{future::RouteFuture, single_flight::SingleFlight, Fallback, MethodFilter, Route},
 };
 use axum_core::response::IntoResponse;
 use bytes::BytesMut;
 use std::{
     convert::Infallible,
     fmt,
+    sync::Arc,
     task::{Context, Poll},
 };
 use tower::{service_fn, util::MapResponseLayer};
@@ -481,8 +482,11 @@ pub struct MethodRouter<B = Body, E = Infallible> {
     trace: Option<Route<B, E>>,
     fallback: Fallback<B, E>,
     allow_header: AllowHeader,
+    layers: Vec<LayerFn<B, E>>,
 }
 
+type LayerFn<B, E> = Arc<dyn Fn(Route<B, E>) -> Route<B, E> + Send + Sync>;
+
 #[derive(Clone)]
 enum AllowHeader {
     /// No `Allow` header value has been built-up yet. This is the default state
@@ -546,6 +550,7 @@ impl<B, E> MethodRouter<B, E> {
             trace: None,
             allow_header: AllowHeader::None,
             fallback: Fallback::Default(fallback),
+            layers: Vec::new(),
         }
     }
 }
@@ -661,6 +666,23 @@ where
     pub fn into_make_service_with_connect_info<C>(self) -> IntoMakeServiceWithConnectInfo<Self, C> {
         IntoMakeServiceWithConnectInfo::new(self)
     }
+
+    #[doc = include_str!("../docs/method_routing/single_flight.md")]
+    pub fn single_flight<F>(mut self, key_fn: F) -> Self
+    where
+        F: Fn(&Request<B>) -> String + Send + Sync + 'static,
+    {
+        let key_fn: Arc<dyn Fn(&Request<B>) -> String + Send + Sync> = Arc::new(key_fn);
+
+        self.get = self
+            .get
+            .map(|svc| Route::new(SingleFlight::new(svc, Arc::clone(&key_fn))));
+        self.head = self
+            .head
+            .map(|svc| Route::new(SingleFlight::new(svc, Arc::clone(&key_fn))));
+
+        self
+    }
 }
 
 impl<ReqBody, E> MethodRouter<ReqBody, E> {
@@ -698,6 +720,57 @@ impl<ReqBody, E> MethodRouter<ReqBody, E> {
         self.on_service_boxed_response_body(filter, svc)
     }
 
+    /// Create a `MethodRouter` from pairs of methods and services.
+    ///
+    /// This is useful for building method routers from data, such as a spec
+    /// loaded at runtime, rather than chaining calls to [`MethodRouter::get`],
+    /// [`MethodRouter::post`], and so on. Each pair is added with
+    /// [`MethodRouter::on_service`].
+    ///
+    /// Since all services must have the same type, use a [`MethodRouter`]
+    /// per handler, for example by calling [`any`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum::{
+    ///     http::Method,
+    ///     routing::{any, MethodRouter},
+    ///     Router,
+    /// };
+    /// use std::collections::HashMap;
+    ///
+    /// let mut handlers = HashMap::new();
+    /// handlers.insert(Method::GET, any(|| async { "list users" }));
+    /// handlers.insert(Method::POST, any(|| async { "create user" }));
+    ///
+    /// let app = Router::new().route("/users", MethodRouter::from_handlers(handlers));
+    /// # async {
+    /// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+    /// # };
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a method has no [`MethodFilter`], such as `CONNECT`, or if the
+    /// same method is given more than once.
+    pub fn from_handlers<I, S>(handlers: I) -> Self
+    where
+        I: IntoIterator<Item = (Method, S)>,
+        S: Service<Request<ReqBody>, Error = E> + Clone + Send + 'static,
+        S::Response: IntoResponse + 'static,
+        S::Future: Send + 'static,
+    {
+        handlers
+            .into_iter()
+            .fold(Self::new(), |method_router, (method, svc)| {
+                let filter = MethodFilter::try_from(method).unwrap_or_else(|err| {
+                    panic!("Cannot build `MethodRouter` from handlers: {}", err)
+                });
+                method_router.on_service(filter, svc)
+            })
+    }
+
     chained_service_fn!(delete_service, DELETE);
     chained_service_fn!(get_service, GET);
     chained_service_fn!(head_service, HEAD);
@@ -714,7 +787,7 @@ impl<ReqBody, E> MethodRouter<ReqBody, E> {
         S::Response: IntoResponse + 'static,
         S::Future: Send + 'static,
     {
-        self.fallback = Fallback::Custom(Route::new(svc));
+        self.fallback = Fallback::Custom(self.apply_layers(Route::new(svc)));
         self
     }
 
@@ -724,7 +797,7 @@ impl<ReqBody, E> MethodRouter<ReqBody, E> {
         S::Response: IntoResponse + 'static,
         S::Future: Send + 'static,
     {
-        self.fallback = Fallback::Custom(Route::new(svc));
+        self.fallback = Fallback::Custom(self.apply_layers(Route::new(svc)));
         self
     }
 
@@ -753,9 +826,78 @@ impl<ReqBody, E> MethodRouter<ReqBody, E> {
             trace: self.trace.map(layer_fn),
             fallback: self.fallback.map(layer_fn),
             allow_header: self.allow_header,
+            layers: Vec::new(),
         }
     }
 
+    #[doc = include_str!("../docs/method_routing/layer_all.md")]
+    pub fn layer_all<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Route<ReqBody, E>> + Send + Sync + 'static,
+        L::Service: Service<Request<ReqBody>, Error = E> + Clone + Send + 'static,
+        <L::Service as Service<Request<ReqBody>>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request<ReqBody>>>::Future: Send + 'static,
+    {
+        let layer_fn: LayerFn<ReqBody, E> = Arc::new(move |svc| {
+            let svc = layer.layer(svc);
+            let svc = MapResponseLayer::new(IntoResponse::into_response).layer(svc);
+            Route::new(svc)
+        });
+
+        self.get = self.get.map(&*layer_fn);
+        self.head = self.head.map(&*layer_fn);
+        self.delete = self.delete.map(&*layer_fn);
+        self.options = self.options.map(&*layer_fn);
+        self.patch = self.patch.map(&*layer_fn);
+        self.post = self.post.map(&*layer_fn);
+        self.put = self.put.map(&*layer_fn);
+        self.trace = self.trace.map(&*layer_fn);
+        self.fallback = self.fallback.map(&*layer_fn);
+        self.layers.push(layer_fn);
+
+        self
+    }
+
+    /// Apply the layers added with [`MethodRouter::layer_all`] to a newly
+    /// added service, innermost first.
+    fn apply_layers(&self, svc: Route<ReqBody, E>) -> Route<ReqBody, E> {
+        self.layers.iter().fold(svc, |svc, layer_fn| layer_fn(svc))
+    }
+
+    #[doc = include_str!("../docs/method_routing/cors.md")]
+    #[cfg(feature = "cors")]
+    pub fn cors(self, config: super::CorsConfig) -> Self
+    where
+        ReqBody: HttpBody + Send + 'static,
+        E: 'static,
+    {
+        use tower_http::cors::AllowMethods;
+
+        let methods = if matches!(self.fallback, Fallback::Custom(_)) {
+            AllowMethods::mirror_request()
+        } else {
+            // `HEAD` requests are also handled by `GET` routes
+            let routes = [
+                (Method::GET, self.get.is_some()),
+                (Method::HEAD, self.get.is_some() || self.head.is_some()),
+                (Method::DELETE, self.delete.is_some()),
+                (Method::OPTIONS, self.options.is_some()),
+                (Method::PATCH, self.patch.is_some()),
+                (Method::POST, self.post.is_some()),
+                (Method::PUT, self.put.is_some()),
+                (Method::TRACE, self.trace.is_some()),
+            ];
+            let methods = routes
+                .into_iter()
+                .filter(|(_, routed)| *routed)
+                .map(|(method, _)| method)
+                .collect::<Vec<_>>();
+            AllowMethods::list(methods)
+        };
+
+        self.layer(config.into_layer(methods))
+    }
+
     #[doc = include_str!("../docs/method_routing/route_layer.md")]
     pub fn route_layer<L>(mut self, layer: L) -> MethodRouter<ReqBody, E>
     where
@@ -872,7 +1014,7 @@ impl<ReqBody, E> MethodRouter<ReqBody, E> {
             }
         }
 
-        let svc = Route::new(svc);
+        let svc = self.apply_layers(Route::new(svc));
 
         set_service(
             "GET",
@@ -957,10 +1099,87 @@ impl<ReqBody, E> MethodRouter<ReqBody, E> {
         self
     }
 
+    /// Replace the default fallback, that responds with `405 Method Not Allowed`,
+    /// with another service. Custom fallbacks are kept as is.
+    pub(crate) fn default_fallback(mut self, svc: Route<ReqBody, E>) -> Self {
+        if let Fallback::Default(_) = self.fallback {
+            self.fallback = Fallback::Default(self.apply_layers(svc));
+        }
+        self
+    }
+
     fn skip_allow_header(mut self) -> Self {
         self.allow_header = AllowHeader::Skip;
         self
     }
+
+    /// Returns `true` if requests with `method` are handled by a route or a
+    /// custom fallback rather than getting `405 Method Not Allowed`.
+    pub(crate) fn handles(&self, method: &Method) -> bool {
+        let route = match *method {
+            Method::GET => &self.get,
+            Method::HEAD => {
+                if self.head.is_some() {
+                    &self.head
+                } else {
+                    &self.get
+                }
+            }
+            Method::DELETE => &self.delete,
+            Method::OPTIONS => &self.options,
+            Method::PATCH => &self.patch,
+            Method::POST => &self.post,
+            Method::PUT => &self.put,
+            Method::TRACE => &self.trace,
+            _ => &None,
+        };
+
+        route.is_some() || matches!(self.fallback, Fallback::Custom(_))
+    }
+
+    /// Returns `true` if merging with `other` would panic because both handle
+    /// the same method or both have a custom fallback.
+    pub(crate) fn overlaps(&self, other: &Self) -> bool {
+        let both_custom_fallbacks = matches!(
+            (&self.fallback, &other.fallback),
+            (Fallback::Custom(_), Fallback::Custom(_))
+        );
+
+        both_custom_fallbacks
+            || (self.get.is_some() && other.get.is_some())
+            || (self.head.is_some() && other.head.is_some())
+            || (self.delete.is_some() && other.delete.is_some())
+            || (self.options.is_some() && other.options.is_some())
+            || (self.patch.is_some() && other.patch.is_some())
+            || (self.post.is_some() && other.post.is_some())
+            || (self.put.is_some() && other.put.is_some())
+            || (self.trace.is_some() && other.trace.is_some())
+    }
+
+    /// The methods this `MethodRouter` has routes for.
+    ///
+    /// Returns an empty list if the `MethodRouter` has a custom fallback and
+    /// thus accepts any method. `HEAD` is only included if it was added
+    /// explicitly, even though `GET` routes also handle `HEAD` requests.
+    pub fn methods(&self) -> Vec<Method> {
+        if let Fallback::Custom(_) = self.fallback {
+            return Vec::new();
+        }
+
+        [
+            (self.get.is_some(), Method::GET),
+            (self.head.is_some(), Method::HEAD),
+            (self.delete.is_some(), Method::DELETE),
+            (self.options.is_some(), Method::OPTIONS),
+            (self.patch.is_some(), Method::PATCH),
+            (self.post.is_some(), Method::POST),
+            (self.put.is_some(), Method::PUT),
+            (self.trace.is_some(), Method::TRACE),
+        ]
+        .into_iter()
+        .filter_map(|(is_some, method)| is_some.then(|| method))
+        .collect()
+    }
 }
 
 fn append_allow_header(allow_header: &mut AllowHeader, method: &'static str) {
@@ -996,6 +1215,7 @@ impl<B, E> Clone for MethodRouter<B, E> {
             trace: self.trace.clone(),
             fallback: self.fallback.clone(),
             allow_header: self.allow_header.clone(),
+            layers: self.layers.clone(),
         }
     }
 }
@@ -1053,6 +1273,7 @@ where
             trace,
             fallback,
             allow_header,
+            layers: _,
         } = self;
 
         call!(req, method, HEAD, head);
@@ -1085,10 +1306,12 @@ mod tests {
     use super::*;
     use crate::{body::Body, error_handling::HandleErrorLayer};
     use axum_core::response::IntoResponse;
-    use http::{header::ALLOW, HeaderMap};
+    use http::{header::ALLOW, HeaderMap, HeaderName, HeaderValue};
     use std::time::Duration;
     use tower::{timeout::TimeoutLayer, Service, ServiceBuilder, ServiceExt};
-    use tower_http::{auth::RequireAuthorizationLayer, services::fs::ServeDir};
+    use tower_http::{
+        auth::RequireAuthorizationLayer, services::fs::ServeDir, set_header::SetResponseHeaderLayer,
+    };
 
     #[tokio::test]
     async fn method_not_allowed_by_default() {
@@ -1133,6 +1356,32 @@ mod tests {
         assert_eq!(status, StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn from_handlers() {
+        let mut svc =
+            MethodRouter::from_handlers(vec![(Method::GET, any(ok)), (Method::POST, any(created))]);
+
+        let (status, _, body) = call(Method::GET, &mut svc).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "ok");
+
+        let (status, _, body) = call(Method::POST, &mut svc).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body, "created");
+
+        let (status, headers, _) = call(Method::DELETE, &mut svc).await;
+        assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(headers[ALLOW], "GET,HEAD,POST");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot build `MethodRouter` from handlers: no `MethodFilter` for `CONNECT`"
+    )]
+    fn from_handlers_with_unsupported_method() {
+        let _: MethodRouter = MethodRouter::from_handlers(vec![(Method::CONNECT, any(ok))]);
+    }
+
     #[tokio::test]
     async fn layer() {
         let mut svc = MethodRouter::new()
@@ -1148,6 +1397,61 @@ mod tests {
         assert_eq!(status, StatusCode::UNAUTHORIZED);
     }
 
+    #[tokio::test]
+    async fn layer_all() {
+        let mut svc = MethodRouter::new()
+            .get(|| async { std::future::pending::<()>().await })
+            .layer_all(RequireAuthorizationLayer::bearer("password"))
+            .post(|| async { std::future::pending::<()>().await })
+            .fallback((|| async { std::future::pending::<()>().await }).into_service());
+
+        // method added before `layer_all`
+        let (status, _, _) = call(Method::GET, &mut svc).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // method added after `layer_all`
+        let (status, _, _) = call(Method::POST, &mut svc).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // fallback added after `layer_all`
+        let (status, _, _) = call(Method::DELETE, &mut svc).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn layer_doesnt_apply_to_methods_added_later() {
+        let mut svc = MethodRouter::new()
+            .get(|| async {})
+            .layer(RequireAuthorizationLayer::bearer("password"))
+            .post(|| async {});
+
+        let (status, _, _) = call(Method::GET, &mut svc).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, _, _) = call(Method::POST, &mut svc).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn layer_all_doesnt_apply_to_methods_added_after_layer() {
+        let mut svc = MethodRouter::new()
+            .get(|| async {})
+            .layer_all(RequireAuthorizationLayer::bearer("password"))
+            .layer(SetResponseHeaderLayer::overriding(
+                HeaderName::from_static("x-layer"),
+                HeaderValue::from_static("1"),
+            ))
+            .post(|| async {});
+
+        let (status, headers, _) = call(Method::GET, &mut svc).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(headers["x-layer"], "1");
+
+        let (status, headers, _) = call(Method::POST, &mut svc).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(!headers.contains_key("x-layer"));
+    }
+
     #[tokio::test]
     async fn route_layer() {
         let mut svc = MethodRouter::new()
@@ -1163,6 +1467,124 @@ mod tests {
         assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn single_flight() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let svc = MethodRouter::new()
+            .get({
+                let calls = Arc::clone(&calls);
+                move |uri: http::Uri| async move {
+                    let n = calls.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    format!("{} {}", uri, n)
+                }
+            })
+            .single_flight(|req| req.uri().to_string());
+
+        let get = |uri: &'static str| {
+            let svc = svc.clone();
+            async move {
+                let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+                let res = svc.oneshot(req).await.unwrap();
+                let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+                String::from_utf8(body.to_vec()).unwrap()
+            }
+        };
+
+        let (a, b, c) = futures::join!(get("/a"), get("/a"), get("/b"));
+        assert_eq!(a, "/a 0");
+        assert_eq!(b, "/a 0");
+        assert_eq!(c, "/b 1");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // the in-flight request is done so a new one is made
+        assert_eq!(get("/a").await, "/a 2");
+    }
+
+    #[tokio::test]
+    async fn single_flight_handler_panic_does_not_poison_key() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let panicked = Arc::new(AtomicBool::new(false));
+        let svc = MethodRouter::new()
+            .get({
+                let panicked = Arc::clone(&panicked);
+                move || async move {
+                    if !panicked.swap(true, Ordering::SeqCst) {
+                        panic!("handler panicked");
+                    }
+                    "ok"
+                }
+            })
+            .single_flight(|req| req.uri().to_string());
+
+        let req = || Request::builder().uri("/").body(Body::empty()).unwrap();
+
+        let res = tokio::spawn(svc.clone().oneshot(req())).await;
+        assert!(res.unwrap_err().is_panic());
+
+        let res = svc.oneshot(req()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(body, "ok");
+    }
+
+    #[cfg(feature = "cors")]
+    #[tokio::test]
+    async fn cors() {
+        use http::header::{
+            ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN,
+            ACCESS_CONTROL_REQUEST_METHOD, ORIGIN,
+        };
+
+        let config = super::super::CorsConfig::new()
+            .allow_origin(http::HeaderValue::from_static("https://example.com"));
+        let svc = get(ok).post(created).cors(config);
+
+        let preflight = Request::builder()
+            .method(Method::OPTIONS)
+            .header(ORIGIN, "https://example.com")
+            .header(ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .body(Body::empty())
+            .unwrap();
+        let res = svc.clone().oneshot(preflight).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()[ACCESS_CONTROL_ALLOW_METHODS], "GET,HEAD,POST");
+        assert_eq!(
+            res.headers()[ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://example.com"
+        );
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .header(ORIGIN, "https://example.com")
+            .body(Body::empty())
+            .unwrap();
+        let res = svc.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+        assert_eq!(
+            res.headers()[ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://example.com"
+        );
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .header(ORIGIN, "https://other.example.com")
+            .body(Body::empty())
+            .unwrap();
+        let res = svc.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(!res.headers().contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+
+        // method routers without `cors` don't get any CORS headers
+        let mut svc = get(ok);
+        let (status, headers, _) = call(Method::OPTIONS, &mut svc).await;
+        assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+        assert!(!headers.contains_key(ACCESS_CONTROL_ALLOW_METHODS));
+    }
+
     #[allow(dead_code)]
     fn buiding_complex_router() {
         let app = crate::Router::new().route(
@@ -1,42 +1,90 @@
 //! Routing between [`Service`]s and handlers.
 
-use self::{future::RouteFuture, not_found::NotFound};
+use self::{
+    body_read_timeout::BodyReadTimeout, future::RouteFuture, map_html_body::MapHtmlBody,
+    not_found::NotFound, predicate::Predicated, route_meta::InsertRouteMeta,
+    route_timeout::RouteTimeoutLayer, strip_bodies::StripDisallowedBodies, tap::Tap,
+};
 use crate::{
-    body::{Body, HttpBody},
-    extract::connect_info::IntoMakeServiceWithConnectInfo,
-    response::Response,
-    routing::strip_prefix::StripPrefix,
-    util::try_downcast,
+    body::{boxed, Body, BoxBody, Bytes, HttpBody},
+    extract::{
+        connect_info::IntoMakeServiceWithConnectInfo,
+        rate_limit::{RateLimitConfig, RateLimited},
+    },
+    handler::Handler,
+    middleware::from_extractor,
+    response::{PendingError, Redirect, Response},
+    routing::strip_prefix::{CaseInsensitive, StripPrefix},
+    util::{try_downcast, PercentDecodedStr},
+    BoxError, Extension,
 };
 use axum_core::response::IntoResponse;
-use http::Request;
+use http::{HeaderMap, Method, Request, StatusCode, Version};
 use matchit::MatchError;
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
 use std::{
     borrow::Cow,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     convert::Infallible,
     fmt,
-    sync::Arc,
+    future::{ready, Future},
+    net::SocketAddr,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
+    time::Duration,
+};
+use tower::{
+    layer::layer_fn,
+    service_fn,
+    util::{MapRequestLayer, MapResponseLayer},
+    ServiceBuilder,
 };
-use tower::{layer::layer_fn, util::MapResponseLayer, ServiceBuilder};
+#[cfg(feature = "catch-panic")]
+use tower_http::catch_panic::CatchPanicLayer;
 use tower_layer::Layer;
 use tower_service::Service;
 
 pub mod future;
 
+mod body_read_timeout;
+#[cfg(feature = "compression")]
+mod compression;
+#[cfg(feature = "cors")]
+mod cors;
+#[cfg(feature = "decompression")]
+mod decompression;
+mod health;
 mod into_make_service;
+mod map_html_body;
+mod match_outcome;
 mod method_filter;
 mod method_routing;
+mod normalize_path;
 mod not_found;
+mod predicate;
 mod route;
+mod route_meta;
+mod route_timeout;
+mod single_flight;
+mod strip_bodies;
 mod strip_prefix;
+mod tap;
+mod trailing_slash;
 pub(crate) mod url_params;
 
 #[cfg(test)]
 mod tests;
 
-pub use self::{into_make_service::IntoMakeService, method_filter::MethodFilter, route::Route};
+pub use self::{
+    health::HealthConfig, into_make_service::IntoMakeService, match_outcome::MatchOutcome,
+    method_filter::MethodFilter, predicate::Predicate, route::Route, route_meta::RouteMeta,
+    trailing_slash::TrailingSlashPolicy,
+};
+
+#[cfg(feature = "compression")]
+pub use self::compression::CompressionConfig;
+#[cfg(feature = "cors")]
+pub use self::cors::CorsConfig;
 
 pub use self::method_routing::{
     any, any_service, delete, delete_service, get, get_service, head, head_service, on, on_service,
@@ -44,6 +92,70 @@ pub use self::method_routing::{
     trace_service, MethodRouter,
 };
 
+macro_rules! route_handler_fn {
+    (
+        $name:ident, GET
+    ) => {
+        route_handler_fn!(
+            /// Add a route for `GET` requests to the given handler.
+            ///
+            /// This is a shorthand for [`Router::route`] with
+            /// [`get`](method_routing::get). Call it multiple times, or
+            /// together with the other methods such as [`Router::post`], to
+            /// accept several methods at the same path.
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            /// use axum::Router;
+            ///
+            /// async fn list_users() {}
+            ///
+            /// async fn create_user() {}
+            ///
+            /// let app = Router::new()
+            ///     .get("/users", list_users)
+            ///     .post("/users", create_user);
+            /// # async {
+            /// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+            /// # };
+            /// ```
+            ///
+            /// Note that `get` routes will also be called for `HEAD` requests but will have
+            /// the response body removed.
+            $name,
+            GET
+        );
+    };
+
+    (
+        $name:ident, $method:ident
+    ) => {
+        route_handler_fn!(
+            #[doc = concat!("Add a route for `", stringify!($method), "` requests to the given handler.")]
+            ///
+            #[doc = concat!("This is a shorthand for [`Router::route`] with [`", stringify!($name), "`](method_routing::", stringify!($name), ").")]
+            /// See [`Router::get`] for an example.
+            $name,
+            $method
+        );
+    };
+
+    (
+        $(#[$m:meta])+
+        $name:ident, $method:ident
+    ) => {
+        $(#[$m])+
+        pub fn $name<H, T>(self, path: &str, handler: H) -> Self
+        where
+            H: Handler<T, B>,
+            T: 'static,
+        {
+            self.route(path, method_routing::$name(handler))
+        }
+    };
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct RouteId(u32);
 
@@ -65,16 +177,73 @@ pub struct Router<B = Body> {
     routes: HashMap<RouteId, Endpoint<B>>,
     node: Arc<Node>,
     fallback: Fallback<B>,
+    method_not_allowed: Option<Route<B>>,
+    // in the same order as `RouteConfig::fallback_prefixes`
+    prefix_fallbacks: Vec<Route<B>>,
     nested_at_root: bool,
+    pre_routing_layered: bool,
+    missing_route_hook: Option<MissingRouteHook>,
+    trailing_slash: TrailingSlashPolicy,
+    normalize_path: bool,
+    config: Arc<RouteConfig>,
+    http_versions: Option<Arc<[Version]>>,
+    route_registered_hook: Option<RouteRegisteredHook<B>>,
+}
+
+/// Settings of a router's routes that don't change while serving requests.
+///
+/// Like [`Node`] they're kept in an `Arc`, so cloning a router, which
+/// `IntoMakeService` does for every connection, doesn't copy them.
+#[derive(Clone, Default, Debug)]
+struct RouteConfig {
+    names: HashMap<Arc<str>, Arc<str>>,
+    // the timeouts the routes are wrapped with, `None` for routes opted out
+    // with `Router::without_timeout`
+    timeouts: HashMap<RouteId, Option<Duration>>,
+    bypass_route_layers: HashSet<Arc<str>>,
+    meta: HashMap<RouteId, Vec<InsertRouteMeta>>,
+    fallback_prefixes: Vec<FallbackPrefix>,
+}
+
+/// The prefix of a fallback added with `Router::fallback_for_prefix`.
+#[derive(Clone, Debug)]
+struct FallbackPrefix {
+    prefix: Arc<str>,
+    // `prefix` lowercased if the router is case insensitive, so requests can
+    // be matched against it without allocating
+    lookup: Arc<str>,
+}
+
+impl FallbackPrefix {
+    fn new(prefix: Arc<str>, node: &Node) -> Self {
+        let lookup = match node.lookup_path(&prefix) {
+            Cow::Borrowed(_) => Arc::clone(&prefix),
+            Cow::Owned(lookup) => lookup.into(),
+        };
+        Self { prefix, lookup }
+    }
 }
 
+type MissingRouteHook = Arc<dyn Fn(&http::request::Parts) + Send + Sync>;
+
+type RouteRegisteredHook<B> = Arc<Mutex<dyn FnMut(&str, &MethodRouter<B>) + Send>>;
+
 impl<B> Clone for Router<B> {
     fn clone(&self) -> Self {
         Self {
             routes: self.routes.clone(),
             node: Arc::clone(&self.node),
             fallback: self.fallback.clone(),
+            method_not_allowed: self.method_not_allowed.clone(),
+            prefix_fallbacks: self.prefix_fallbacks.clone(),
             nested_at_root: self.nested_at_root,
+            pre_routing_layered: self.pre_routing_layered,
+            missing_route_hook: self.missing_route_hook.clone(),
+            trailing_slash: self.trailing_slash,
+            normalize_path: self.normalize_path,
+            config: Arc::clone(&self.config),
+            http_versions: self.http_versions.clone(),
+            route_registered_hook: self.route_registered_hook.clone(),
         }
     }
 }
@@ -94,11 +263,42 @@ impl<B> fmt::Debug for Router<B> {
             .field("routes", &self.routes)
             .field("node", &self.node)
             .field("fallback", &self.fallback)
+            .field("method_not_allowed", &self.method_not_allowed)
+            .field("prefix_fallbacks", &self.prefix_fallbacks)
             .field("nested_at_root", &self.nested_at_root)
+            .field("pre_routing_layered", &self.pre_routing_layered)
+            .field("missing_route_hook", &self.missing_route_hook.is_some())
+            .field("trailing_slash", &self.trailing_slash)
+            .field("normalize_path", &self.normalize_path)
+            .field("config", &self.config)
+            .field("http_versions", &self.http_versions)
+            .field(
+                "route_registered_hook",
+                &self.route_registered_hook.is_some(),
+            )
             .finish()
     }
 }
 
+const DEFAULT_PRIORITY: i32 = 0;
+
+/// Characters that are percent-encoded in path segments built by
+/// `Router::url_for`.
+const PATH_SEGMENT: &AsciiSet = &WILDCARD.add(b'/');
+
+/// Same as `PATH_SEGMENT` but for wildcards, which can contain `/`.
+const WILDCARD: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
 pub(crate) const NEST_TAIL_PARAM: &str = "__private__axum_nest_tail_param";
 const NEST_TAIL_PARAM_CAPTURE: &str = "/*__private__axum_nest_tail_param";
 
@@ -115,25 +315,197 @@ where
             routes: Default::default(),
             node: Default::default(),
             fallback: Fallback::Default(Route::new(NotFound)),
+            method_not_allowed: None,
+            prefix_fallbacks: Vec::new(),
             nested_at_root: false,
+            pre_routing_layered: false,
+            missing_route_hook: None,
+            trailing_slash: TrailingSlashPolicy::Strict,
+            normalize_path: false,
+            config: Default::default(),
+            http_versions: None,
+            route_registered_hook: None,
         }
     }
 
     #[doc = include_str!("../docs/routing/route.md")]
-    pub fn route<T>(mut self, path: &str, service: T) -> Self
+    pub fn route<T>(self, path: &str, service: T) -> Self
+    where
+        T: Service<Request<B>, Error = Infallible> + Clone + Send + 'static,
+        T::Response: IntoResponse,
+        T::Future: Send + 'static,
+    {
+        self.route_with_priority(path, service, DEFAULT_PRIORITY)
+    }
+
+    #[doc = include_str!("../docs/routing/route_named.md")]
+    pub fn route_named<T>(mut self, name: &str, path: &str, service: T) -> Self
+    where
+        T: Service<Request<B>, Error = Infallible> + Clone + Send + 'static,
+        T::Response: IntoResponse,
+        T::Future: Send + 'static,
+    {
+        self = self.route(path, service);
+        self.set_route_name(name.into(), path.into());
+        self
+    }
+
+    #[doc = include_str!("../docs/routing/route_methods.md")]
+    pub fn route_methods<H, T>(self, path: &str, methods: &[Method], handler: H) -> Self
+    where
+        H: Handler<T, B>,
+        T: 'static,
+    {
+        if methods.is_empty() {
+            panic!("Invalid route: `Router::route_methods` requires at least one method");
+        }
+
+        let filter = methods
+            .iter()
+            .map(|method| match MethodFilter::try_from(method.clone()) {
+                Ok(filter) => filter,
+                Err(err) => panic!("Invalid route: {}", err),
+            })
+            .fold(MethodFilter::empty(), |acc, filter| acc | filter);
+
+        self.route(path, on(filter, handler))
+    }
+
+    #[doc = include_str!("../docs/routing/todo.md")]
+    pub fn todo(self, path: &str, methods: MethodFilter) -> Self {
+        self.route(path, on(methods, || async { StatusCode::NOT_IMPLEMENTED }))
+    }
+
+    #[doc = include_str!("../docs/routing/with_health_checks.md")]
+    pub fn with_health_checks(self, config: HealthConfig) -> Self {
+        let liveness = config.liveness_check();
+        let readiness = config.readiness_check();
+
+        self.route(&config.health_path, get(move || liveness.clone().run()))
+            .route(&config.ready_path, get(move || readiness.clone().run()))
+    }
+
+    fn set_route_name(&mut self, name: Arc<str>, path: Arc<str>) {
+        if let Some(existing) = self.config.names.get(&name) {
+            panic!(
+                "Invalid route name: `{}` is already used for `{}`",
+                name, existing
+            );
+        }
+        Arc::make_mut(&mut self.config).names.insert(name, path);
+    }
+
+    #[doc = include_str!("../docs/routing/route_with_meta.md")]
+    pub fn route_with_meta<T, M>(mut self, path: &str, service: T, meta: M) -> Self
     where
         T: Service<Request<B>, Error = Infallible> + Clone + Send + 'static,
         T::Response: IntoResponse,
         T::Future: Send + 'static,
+        M: Clone + Send + Sync + 'static,
+    {
+        self = self.route(path, service);
+        self.add_route_meta(path, vec![InsertRouteMeta::new(meta)]);
+        self
+    }
+
+    /// Add metadata to the route at `path`, unless the route was dropped
+    /// because it conflicted with a route of higher priority.
+    fn add_route_meta(&mut self, path: &str, meta: Vec<InsertRouteMeta>) {
+        if let Some(id) = self.node.path_to_route_id.get(path).copied() {
+            Arc::make_mut(&mut self.config)
+                .meta
+                .entry(id)
+                .or_default()
+                .extend(meta);
+        }
+    }
+
+    /// Record the timeout of a route from a router that was nested or merged
+    /// into this one. The route is already wrapped with the timeout.
+    fn add_route_timeout(&mut self, path: &str, timeout: Option<Duration>) {
+        let id = match self.node.path_to_route_id.get(path) {
+            Some(id) => *id,
+            // the route was dropped because it conflicted with a route of
+            // higher priority
+            None => return,
+        };
+        if let Some(existing) = self.config.timeouts.get(&id) {
+            if *existing != timeout {
+                panic!(
+                    "Cannot combine `Router`s with different timeouts for `{}`",
+                    path
+                );
+            }
+        }
+        Arc::make_mut(&mut self.config).timeouts.insert(id, timeout);
+    }
+
+    /// Build the path of the route with the given name, substituting the
+    /// captures with `params`.
+    ///
+    /// Routes are named with [`Router::route_named`]. The path includes the
+    /// prefixes added by [`Router::nest`] and [`Router::prefix_paths`].
+    /// Parameter values are percent-encoded, except for `/` in wildcard
+    /// captures. Parameters that the route doesn't capture are ignored.
+    ///
+    /// Returns `None` if there is no route with the name or a capture is
+    /// missing from `params`.
+    ///
+    /// See [`Router::route_named`] for an example.
+    pub fn url_for<I, K, V>(&self, name: &str, params: I) -> Option<String>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
     {
-        fn validate_path(path: &str) {
-            if path.is_empty() {
-                panic!("Paths must start with a `/`. Use \"/\" for root routes");
-            } else if !path.starts_with('/') {
-                panic!("Paths must start with a `/`");
+        let template = self.config.names.get(name)?;
+        let params = params.into_iter().collect::<Vec<_>>();
+        let param = |key: &str| {
+            params
+                .iter()
+                .find(|(k, _)| k.as_ref() == key)
+                .map(|(_, v)| v.as_ref())
+        };
+
+        let mut url = String::with_capacity(template.len());
+        for (idx, segment) in template.split('/').enumerate() {
+            if idx > 0 {
+                url.push('/');
+            }
+
+            if let Some(key) = segment.strip_prefix(':') {
+                let value = utf8_percent_encode(param(key)?, PATH_SEGMENT);
+                url.extend(value);
+            } else if let Some(key) = segment.strip_prefix('*') {
+                let value = param(key)?;
+                let value = value.strip_prefix('/').unwrap_or(value);
+                url.extend(utf8_percent_encode(value, WILDCARD));
+            } else {
+                url.push_str(segment);
             }
         }
 
+        Some(url)
+    }
+
+    route_handler_fn!(delete, DELETE);
+    route_handler_fn!(get, GET);
+    route_handler_fn!(head, HEAD);
+    route_handler_fn!(options, OPTIONS);
+    route_handler_fn!(patch, PATCH);
+    route_handler_fn!(post, POST);
+    route_handler_fn!(put, PUT);
+    route_handler_fn!(trace, TRACE);
+
+    #[doc = include_str!("../docs/routing/route_with_priority.md")]
+    pub fn route_with_priority<T>(mut self, path: &str, service: T, priority: i32) -> Self
+    where
+        T: Service<Request<B>, Error = Infallible> + Clone + Send + 'static,
+        T::Response: IntoResponse,
+        T::Future: Send + 'static,
+    {
+        self.assert_not_pre_routing_layered();
+
         validate_path(path);
 
         let service = match try_downcast::<Router<B>, _>(service) {
@@ -146,7 +518,11 @@ where
         let id = RouteId::next();
 
         let service = match try_downcast::<MethodRouter<B, Infallible>, _>(service) {
-            Ok(method_router) => {
+            Ok(mut method_router) => {
+                if let Some(method_not_allowed) = &self.method_not_allowed {
+                    method_router = method_router.default_fallback(method_not_allowed.clone());
+                }
+
                 if let Some((route_id, Endpoint::MethodRouter(prev_method_router))) = self
                     .node
                     .path_to_route_id
@@ -155,6 +531,7 @@ where
                 {
                     // if we're adding a new `MethodRouter` to a route that already has one just
                     // merge them. This makes `.route("/", get(_)).route("/", post(_))` work
+                    self.notify_route_registered(path, &method_router);
                     let service =
                         Endpoint::MethodRouter(prev_method_router.clone().merge(method_router));
                     self.routes.insert(route_id, service);
@@ -163,23 +540,206 @@ where
                     Endpoint::MethodRouter(method_router)
                 }
             }
-            Err(service) => Endpoint::Route(Route::new(service)),
+            Err(service) => Endpoint::Route(into_route(service)),
         };
 
-        self.set_node(path, id);
+        if self.set_node(path, id, priority) {
+            match &service {
+                Endpoint::MethodRouter(method_router) => {
+                    self.notify_route_registered(path, method_router);
+                }
+                Endpoint::Route(route) => self.notify_service_registered(path, route),
+                Endpoint::Predicated(_) => {}
+            }
+            self.routes.insert(id, service);
+        }
+
+        self
+    }
+
+    fn notify_route_registered(&self, path: &str, method_router: &MethodRouter<B>) {
+        if let Some(hook) = &self.route_registered_hook {
+            // `/foo/*__private__axum_nest_tail_param` is reported as `/foo/*`
+            let path = path.strip_suffix(NEST_TAIL_PARAM).unwrap_or(path);
+            (hook.lock().unwrap())(path, method_router);
+        }
+    }
+
+    /// Report a service that isn't a `MethodRouter` as one that routes all
+    /// methods to it.
+    fn notify_service_registered(&self, path: &str, route: &Route<B>) {
+        if self.route_registered_hook.is_some() {
+            let method_router = MethodRouter::new().fallback(route.clone());
+            self.notify_route_registered(path, &method_router);
+        }
+    }
+
+    #[doc = include_str!("../docs/routing/route_predicated.md")]
+    pub fn route_predicated<T>(self, path: &str, routes: &[(Predicate, T)]) -> Self
+    where
+        T: Service<Request<B>, Error = Infallible> + Clone + Send + 'static,
+        T::Response: IntoResponse,
+        T::Future: Send + 'static,
+    {
+        if routes.is_empty() {
+            panic!("`Router::route_predicated` requires at least one service");
+        }
+
+        let predicated = Predicated::new(
+            routes
+                .iter()
+                .map(|(predicate, svc)| (predicate.clone(), Route::new(svc.clone())))
+                .collect(),
+        );
+
+        self.route_predicated_with_priority(path, predicated, DEFAULT_PRIORITY)
+    }
+
+    #[doc = include_str!("../docs/routing/route_for_host.md")]
+    pub fn route_for_host<T>(mut self, host: &str, path: &str, service: T) -> Self
+    where
+        T: Service<Request<B>, Error = Infallible> + Clone + Send + 'static,
+        T::Response: IntoResponse,
+        T::Future: Send + 'static,
+    {
+        self.assert_not_pre_routing_layered();
+
+        let predicate = Predicate::host(host);
+        let route = into_route(service);
+
+        let existing = self
+            .node
+            .path_to_route_id
+            .get(path)
+            .copied()
+            .filter(|id| matches!(self.routes.get(id), Some(Endpoint::Predicated(_))));
+        if let Some(id) = existing {
+            self.notify_service_registered(path, &route);
+            if let Some(Endpoint::Predicated(predicated)) = self.routes.get_mut(&id) {
+                predicated.push(predicate, route);
+            }
+            return self;
+        }
+
+        self.route_predicated_with_priority(
+            path,
+            Predicated::new(vec![(predicate, route)]),
+            DEFAULT_PRIORITY,
+        )
+    }
+
+    fn route_predicated_with_priority(
+        mut self,
+        path: &str,
+        predicated: Predicated<B>,
+        priority: i32,
+    ) -> Self {
+        self.assert_not_pre_routing_layered();
+
+        validate_path(path);
 
-        self.routes.insert(id, service);
+        let id = RouteId::next();
+        if self.set_node(path, id, priority) {
+            for route in predicated.routes() {
+                self.notify_service_registered(path, route);
+            }
+            self.routes.insert(id, Endpoint::Predicated(predicated));
+        }
 
         self
     }
 
-    fn set_node(&mut self, path: &str, id: RouteId) {
+    /// Insert `path` into the routing tree.
+    ///
+    /// Returns `false` if the route conflicted with a route of higher priority
+    /// and was therefore dropped.
+    fn set_node(&mut self, path: &str, id: RouteId, priority: i32) -> bool {
         let mut node =
             Arc::try_unwrap(Arc::clone(&self.node)).unwrap_or_else(|node| (*node).clone());
-        if let Err(err) = node.insert(path, id) {
-            self.panic_on_matchit_error(err);
-        }
+
+        let inserted = match node.insert(path, id, priority) {
+            Ok(()) => true,
+            Err(err @ matchit::InsertError::Conflict { .. }) => {
+                match node.insert_by_priority(path, id, priority) {
+                    PriorityResolution::Inserted { dropped } => {
+                        for dropped_id in dropped {
+                            self.routes.remove(&dropped_id);
+
+                            let dropped_path = match self.node.route_id_to_path.get(&dropped_id) {
+                                Some(dropped_path) => Arc::clone(dropped_path),
+                                None => continue,
+                            };
+
+                            #[cfg(feature = "tracing")]
+                            tracing::warn!(
+                                route = %dropped_path,
+                                "dropping route that conflicts with higher priority route: {}",
+                                path,
+                            );
+
+                            self.remove_route_metadata(dropped_id, &dropped_path);
+                        }
+                        true
+                    }
+                    PriorityResolution::Rejected => {
+                        node.unreachable.push(path.into());
+
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(
+                            route = %path,
+                            "dropping route that conflicts with higher priority route: {}",
+                            err,
+                        );
+                        false
+                    }
+                    PriorityResolution::Tie => {
+                        self.panic_on_matchit_error(err);
+                    }
+                }
+            }
+            Err(err) => self.panic_on_matchit_error(err),
+        };
+
         self.node = Arc::new(node);
+
+        inserted
+    }
+
+    /// Remove the names, metadata and timeouts of a route that was dropped, so
+    /// they aren't applied to or reported for a route that no longer exists.
+    fn remove_route_metadata(&mut self, id: RouteId, path: &str) {
+        let config = Arc::make_mut(&mut self.config);
+        config.names.retain(|_, route_path| &**route_path != path);
+        config.meta.remove(&id);
+        config.timeouts.remove(&id);
+        config.bypass_route_layers.remove(path);
+    }
+
+    /// Record the paths of routes that were dropped from another router
+    /// before it was combined with this one.
+    fn add_unreachable<I>(&mut self, paths: I)
+    where
+        I: IntoIterator<Item = Arc<str>>,
+    {
+        let mut paths = paths.into_iter().peekable();
+        if paths.peek().is_some() {
+            Arc::make_mut(&mut self.node).unreachable.extend(paths);
+        }
+    }
+
+    #[doc = include_str!("../docs/routing/unreachable_routes.md")]
+    pub fn unreachable_routes(&self) -> Vec<String> {
+        let mut paths = self
+            .node
+            .unreachable
+            .iter()
+            // a route might have been added again at the same path
+            .filter(|path| !self.node.path_to_route_id.contains_key(*path))
+            .map(|path| path.to_string())
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths.dedup();
+        paths
     }
 
     #[doc = include_str!("../docs/routing/nest.md")]
@@ -213,39 +773,117 @@ where
                     mut routes,
                     node,
                     fallback,
+                    // the nested router's routes already use its method not allowed
+                    // handler, unless this router has one which takes precedence
+                    method_not_allowed: _,
+                    prefix_fallbacks,
                     // nesting a router that has something nested at root
                     // doesn't mean something is nested at root in _this_ router
                     // thus we don't need to propagate that
                     nested_at_root: _,
+                    pre_routing_layered: _,
+                    missing_route_hook,
+                    // the routes are added to this router so its policy is used
+                    trailing_slash: _,
+                    normalize_path: _,
+                    config,
+                    // the routes are added to this router so its versions are used
+                    http_versions: _,
+                    // the nested router's hook has already seen its routes
+                    route_registered_hook: _,
                 } = router;
 
                 if let Fallback::Custom(_) = fallback {
                     panic!("Cannot nest `Router`s that has a fallback");
                 }
 
-                for (id, nested_path) in &node.route_id_to_path {
-                    let route = routes.remove(id).unwrap();
-                    let full_path: Cow<str> = if &**nested_path == "/" {
+                if missing_route_hook.is_some() {
+                    panic!("Cannot nest `Router`s that have a missing route hook");
+                }
+
+                let full_path = |nested_path: &str| -> Cow<str> {
+                    if nested_path == "/" {
                         path.into()
                     } else if path == "/" {
-                        (&**nested_path).into()
+                        nested_path.to_owned().into()
                     } else if let Some(path) = path.strip_suffix('/') {
                         format!("{}{}", path, nested_path).into()
                     } else {
                         format!("{}{}", path, nested_path).into()
-                    };
+                    }
+                };
+
+                let mut config = take_config(config);
+
+                for (name, nested_path) in config.names {
+                    self.set_route_name(name, full_path(&nested_path).into());
+                }
+
+                Arc::make_mut(&mut self.config).bypass_route_layers.extend(
+                    config
+                        .bypass_route_layers
+                        .iter()
+                        .map(|nested_path| full_path(nested_path).into()),
+                );
+
+                for (id, nested_path) in &node.route_id_to_path {
+                    let route = routes.remove(id).unwrap();
+                    let priority = node.priority(id);
+                    let full_path = full_path(nested_path);
+                    let meta = config.meta.remove(id);
+                    let timeout = config.timeouts.remove(id);
                     self = match route {
-                        Endpoint::MethodRouter(method_router) => self.route(
+                        Endpoint::MethodRouter(method_router) => self.route_with_priority(
                             &full_path,
                             method_router.layer(layer_fn(|s| StripPrefix::new(s, prefix))),
+                            priority,
+                        ),
+                        Endpoint::Route(route) => self.route_with_priority(
+                            &full_path,
+                            StripPrefix::new(route, prefix),
+                            priority,
+                        ),
+                        Endpoint::Predicated(predicated) => self.route_predicated_with_priority(
+                            &full_path,
+                            predicated.map(|route| Route::new(StripPrefix::new(route, prefix))),
+                            priority,
                         ),
-                        Endpoint::Route(route) => {
-                            self.route(&full_path, StripPrefix::new(route, prefix))
-                        }
                     };
+                    if let Some(meta) = meta {
+                        self.add_route_meta(&full_path, meta);
+                    }
+                    if let Some(timeout) = timeout {
+                        self.add_route_timeout(&full_path, timeout);
+                    }
                 }
 
                 debug_assert!(routes.is_empty());
+
+                self.add_unreachable(
+                    node.unreachable
+                        .iter()
+                        .map(|nested_path| full_path(nested_path).into()),
+                );
+
+                if !prefix_fallbacks.is_empty() && path.contains(':') {
+                    panic!(
+                        "Cannot nest `Router`s that have prefix fallbacks at paths with captures"
+                    );
+                }
+
+                for (nested_prefix, fallback) in
+                    config.fallback_prefixes.iter().zip(prefix_fallbacks)
+                {
+                    let full_prefix = if &*nested_prefix.prefix == "/" {
+                        path.to_owned()
+                    } else {
+                        format!("{}{}", path.trim_end_matches('/'), nested_prefix.prefix)
+                    };
+                    self = self.set_prefix_fallback(
+                        &full_prefix,
+                        Route::new(StripPrefix::new(fallback, prefix)),
+                    );
+                }
             }
             // otherwise we add a wildcard route to the service
             Err(svc) => {
@@ -271,18 +909,46 @@ where
             routes,
             node,
             fallback,
+            method_not_allowed,
+            prefix_fallbacks,
             nested_at_root,
+            pre_routing_layered,
+            missing_route_hook,
+            // the policy of `self` applies to all routes after merging
+            trailing_slash: _,
+            normalize_path: _,
+            config,
+            // the versions of `self` apply to all routes after merging
+            http_versions: _,
+            // the other router's hook has already seen its routes
+            route_registered_hook: _,
         } = other.into();
 
+        if pre_routing_layered {
+            panic!("Cannot merge a `Router` that has a pre-routing layer into another `Router`");
+        }
+
+        let mut config = take_config(config);
+
         for (id, route) in routes {
             let path = node
                 .route_id_to_path
                 .get(&id)
                 .expect("no path for route id. This is a bug in axum. Please file an issue");
+            let priority = node.priority(&id);
             self = match route {
-                Endpoint::MethodRouter(route) => self.route(path, route),
-                Endpoint::Route(route) => self.route(path, route),
+                Endpoint::MethodRouter(route) => self.route_with_priority(path, route, priority),
+                Endpoint::Route(route) => self.route_with_priority(path, route, priority),
+                Endpoint::Predicated(predicated) => {
+                    self.route_predicated_with_priority(path, predicated, priority)
+                }
             };
+            if let Some(meta) = config.meta.remove(&id) {
+                self.add_route_meta(path, meta);
+            }
+            if let Some(timeout) = config.timeouts.remove(&id) {
+                self.add_route_timeout(path, timeout);
+            }
         }
 
         self.fallback = match (self.fallback, fallback) {
@@ -294,11 +960,198 @@ where
             }
         };
 
+        for (FallbackPrefix { prefix, .. }, fallback) in
+            config.fallback_prefixes.into_iter().zip(prefix_fallbacks)
+        {
+            if self
+                .config
+                .fallback_prefixes
+                .iter()
+                .any(|existing| existing.prefix == prefix)
+            {
+                panic!(
+                    "Cannot merge two `Router`s that both have a fallback for the prefix `{}`",
+                    prefix
+                );
+            }
+            let prefix = FallbackPrefix::new(prefix, &self.node);
+            Arc::make_mut(&mut self.config)
+                .fallback_prefixes
+                .push(prefix);
+            self.prefix_fallbacks.push(fallback);
+        }
+
+        self = match (self.method_not_allowed.is_some(), method_not_allowed) {
+            (true, Some(_)) => {
+                panic!("Cannot merge two `Router`s that both have a method not allowed handler")
+            }
+            (false, Some(method_not_allowed)) => self.set_method_not_allowed(method_not_allowed),
+            (_, None) => self,
+        };
+
+        self.missing_route_hook = match (self.missing_route_hook, missing_route_hook) {
+            (Some(_), Some(_)) => {
+                panic!("Cannot merge two `Router`s that both have a missing route hook")
+            }
+            (pick @ Some(_), None) | (None, pick) => pick,
+        };
+
+        for (name, path) in config.names {
+            self.set_route_name(name, path);
+        }
+
+        Arc::make_mut(&mut self.config)
+            .bypass_route_layers
+            .extend(config.bypass_route_layers);
+
+        self.add_unreachable(node.unreachable.iter().cloned());
+
         self.nested_at_root = self.nested_at_root || nested_at_root;
 
         self
     }
 
+    #[doc = include_str!("../docs/routing/prefix_paths.md")]
+    pub fn prefix_paths(self, prefix: &str) -> Self {
+        self.add_path_prefixes(&[prefix])
+    }
+
+    #[doc = include_str!("../docs/routing/alias_prefix.md")]
+    pub fn alias_prefix(self, prefixes: &[&str]) -> Self {
+        if prefixes.is_empty() {
+            panic!("`Router::alias_prefix` requires at least one prefix");
+        }
+
+        self.add_path_prefixes(prefixes)
+    }
+
+    /// Add each of `prefixes` to the path of every route, registering a copy
+    /// of the route for each prefix.
+    ///
+    /// Named routes use the first prefix.
+    fn add_path_prefixes(self, prefixes: &[&str]) -> Self {
+        self.assert_not_pre_routing_layered();
+
+        for prefix in prefixes {
+            if !prefix.starts_with('/') {
+                panic!("Path prefixes must start with a `/`");
+            }
+
+            if prefix.contains('*') {
+                panic!("Invalid path prefix: prefixes cannot contain wildcards (*)");
+            }
+        }
+
+        let prefixes = prefixes
+            .iter()
+            .map(|prefix| prefix.trim_end_matches('/'))
+            .collect::<Vec<_>>();
+        let prefixed = |prefix: &str, path: &str| -> String {
+            if path == "/" && !prefix.is_empty() {
+                prefix.to_owned()
+            } else {
+                format!("{}{}", prefix, path)
+            }
+        };
+
+        let Router {
+            routes,
+            node,
+            fallback,
+            method_not_allowed,
+            prefix_fallbacks,
+            nested_at_root: _,
+            pre_routing_layered: _,
+            missing_route_hook,
+            trailing_slash,
+            normalize_path,
+            config,
+            http_versions,
+            route_registered_hook,
+        } = self;
+
+        // the routes already use the method not allowed handler so it is set
+        // directly rather than through `method_not_allowed_handler`
+        let mut router = Router {
+            fallback,
+            method_not_allowed,
+            missing_route_hook,
+            trailing_slash,
+            normalize_path,
+            http_versions,
+            config: Arc::new(RouteConfig {
+                names: config
+                    .names
+                    .iter()
+                    .map(|(name, path)| (Arc::clone(name), prefixed(prefixes[0], path).into()))
+                    .collect(),
+                bypass_route_layers: config
+                    .bypass_route_layers
+                    .iter()
+                    .flat_map(|path| {
+                        prefixes
+                            .iter()
+                            .map(move |prefix| prefixed(prefix, path).into())
+                    })
+                    .collect(),
+                // added with the routes below
+                timeouts: HashMap::new(),
+                meta: HashMap::new(),
+                // added with `set_prefix_fallback` below
+                fallback_prefixes: Vec::new(),
+            }),
+            ..Router::new()
+        }
+        .case_insensitive(node.case_insensitive);
+
+        for (id, path) in &node.route_id_to_path {
+            let route = routes.get(id).unwrap();
+            let priority = node.priority(id);
+            for prefix in &prefixes {
+                let path = prefixed(prefix, path);
+                router = match route.clone() {
+                    Endpoint::MethodRouter(method_router) => {
+                        router.route_with_priority(&path, method_router, priority)
+                    }
+                    Endpoint::Route(route) => router.route_with_priority(&path, route, priority),
+                    Endpoint::Predicated(predicated) => {
+                        router.route_predicated_with_priority(&path, predicated, priority)
+                    }
+                };
+                if let Some(meta) = config.meta.get(id) {
+                    router.add_route_meta(&path, meta.clone());
+                }
+                if let Some(timeout) = config.timeouts.get(id) {
+                    router.add_route_timeout(&path, *timeout);
+                }
+            }
+        }
+
+        router.add_unreachable(node.unreachable.iter().flat_map(|path| {
+            prefixes
+                .iter()
+                .map(move |prefix| prefixed(prefix, path).into())
+        }));
+
+        if !prefix_fallbacks.is_empty() && prefixes.iter().any(|prefix| prefix.contains(':')) {
+            panic!("Cannot add a prefix with captures to `Router`s that have prefix fallbacks");
+        }
+
+        for (fallback_prefix, fallback) in config.fallback_prefixes.iter().zip(prefix_fallbacks) {
+            for prefix in &prefixes {
+                router = router.set_prefix_fallback(
+                    &prefixed(prefix, &fallback_prefix.prefix),
+                    fallback.clone(),
+                );
+            }
+        }
+
+        // set afterwards so the hook isn't called again for the existing routes
+        router.route_registered_hook = route_registered_hook;
+
+        router
+    }
+
     #[doc = include_str!("../docs/routing/layer.md")]
     pub fn layer<L, NewReqBody>(self, layer: L) -> Router<NewReqBody>
     where
@@ -323,6 +1176,9 @@ where
                         Endpoint::MethodRouter(method_router.layer(&layer))
                     }
                     Endpoint::Route(route) => Endpoint::Route(Route::new(layer.layer(route))),
+                    Endpoint::Predicated(predicated) => {
+                        Endpoint::Predicated(predicated.map(|route| Route::new(layer.layer(route))))
+                    }
                 };
                 (id, route)
             })
@@ -330,22 +1186,152 @@ where
 
         let fallback = self.fallback.map(|svc| Route::new(layer.layer(svc)));
 
+        let method_not_allowed = self
+            .method_not_allowed
+            .map(|svc| Route::new(layer.layer(svc)));
+
+        let prefix_fallbacks = self
+            .prefix_fallbacks
+            .into_iter()
+            .map(|svc| Route::new(layer.layer(svc)))
+            .collect();
+
         Router {
             routes,
             node: self.node,
             fallback,
+            method_not_allowed,
+            prefix_fallbacks,
             nested_at_root: self.nested_at_root,
+            pre_routing_layered: self.pre_routing_layered,
+            missing_route_hook: self.missing_route_hook,
+            trailing_slash: self.trailing_slash,
+            normalize_path: self.normalize_path,
+            config: self.config,
+            http_versions: self.http_versions,
+            // the hook takes `MethodRouter<B>`s so it can't be kept if the layer
+            // changes the request body type
+            route_registered_hook: None,
         }
     }
 
-    #[doc = include_str!("../docs/routing/route_layer.md")]
-    pub fn route_layer<L>(self, layer: L) -> Self
+    /// Catch panics in handlers and services and convert them into
+    /// `500 Internal Server Error` responses.
+    ///
+    /// Without this a panic while handling a request unwinds into hyper,
+    /// which closes the connection without sending a response. The panic
+    /// message is logged with [`tracing`] at the error level. The backtrace is
+    /// printed by the panic hook as usual, if enabled with `RUST_BACKTRACE`.
+    ///
+    /// This works like [`Router::layer`] with
+    /// [`tower_http::catch_panic::CatchPanicLayer`] and so only applies to
+    /// routes and fallbacks added before calling it. Use
+    /// [`Router::layer`] with [`CatchPanicLayer::custom`] to customize the
+    /// response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum::{Router, routing::get};
+    ///
+    /// async fn handler() {
+    ///     panic!("oh no")
+    /// }
+    ///
+    /// let app: Router = Router::new()
+    ///     .route("/", get(handler))
+    ///     // `GET /` responds with `500 Internal Server Error`
+    ///     .catch_panic();
+    /// # async {
+    /// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+    /// # };
+    /// ```
+    ///
+    /// [`tracing`]: https://crates.io/crates/tracing
+    /// [`CatchPanicLayer::custom`]: tower_http::catch_panic::CatchPanicLayer::custom
+    #[cfg(feature = "catch-panic")]
+    pub fn catch_panic(self) -> Self {
+        self.layer(CatchPanicLayer::new())
+    }
+
+    #[doc = include_str!("../docs/routing/strip_disallowed_bodies.md")]
+    pub fn strip_disallowed_bodies(self) -> Self {
+        self.layer(layer_fn(StripDisallowedBodies::new))
+    }
+
+    #[doc = include_str!("../docs/routing/map_html_body.md")]
+    pub fn map_html_body<F>(self, transform: F) -> Self
     where
-        L: Layer<Route<B>>,
-        L::Service: Service<Request<B>> + Clone + Send + 'static,
-        <L::Service as Service<Request<B>>>::Response: IntoResponse + 'static,
-        <L::Service as Service<Request<B>>>::Error: Into<Infallible> + 'static,
-        <L::Service as Service<Request<B>>>::Future: Send + 'static,
+        F: Fn(String) -> String + Send + Sync + 'static,
+    {
+        let transform: map_html_body::HtmlTransform = Arc::new(transform);
+        self.layer(layer_fn(move |inner| {
+            MapHtmlBody::new(inner, Arc::clone(&transform))
+        }))
+    }
+
+    #[doc = include_str!("../docs/routing/tap.md")]
+    pub fn tap<Req, Res>(self, on_request: Req, on_response: Res) -> Self
+    where
+        Req: Fn(&Request<B>) + Send + Sync + 'static,
+        Res: Fn(&Response) + Send + Sync + 'static,
+    {
+        let on_request = Arc::new(on_request);
+        let on_response = Arc::new(on_response);
+        self.layer(layer_fn(move |inner| {
+            Tap::new(inner, Arc::clone(&on_request), Arc::clone(&on_response))
+        }))
+    }
+
+    #[doc = include_str!("../docs/routing/rate_limit.md")]
+    pub fn rate_limit(self, config: RateLimitConfig) -> Self
+    where
+        B: Default,
+    {
+        self.layer(from_extractor::<RateLimited>())
+            .layer(Extension(config))
+    }
+
+    #[cfg(feature = "compression")]
+    #[doc = include_str!("../docs/routing/auto_compress.md")]
+    pub fn auto_compress(self, config: CompressionConfig) -> Self {
+        self.layer(config.into_layer())
+    }
+
+    /// Register a function that converts [`RouterError<E>`] responses into
+    /// the final response.
+    ///
+    /// This makes it possible to render errors in one place while handlers
+    /// use `?` to propagate them. The error handler works like
+    /// [`Router::layer`] and so only applies to routes and fallbacks added
+    /// before calling it. Register one handler per error type.
+    ///
+    /// See [`RouterError`] for an example.
+    ///
+    /// [`RouterError<E>`]: crate::response::RouterError
+    /// [`RouterError`]: crate::response::RouterError
+    pub fn error_handler<E, F, R>(self, f: F) -> Self
+    where
+        F: Fn(E) -> R + Clone + Send + 'static,
+        R: IntoResponse,
+        E: Send + Sync + 'static,
+    {
+        self.layer(MapResponseLayer::new(move |mut res: Response| {
+            match res.extensions_mut().remove::<PendingError<E>>() {
+                Some(PendingError(err)) => f(err).into_response(),
+                None => res,
+            }
+        }))
+    }
+
+    #[doc = include_str!("../docs/routing/route_layer.md")]
+    pub fn route_layer<L>(self, layer: L) -> Self
+    where
+        L: Layer<Route<B>>,
+        L::Service: Service<Request<B>> + Clone + Send + 'static,
+        <L::Service as Service<Request<B>>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request<B>>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Request<B>>>::Future: Send + 'static,
     {
         let layer = ServiceBuilder::new()
             .map_err(Into::into)
@@ -353,15 +1339,28 @@ where
             .layer(layer)
             .into_inner();
 
+        let node = &self.node;
+        let bypass_route_layers = &self.config.bypass_route_layers;
         let routes = self
             .routes
             .into_iter()
             .map(|(id, route)| {
+                let bypass = node
+                    .route_id_to_path
+                    .get(&id)
+                    .map_or(false, |path| bypass_route_layers.contains(path));
+                if bypass {
+                    return (id, route);
+                }
+
                 let route = match route {
                     Endpoint::MethodRouter(method_router) => {
                         Endpoint::MethodRouter(method_router.layer(&layer))
                     }
                     Endpoint::Route(route) => Endpoint::Route(Route::new(layer.layer(route))),
+                    Endpoint::Predicated(predicated) => {
+                        Endpoint::Predicated(predicated.map(|route| Route::new(layer.layer(route))))
+                    }
                 };
                 (id, route)
             })
@@ -371,10 +1370,27 @@ where
             routes,
             node: self.node,
             fallback: self.fallback,
+            method_not_allowed: self.method_not_allowed,
+            prefix_fallbacks: self.prefix_fallbacks,
             nested_at_root: self.nested_at_root,
+            pre_routing_layered: self.pre_routing_layered,
+            missing_route_hook: self.missing_route_hook,
+            trailing_slash: self.trailing_slash,
+            normalize_path: self.normalize_path,
+            config: self.config,
+            http_versions: self.http_versions,
+            route_registered_hook: self.route_registered_hook,
         }
     }
 
+    #[doc = include_str!("../docs/routing/bypass_outer_route_layers.md")]
+    pub fn bypass_outer_route_layers(mut self) -> Self {
+        Arc::make_mut(&mut self.config)
+            .bypass_route_layers
+            .extend(self.node.route_id_to_path.values().cloned());
+        self
+    }
+
     #[doc = include_str!("../docs/routing/fallback.md")]
     pub fn fallback<T>(mut self, svc: T) -> Self
     where
@@ -382,7 +1398,315 @@ where
         T::Response: IntoResponse,
         T::Future: Send + 'static,
     {
-        self.fallback = Fallback::Custom(Route::new(svc));
+        self.assert_not_pre_routing_layered();
+        self.fallback = Fallback::Custom(into_route(svc));
+        self
+    }
+
+    #[doc = include_str!("../docs/routing/fallback_layer.md")]
+    pub fn fallback_layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Route<B>>,
+        L::Service: Service<Request<B>> + Clone + Send + 'static,
+        <L::Service as Service<Request<B>>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request<B>>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Request<B>>>::Future: Send + 'static,
+    {
+        let layer = ServiceBuilder::new()
+            .map_err(Into::into)
+            .layer(MapResponseLayer::new(IntoResponse::into_response))
+            .layer(layer)
+            .into_inner();
+
+        self.fallback = self.fallback.map(|svc| Route::new(layer.layer(svc)));
+
+        self.prefix_fallbacks = self
+            .prefix_fallbacks
+            .into_iter()
+            .map(|svc| Route::new(layer.layer(svc)))
+            .collect();
+
+        self
+    }
+
+    #[doc = include_str!("../docs/routing/pre_routing_layer.md")]
+    pub fn pre_routing_layer<L, NewReqBody>(self, layer: L) -> Router<NewReqBody>
+    where
+        L: Layer<Self>,
+        L::Service: Service<Request<NewReqBody>> + Clone + Send + 'static,
+        <L::Service as Service<Request<NewReqBody>>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request<NewReqBody>>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Request<NewReqBody>>>::Future: Send + 'static,
+        NewReqBody: HttpBody + Send + 'static,
+    {
+        let svc = ServiceBuilder::new()
+            .map_err(Into::into)
+            .layer(MapResponseLayer::new(IntoResponse::into_response))
+            .service(layer.layer(self));
+
+        // the new router has no routes so every request is sent to the layered
+        // router, which then does the actual routing
+        let mut router = Router::new().fallback(svc);
+        router.pre_routing_layered = true;
+        router
+    }
+
+    fn assert_not_pre_routing_layered(&self) {
+        if self.pre_routing_layered {
+            panic!(
+                "Cannot add routes or fallbacks to a `Router` after calling `pre_routing_layer`. \
+                 Call `pre_routing_layer` last"
+            );
+        }
+    }
+
+    #[doc = include_str!("../docs/routing/case_insensitive.md")]
+    pub fn case_insensitive(mut self, enabled: bool) -> Self {
+        self.assert_not_pre_routing_layered();
+
+        if self.node.case_insensitive == enabled {
+            return self;
+        }
+
+        let mut node = Node {
+            case_insensitive: enabled,
+            unreachable: self.node.unreachable.clone(),
+            ..Node::default()
+        };
+
+        let mut existing = self.node.route_id_to_path.iter().collect::<Vec<_>>();
+        existing.sort_by_key(|(id, _)| **id);
+
+        for (id, path) in existing {
+            if let Err(err) = node.insert(&**path, *id, self.node.priority(id)) {
+                self.panic_on_matchit_error(err);
+            }
+        }
+
+        self.node = Arc::new(node);
+
+        if !self.config.fallback_prefixes.is_empty() {
+            let config = Arc::make_mut(&mut self.config);
+            for fallback_prefix in &mut config.fallback_prefixes {
+                *fallback_prefix =
+                    FallbackPrefix::new(Arc::clone(&fallback_prefix.prefix), &self.node);
+            }
+        }
+
+        self
+    }
+
+    #[doc = include_str!("../docs/routing/trailing_slash.md")]
+    pub fn trailing_slash(mut self, policy: TrailingSlashPolicy) -> Self {
+        self.assert_not_pre_routing_layered();
+        self.trailing_slash = policy;
+        self
+    }
+
+    #[doc = include_str!("../docs/routing/normalize_path.md")]
+    pub fn normalize_path(mut self) -> Self {
+        self.assert_not_pre_routing_layered();
+        self.normalize_path = true;
+        self
+    }
+
+    #[doc = include_str!("../docs/routing/require_http_versions.md")]
+    pub fn require_http_versions(mut self, versions: &[Version]) -> Self {
+        self.assert_not_pre_routing_layered();
+        if versions.is_empty() {
+            panic!("`Router::require_http_versions` requires at least one version");
+        }
+        self.http_versions = Some(versions.into());
+        self
+    }
+
+    #[doc = include_str!("../docs/routing/default_timeout.md")]
+    pub fn default_timeout(mut self, duration: Duration) -> Self {
+        self.assert_not_pre_routing_layered();
+
+        let ids = self
+            .routes
+            .keys()
+            .filter(|id| !self.config.timeouts.contains_key(id))
+            .copied()
+            .collect::<Vec<_>>();
+        for id in ids {
+            self.wrap_with_timeout(id, Some(duration));
+        }
+
+        self
+    }
+
+    /// Set the timeout of the route at `path`, instead of the default
+    /// timeout.
+    ///
+    /// `path` must be the same as the path given to [`Router::route`]. See
+    /// [`Router::default_timeout`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// - If there is no route at `path`.
+    /// - If the route already has a timeout, for example because this is
+    ///   called after [`Router::default_timeout`].
+    /// - If called after [`Router::pre_routing_layer`].
+    pub fn route_timeout(self, path: &str, duration: Duration) -> Self {
+        self.set_timeout(path, Some(duration))
+    }
+
+    /// Don't apply the default timeout to the route at `path`.
+    ///
+    /// `path` must be the same as the path given to [`Router::route`]. See
+    /// [`Router::default_timeout`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// - If there is no route at `path`.
+    /// - If the route already has a timeout, for example because this is
+    ///   called after [`Router::default_timeout`].
+    /// - If called after [`Router::pre_routing_layer`].
+    pub fn without_timeout(self, path: &str) -> Self {
+        self.set_timeout(path, None)
+    }
+
+    fn set_timeout(mut self, path: &str, timeout: Option<Duration>) -> Self {
+        self.assert_not_pre_routing_layered();
+
+        let id = match self.node.path_to_route_id.get(path) {
+            Some(id) => *id,
+            None => panic!(
+                "Cannot set the timeout of `{}` since there is no route at that path",
+                path
+            ),
+        };
+        if self.config.timeouts.contains_key(&id) {
+            panic!(
+                "The route at `{}` already has a timeout. Note that `Router::route_timeout` \
+                 and `Router::without_timeout` must be called before `Router::default_timeout`",
+                path
+            );
+        }
+
+        self.wrap_with_timeout(id, timeout);
+        self
+    }
+
+    /// Wrap the route with the given id once with its timeout, so requests to
+    /// it don't need to look the timeout up.
+    fn wrap_with_timeout(&mut self, id: RouteId, timeout: Option<Duration>) {
+        if let Some(duration) = timeout {
+            let layer = RouteTimeoutLayer::new(duration);
+            let endpoint = self
+                .routes
+                .remove(&id)
+                .expect("no route for id. This is a bug in axum. Please file an issue");
+            let endpoint = match endpoint {
+                Endpoint::MethodRouter(method_router) => {
+                    Endpoint::MethodRouter(method_router.layer(layer))
+                }
+                Endpoint::Route(route) => Endpoint::Route(Route::new(layer.layer(route))),
+                Endpoint::Predicated(predicated) => {
+                    Endpoint::Predicated(predicated.map(|route| Route::new(layer.layer(route))))
+                }
+            };
+            self.routes.insert(id, endpoint);
+        }
+
+        Arc::make_mut(&mut self.config).timeouts.insert(id, timeout);
+    }
+
+    #[doc = include_str!("../docs/routing/on_route_registered.md")]
+    pub fn on_route_registered<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&str, &MethodRouter<B>) + Send + 'static,
+    {
+        self.route_registered_hook = Some(Arc::new(Mutex::new(f)));
+        self
+    }
+
+    #[doc = include_str!("../docs/routing/on_missing_route.md")]
+    pub fn on_missing_route<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&http::request::Parts) + Send + Sync + 'static,
+    {
+        self.assert_not_pre_routing_layered();
+        self.missing_route_hook = Some(Arc::new(f));
+        self
+    }
+
+    #[doc = include_str!("../docs/routing/fallback_for_prefix.md")]
+    pub fn fallback_for_prefix<T>(self, prefix: &str, svc: T) -> Self
+    where
+        T: Service<Request<B>, Error = Infallible> + Clone + Send + 'static,
+        T::Response: IntoResponse,
+        T::Future: Send + 'static,
+    {
+        if !prefix.starts_with('/') {
+            panic!("Fallback prefixes must start with a `/`");
+        }
+
+        if prefix.contains(':') || prefix.contains('*') {
+            panic!(
+                "Invalid fallback prefix: prefixes cannot contain captures (:) or wildcards (*)"
+            );
+        }
+
+        self.set_prefix_fallback(prefix, into_route(svc))
+    }
+
+    fn set_prefix_fallback(mut self, prefix: &str, svc: Route<B>) -> Self {
+        self.assert_not_pre_routing_layered();
+
+        let prefix = if prefix == "/" {
+            prefix
+        } else {
+            prefix.trim_end_matches('/')
+        };
+
+        if let Some(idx) = self
+            .config
+            .fallback_prefixes
+            .iter()
+            .position(|p| &*p.prefix == prefix)
+        {
+            self.prefix_fallbacks[idx] = svc;
+        } else {
+            let prefix = FallbackPrefix::new(prefix.into(), &self.node);
+            Arc::make_mut(&mut self.config)
+                .fallback_prefixes
+                .push(prefix);
+            self.prefix_fallbacks.push(svc);
+        }
+
+        self
+    }
+
+    #[doc = include_str!("../docs/routing/method_not_allowed_handler.md")]
+    pub fn method_not_allowed_handler<T>(self, svc: T) -> Self
+    where
+        T: Service<Request<B>, Error = Infallible> + Clone + Send + 'static,
+        T::Response: IntoResponse,
+        T::Future: Send + 'static,
+    {
+        self.set_method_not_allowed(into_route(svc))
+    }
+
+    fn set_method_not_allowed(mut self, svc: Route<B>) -> Self {
+        self.assert_not_pre_routing_layered();
+
+        self.routes = self
+            .routes
+            .into_iter()
+            .map(|(id, route)| {
+                let route = match route {
+                    Endpoint::MethodRouter(method_router) => {
+                        Endpoint::MethodRouter(method_router.default_fallback(svc.clone()))
+                    }
+                    endpoint @ (Endpoint::Route(_) | Endpoint::Predicated(_)) => endpoint,
+                };
+                (id, route)
+            })
+            .collect();
+        self.method_not_allowed = Some(svc);
         self
     }
 
@@ -413,19 +1737,285 @@ where
         IntoMakeService::new(self)
     }
 
+    /// Call the router once with the given request.
+    ///
+    /// This is a convenience for tests that avoids importing
+    /// [`tower::ServiceExt`] for [`oneshot`]. The error type is [`Infallible`]
+    /// so unwrapping the result never panics.
+    ///
+    /// [`oneshot`]: tower::ServiceExt::oneshot
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum::{
+    ///     body::Body,
+    ///     http::{Request, StatusCode},
+    ///     routing::get,
+    ///     Router,
+    /// };
+    ///
+    /// # async {
+    /// let app = Router::new().route("/", get(|| async { "Hello, World!" }));
+    ///
+    /// let response = app
+    ///     .call_once(Request::builder().uri("/").body(Body::empty()).unwrap())
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// assert_eq!(response.status(), StatusCode::OK);
+    /// # };
+    /// ```
+    pub fn call_once(mut self, req: Request<B>) -> RouteFuture<B, Infallible> {
+        // `Router` is always ready so there is no need to call `poll_ready`
+        self.call(req)
+    }
+
     #[doc = include_str!("../docs/routing/into_make_service_with_connect_info.md")]
     pub fn into_make_service_with_connect_info<C>(self) -> IntoMakeServiceWithConnectInfo<Self, C> {
         IntoMakeServiceWithConnectInfo::new(self)
     }
 
+    /// Get a snapshot of the routes in this router and the methods they accept.
+    ///
+    /// The table is sorted by path so it's stable across runs and can be
+    /// compared against a previously recorded snapshot, for example to catch
+    /// unintended changes to an API's surface in tests.
+    ///
+    /// Routes to services that accept any method, such as those added with
+    /// [`any`] or [`Router::nest`], are listed with an empty set of methods.
+    /// Services nested with [`Router::nest`] are rendered with a trailing
+    /// `/*`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum::{
+    ///     routing::{get, get_service},
+    ///     http::{Method, StatusCode},
+    ///     Router,
+    /// };
+    /// use std::io;
+    /// use tower_http::services::ServeDir;
+    ///
+    /// let serve_dir_service = get_service(ServeDir::new("public"))
+    ///     .handle_error(|_: io::Error| async { StatusCode::INTERNAL_SERVER_ERROR });
+    ///
+    /// let app: Router = Router::new()
+    ///     .route("/users", get(|| async {}).post(|| async {}))
+    ///     .route("/users/:id", get(|| async {}))
+    ///     .nest("/assets", serve_dir_service);
+    ///
+    /// assert_eq!(
+    ///     app.route_table(),
+    ///     vec![
+    ///         ("/assets/*".to_owned(), vec![]),
+    ///         ("/users".to_owned(), vec![Method::GET, Method::POST]),
+    ///         ("/users/:id".to_owned(), vec![Method::GET]),
+    ///     ],
+    /// );
+    /// ```
+    pub fn route_table(&self) -> Vec<(String, Vec<Method>)> {
+        let mut table = Vec::with_capacity(self.routes.len());
+
+        for (id, endpoint) in &self.routes {
+            let path = self
+                .node
+                .route_id_to_path
+                .get(id)
+                .expect("no path for route id. This is a bug in axum. Please file an issue");
+            // `/foo/*__private__axum_nest_tail_param` is rendered as `/foo/*`
+            let path = path.strip_suffix(NEST_TAIL_PARAM).unwrap_or(path);
+
+            let methods = match endpoint {
+                Endpoint::MethodRouter(method_router) => method_router.methods(),
+                Endpoint::Route(_) | Endpoint::Predicated(_) => Vec::new(),
+            };
+
+            table.push((path.to_owned(), methods));
+        }
+
+        table.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        table
+    }
+
+    /// Create a [`MethodRouter`] that responds to `GET` requests with the
+    /// [route table](Router::route_table) of this router as JSON.
+    ///
+    /// The table is captured when this is called, so routes added afterwards
+    /// aren't listed. This is the building block of
+    /// [`Router::expose_route_listing`] and can be used to add middleware,
+    /// such as authorization, to the listing only. See that method for more
+    /// details.
+    #[cfg(feature = "json")]
+    pub fn route_listing(&self) -> MethodRouter<B> {
+        let listing = self
+            .route_table()
+            .into_iter()
+            .map(|(path, methods)| {
+                let methods = methods
+                    .iter()
+                    .map(|method| method.as_str())
+                    .collect::<Vec<_>>();
+                serde_json::json!({ "path": path, "methods": methods })
+            })
+            .collect::<Vec<_>>();
+        // the listing doesn't change so it's only serialized once
+        let listing =
+            Bytes::from(serde_json::to_vec(&listing).expect("route listing is always valid JSON"));
+
+        get(move || async move {
+            (
+                [(
+                    http::header::CONTENT_TYPE,
+                    http::HeaderValue::from_static(mime::APPLICATION_JSON.as_ref()),
+                )],
+                listing,
+            )
+        })
+    }
+
+    #[cfg(feature = "json")]
+    #[doc = include_str!("../docs/routing/expose_route_listing.md")]
+    pub fn expose_route_listing(self, path: &str) -> Self {
+        let listing = self.route_listing();
+        self.route(path, listing)
+    }
+
+    /// Get the paths in `other` that would conflict with the routes in this
+    /// router if the two were merged with [`Router::merge`].
+    ///
+    /// A path conflicts if
+    ///
+    /// - both routers have a route at the same path and the routes cannot be
+    ///   combined, either because one of them is a service rather than a
+    ///   [`MethodRouter`] or because both handle the same method, or
+    /// - the path overlaps with a different path in this router in a way the
+    ///   router cannot tell apart, such as `/users/:id` and `/users/:name`.
+    ///
+    /// Conflicts are reported regardless of route priorities, even though
+    /// [`Router::route_with_priority`] can resolve some of them when merging.
+    /// This only inspects the routes, neither router is modified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use axum::{Router, routing::{get, post}};
+    ///
+    /// let app: Router = Router::new()
+    ///     .route("/users", get(|| async {}))
+    ///     .route("/users/:id", get(|| async {}));
+    ///
+    /// let plugin = Router::new()
+    ///     .route("/users", post(|| async {}))
+    ///     .route("/users/:name", get(|| async {}));
+    ///
+    /// // `POST /users` can be combined with `GET /users`
+    /// assert_eq!(app.conflicts_with(&plugin), vec!["/users/:name"]);
+    /// ```
+    pub fn conflicts_with(&self, other: &Router<B>) -> Vec<String> {
+        let mut inner = self.node.inner.clone();
+        let mut conflicts = Vec::new();
+
+        let mut other_routes = other.node.route_id_to_path.iter().collect::<Vec<_>>();
+        other_routes.sort_by_key(|(_, path)| *path);
+
+        for (other_id, path) in other_routes {
+            let conflicts_with_existing = match self.node.path_to_route_id.get(path) {
+                Some(id) => match (&self.routes[id], &other.routes[other_id]) {
+                    (Endpoint::MethodRouter(a), Endpoint::MethodRouter(b)) => a.overlaps(b),
+                    _ => true,
+                },
+                None => inner
+                    .insert(&*self.node.matchit_path(path), *other_id)
+                    .is_err(),
+            };
+
+            if conflicts_with_existing {
+                // `/foo/*__private__axum_nest_tail_param` is rendered as `/foo/*`
+                let path = path.strip_suffix(NEST_TAIL_PARAM).unwrap_or(path);
+                conflicts.push(path.to_owned());
+            }
+        }
+
+        conflicts
+    }
+
     #[inline]
-    fn call_route(
-        &self,
-        match_: matchit::Match<&RouteId>,
-        mut req: Request<B>,
-    ) -> RouteFuture<B, Infallible> {
+    /// Find the service a request is routed to.
+    ///
+    /// The request is split into its parts so the path can be borrowed while
+    /// the extensions are set, without copying it.
+    fn route_target(&self, parts: &mut http::request::Parts) -> Target<'_, B> {
+        let path = parts.uri.path();
+        let lookup_path = self.node.lookup_path(path);
+
+        let toggled;
+        let matched = match self.node.at(&lookup_path) {
+            Ok(match_) => Some((match_, &*lookup_path)),
+            Err(
+                MatchError::NotFound
+                | MatchError::ExtraTrailingSlash
+                | MatchError::MissingTrailingSlash,
+            ) => match trailing_slash::toggle_trailing_slash(&lookup_path) {
+                Some(toggled_lookup) if self.trailing_slash != TrailingSlashPolicy::Strict => {
+                    toggled = toggled_lookup;
+                    match self.node.at(&toggled) {
+                        Ok(match_) if self.trailing_slash == TrailingSlashPolicy::Ignore => {
+                            Some((match_, &*toggled))
+                        }
+                        Ok(_) => {
+                            let toggled = trailing_slash::toggle_trailing_slash(path)
+                                .expect("path isn't `/` since the lookup path isn't");
+                            let location = match parts.uri.query() {
+                                Some(query) => format!("{}?{}", toggled, query),
+                                None => toggled,
+                            };
+                            return Target::Redirect(Redirect::permanent(&location));
+                        }
+                        Err(_) => None,
+                    }
+                }
+                _ => None,
+            },
+        };
+
+        let (match_, lookup_path) = match matched {
+            Some(matched) => matched,
+            None => {
+                if let Some(hook) = &self.missing_route_hook {
+                    hook(parts);
+                }
+                return self.fallback_target(&lookup_path, &mut parts.extensions);
+            }
+        };
+
         let id = *match_.value;
+        self.set_route_extensions(id, &match_, lookup_path, path, &mut parts.extensions);
 
+        match self
+            .routes
+            .get(&id)
+            .expect("no route for id. This is a bug in axum. Please file an issue")
+        {
+            Endpoint::MethodRouter(inner) => Target::MethodRouter(inner),
+            Endpoint::Route(inner) => Target::Route(inner),
+            Endpoint::Predicated(inner) => match inner.select(parts) {
+                Some(route) => Target::Route(route),
+                None => self.fallback_target(lookup_path, &mut parts.extensions),
+            },
+        }
+    }
+
+    fn set_route_extensions(
+        &self,
+        id: RouteId,
+        match_: &matchit::Match<&RouteId>,
+        lookup_path: &str,
+        path: &str,
+        extensions: &mut http::Extensions,
+    ) {
         #[cfg(feature = "matched-path")]
         {
             fn set_matched_path(
@@ -458,24 +2048,212 @@ where
                 }
             }
 
-            set_matched_path(id, &self.node.route_id_to_path, req.extensions_mut());
+            set_matched_path(id, &self.node.route_id_to_path, extensions);
+        }
+
+        let route_path = self
+            .node
+            .route_id_to_path
+            .get(&id)
+            .expect("no path for route id. This is a bug in axum. Please file an issue");
+        // `lookup_path` might be lowercased so take the values of the params from
+        // the original path to preserve their case
+        let params = match_
+            .params
+            .iter()
+            .map(|(key, value)| (key, original_slice(value, lookup_path, path)))
+            .collect::<Vec<_>>();
+        url_params::insert_url_params(extensions, &params, route_path);
+        CaseInsensitive::set(extensions, self.node.case_insensitive);
+
+        if let Some(meta) = self.config.meta.get(&id) {
+            for meta in meta {
+                meta.insert(extensions);
+            }
+        }
+    }
+
+    fn fallback_target(
+        &self,
+        lookup_path: &str,
+        extensions: &mut http::Extensions,
+    ) -> Target<'_, B> {
+        let prefix_fallback = self
+            .config
+            .fallback_prefixes
+            .iter()
+            .zip(&self.prefix_fallbacks)
+            .filter(|(prefix, _)| path_has_prefix(lookup_path, &prefix.lookup))
+            .max_by_key(|(prefix, _)| prefix.lookup.len());
+
+        if let Some((_, inner)) = prefix_fallback {
+            CaseInsensitive::set(extensions, self.node.case_insensitive);
+            return Target::Route(inner);
+        }
+
+        match &self.fallback {
+            Fallback::Default(inner) => Target::Route(inner),
+            Fallback::Custom(inner) => Target::Route(inner),
+        }
+    }
+
+    #[doc = include_str!("../docs/routing/debug_tree.md")]
+    pub fn debug_tree(&self) -> String {
+        let mut routes = self
+            .node
+            .route_id_to_path
+            .iter()
+            .map(|(id, path)| {
+                let priority = self.node.route_id_to_priority[id];
+                match path.strip_suffix(NEST_TAIL_PARAM) {
+                    Some(prefix) => (prefix.to_owned(), true, priority),
+                    None => (path.to_string(), false, priority),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // order the routes the way matchit prefers them: segment by segment,
+        // static segments before parameters before wildcards
+        routes.sort_by(|(a, _, _), (b, _, _)| {
+            let key = |path: &str| {
+                path.split('/')
+                    .map(|segment| (SegmentKind::of(segment), segment.to_owned()))
+                    .collect::<Vec<_>>()
+            };
+            key(a).cmp(&key(b))
+        });
+
+        let width = routes
+            .iter()
+            .map(|(path, _, _)| path.len())
+            .max()
+            .unwrap_or(0);
+
+        let mut out = String::new();
+        if self.node.case_insensitive {
+            out.push_str("(case insensitive)\n");
+        }
+        for (path, nested, priority) in routes {
+            let kind = path
+                .split('/')
+                .map(SegmentKind::of)
+                .max()
+                .unwrap_or(SegmentKind::Static);
+            let mut line = format!("{:width$}  {}", path, kind.as_str(), width = width);
+            if nested {
+                line.push_str(" (nested)");
+            }
+            if priority != DEFAULT_PRIORITY {
+                line.push_str(&format!(" (priority {})", priority));
+            }
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out
+    }
+
+    #[doc = include_str!("../docs/routing/match_path.md")]
+    pub fn match_path(&self, method: &Method, path: &str) -> MatchOutcome {
+        self.match_path_with_headers(method, path, &HeaderMap::new())
+    }
+
+    #[doc = include_str!("../docs/routing/match_path_with_headers.md")]
+    pub fn match_path_with_headers(
+        &self,
+        method: &Method,
+        path: &str,
+        headers: &HeaderMap,
+    ) -> MatchOutcome {
+        let lookup_path = self.node.lookup_path(path);
+
+        if let Ok(match_) = self.node.at(&lookup_path) {
+            return self.match_outcome(method, headers, match_, &lookup_path, path);
+        }
+
+        if self.trailing_slash != TrailingSlashPolicy::Strict {
+            if let Some(toggled) = trailing_slash::toggle_trailing_slash(&lookup_path) {
+                if let Ok(match_) = self.node.at(&toggled) {
+                    if self.trailing_slash == TrailingSlashPolicy::Ignore {
+                        return self.match_outcome(method, headers, match_, &toggled, path);
+                    }
+
+                    let location = trailing_slash::toggle_trailing_slash(path)
+                        .expect("path isn't `/` since the lookup path isn't");
+                    return MatchOutcome::Redirect { location };
+                }
+            }
         }
 
-        url_params::insert_url_params(req.extensions_mut(), match_.params);
+        MatchOutcome::Fallback
+    }
+
+    fn match_outcome(
+        &self,
+        method: &Method,
+        headers: &HeaderMap,
+        match_: matchit::Match<&RouteId>,
+        lookup_path: &str,
+        path: &str,
+    ) -> MatchOutcome {
+        let id = *match_.value;
+        let route_path = self
+            .node
+            .route_id_to_path
+            .get(&id)
+            .expect("no path for route id. This is a bug in axum. Please file an issue");
+        // show services nested at `/api` as `/api` rather than with the
+        // internal wildcard
+        let route_path = match route_path.strip_suffix(NEST_TAIL_PARAM_CAPTURE) {
+            Some("") => "/".to_owned(),
+            Some(prefix) => prefix.to_owned(),
+            None => route_path.to_string(),
+        };
 
-        let mut route = self
+        let handled = match self
             .routes
             .get(&id)
             .expect("no route for id. This is a bug in axum. Please file an issue")
-            .clone();
+        {
+            Endpoint::MethodRouter(method_router) => method_router.handles(method),
+            Endpoint::Route(_) => true,
+            Endpoint::Predicated(predicated) => {
+                let mut req = Request::new(());
+                *req.method_mut() = method.clone();
+                *req.headers_mut() = headers.clone();
+                if let Ok(uri) = path.parse() {
+                    *req.uri_mut() = uri;
+                }
+                let (parts, ()) = req.into_parts();
+                if predicated.select(&parts).is_none() {
+                    return MatchOutcome::Fallback;
+                }
+                true
+            }
+        };
 
-        match &mut route {
-            Endpoint::MethodRouter(inner) => inner.call(req),
-            Endpoint::Route(inner) => inner.call(req),
+        if !handled {
+            return MatchOutcome::MethodNotAllowed { path: route_path };
+        }
+
+        let params = match_
+            .params
+            .iter()
+            .filter(|(key, _)| !key.starts_with(NEST_TAIL_PARAM))
+            .map(|(key, value)| {
+                let value = original_slice(value, lookup_path, path);
+                let value = PercentDecodedStr::new(value)
+                    .map_or_else(|| value.to_owned(), |value| value.as_str().to_owned());
+                (key.to_owned(), value)
+            })
+            .collect();
+
+        MatchOutcome::Route {
+            path: route_path,
+            params,
         }
     }
 
-    fn panic_on_matchit_error(&self, err: matchit::InsertError) {
+    fn panic_on_matchit_error(&self, err: matchit::InsertError) -> ! {
         if self.nested_at_root {
             panic!(
                 "Invalid route: {}. Note that `nest(\"/\", _)` conflicts with all routes. Use `Router::fallback` instead",
@@ -487,6 +2265,92 @@ where
     }
 }
 
+impl Router<BoxBody> {
+    /// Convert a `Router<BoxBody>` into a router that accepts requests with
+    /// any body type.
+    ///
+    /// Incoming request bodies are converted into a [`BoxBody`] before being
+    /// passed on to the routes. This is useful for libraries that expose
+    /// routers and don't want to require a specific request body type from
+    /// their users. The library can build a `Router<BoxBody>` and users can
+    /// call `boxed` to adapt it to the body type used by their application.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum::{
+    ///     body::{Body, BoxBody},
+    ///     routing::post,
+    ///     Router,
+    /// };
+    ///
+    /// // in a library
+    /// fn echo_routes() -> Router<BoxBody> {
+    ///     Router::new().route("/echo", post(|body: String| async { body }))
+    /// }
+    ///
+    /// // in an application that uses `hyper::Body`
+    /// let app: Router<Body> = Router::new()
+    ///     .route("/", post(|| async {}))
+    ///     .merge(echo_routes().boxed());
+    /// # async {
+    /// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+    /// # };
+    /// ```
+    pub fn boxed<B>(self) -> Router<B>
+    where
+        B: HttpBody<Data = Bytes> + Send + 'static,
+        B::Error: Into<BoxError>,
+    {
+        self.layer(MapRequestLayer::new(|req: Request<B>| req.map(boxed)))
+    }
+
+    #[doc = include_str!("../docs/routing/body_read_timeout.md")]
+    pub fn body_read_timeout<B>(self, duration: Duration) -> Router<B>
+    where
+        B: HttpBody<Data = Bytes> + Send + 'static,
+        B::Error: Into<BoxError>,
+    {
+        self.layer(layer_fn(move |inner| BodyReadTimeout::new(inner, duration)))
+    }
+
+    #[cfg(feature = "decompression")]
+    #[doc = include_str!("../docs/routing/decompress_requests.md")]
+    pub fn decompress_requests<B>(self) -> Router<B>
+    where
+        B: HttpBody<Data = Bytes> + Send + 'static,
+        B::Error: Into<BoxError>,
+    {
+        self.decompress_requests_with_limit(decompression::DEFAULT_DECOMPRESSED_SIZE_LIMIT)
+    }
+
+    /// Decompress request bodies like [`Router::decompress_requests`] but
+    /// with a custom limit, in bytes, on the size of decompressed bodies.
+    #[cfg(feature = "decompression")]
+    pub fn decompress_requests_with_limit<B>(self, limit: usize) -> Router<B>
+    where
+        B: HttpBody<Data = Bytes> + Send + 'static,
+        B::Error: Into<BoxError>,
+    {
+        self.layer(layer_fn(move |inner| {
+            decompression::Decompression::new(inner, limit)
+        }))
+    }
+}
+
+impl Router<Body> {
+    #[doc = include_str!("../docs/routing/serve.md")]
+    pub async fn serve<F>(self, addr: SocketAddr, shutdown: F) -> Result<(), hyper::Error>
+    where
+        F: Future<Output = ()>,
+    {
+        hyper::Server::try_bind(&addr)?
+            .serve(self.into_make_service())
+            .with_graceful_shutdown(shutdown)
+            .await
+    }
+}
+
 impl<B> Service<Request<B>> for Router<B>
 where
     B: HttpBody + Send + 'static,
@@ -512,19 +2376,115 @@ where
             }
         }
 
-        let path = req.uri().path().to_owned();
+        if self.normalize_path {
+            if let Some(uri) = normalize_path::collapse_slashes(req.uri()) {
+                *req.uri_mut() = uri;
+            }
+        }
 
-        match self.node.at(&path) {
-            Ok(match_) => self.call_route(match_, req),
-            Err(
-                MatchError::NotFound
-                | MatchError::ExtraTrailingSlash
-                | MatchError::MissingTrailingSlash,
-            ) => match &self.fallback {
-                Fallback::Default(inner) => inner.clone().call(req),
-                Fallback::Custom(inner) => inner.clone().call(req),
-            },
+        if let Some(versions) = &self.http_versions {
+            if !versions.contains(&req.version()) {
+                return Route::new(service_fn(|_: Request<B>| {
+                    ready(Ok::<_, Infallible>(
+                        StatusCode::HTTP_VERSION_NOT_SUPPORTED.into_response(),
+                    ))
+                }))
+                .call(req);
+            }
+        }
+
+        let (mut parts, body) = req.into_parts();
+        let target = self.route_target(&mut parts);
+        target.call(Request::from_parts(parts, body))
+    }
+}
+
+/// Take the config of a router that is nested or merged into another out of
+/// its `Arc`, cloning it if it's still shared with a clone of the router.
+fn take_config(config: Arc<RouteConfig>) -> RouteConfig {
+    Arc::try_unwrap(config).unwrap_or_else(|config| (*config).clone())
+}
+
+/// The kind of a path segment, in the order matchit prefers them.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum SegmentKind {
+    Static,
+    Param,
+    Wildcard,
+}
+
+impl SegmentKind {
+    fn of(segment: &str) -> Self {
+        if segment.starts_with('*') {
+            Self::Wildcard
+        } else if segment.starts_with(':') {
+            Self::Param
+        } else {
+            Self::Static
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Static => "static",
+            Self::Param => "param",
+            Self::Wildcard => "wildcard",
+        }
+    }
+}
+
+/// Box `svc` into a [`Route`], unless it already is one.
+fn into_route<B, T>(svc: T) -> Route<B>
+where
+    B: 'static,
+    T: Service<Request<B>, Error = Infallible> + Clone + Send + 'static,
+    T::Response: IntoResponse,
+    T::Future: Send + 'static,
+{
+    match try_downcast::<Route<B>, _>(svc) {
+        Ok(route) => route,
+        Err(svc) => Route::new(svc),
+    }
+}
+
+fn validate_path(path: &str) {
+    if path.is_empty() {
+        panic!("Paths must start with a `/`. Use \"/\" for root routes");
+    } else if !path.starts_with('/') {
+        panic!("Paths must start with a `/`");
+    } else if path.contains("//") {
+        panic!(
+            "Invalid route {:?}: paths cannot contain empty segments (`//`)",
+            path
+        );
+    }
+}
+
+/// Check if `path` is `prefix` or is below `prefix`, ie. `/api/users` is below
+/// `/api` but `/apis` is not.
+fn path_has_prefix(path: &str, prefix: &str) -> bool {
+    if prefix == "/" {
+        return true;
+    }
+
+    match path.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/'),
+        None => false,
+    }
+}
+
+/// Get the slice of `original` at the same position as `value` in `lookup`.
+///
+/// `lookup` must have the same length as `original` up to the end of `value`,
+/// which holds for lowercased paths and paths with a trailing slash added or
+/// removed.
+fn original_slice<'a>(value: &'a str, lookup: &str, original: &'a str) -> &'a str {
+    let start = (value.as_ptr() as usize).wrapping_sub(lookup.as_ptr() as usize);
+    match start.checked_add(value.len()) {
+        Some(end) if start <= lookup.len() && end <= original.len() => {
+            original.get(start..end).unwrap_or(value)
         }
+        _ => value,
     }
 }
 
@@ -532,8 +2492,13 @@ where
 #[derive(Clone, Default)]
 struct Node {
     inner: matchit::Router<RouteId>,
+    case_insensitive: bool,
     route_id_to_path: HashMap<RouteId, Arc<str>>,
     path_to_route_id: HashMap<Arc<str>, RouteId>,
+    route_id_to_priority: HashMap<RouteId, i32>,
+    // paths of routes that were dropped because they conflicted with a route
+    // of higher priority
+    unreachable: Vec<Arc<str>>,
 }
 
 impl Node {
@@ -541,18 +2506,101 @@ impl Node {
         &mut self,
         path: impl Into<String>,
         val: RouteId,
+        priority: i32,
     ) -> Result<(), matchit::InsertError> {
         let path = path.into();
 
-        self.inner.insert(&path, val)?;
+        self.inner.insert(&*self.matchit_path(&path), val)?;
 
         let shared_path: Arc<str> = path.into();
         self.route_id_to_path.insert(val, shared_path.clone());
         self.path_to_route_id.insert(shared_path, val);
+        self.route_id_to_priority.insert(val, priority);
 
         Ok(())
     }
 
+    /// Insert a route that conflicts with one or more existing routes.
+    ///
+    /// `matchit` doesn't support removing routes so this rebuilds the tree
+    /// with the new route inserted first and drops existing routes that
+    /// conflict with it and have a lower priority.
+    fn insert_by_priority(
+        &mut self,
+        path: &str,
+        val: RouteId,
+        priority: i32,
+    ) -> PriorityResolution {
+        let mut node = Node {
+            case_insensitive: self.case_insensitive,
+            unreachable: self.unreachable.clone(),
+            ..Node::default()
+        };
+        if node.insert(path, val, priority).is_err() {
+            return PriorityResolution::Tie;
+        }
+
+        let mut existing = self.route_id_to_path.iter().collect::<Vec<_>>();
+        existing.sort_by_key(|(id, _)| **id);
+
+        let mut dropped = Vec::new();
+        for (id, existing_path) in existing {
+            let existing_priority = self.priority(id);
+            match node.insert(&**existing_path, *id, existing_priority) {
+                Ok(()) => {}
+                Err(_) if existing_priority < priority => {
+                    node.unreachable.push(existing_path.clone());
+                    dropped.push(*id);
+                }
+                Err(_) if existing_priority > priority => return PriorityResolution::Rejected,
+                Err(_) => return PriorityResolution::Tie,
+            }
+        }
+
+        *self = node;
+        PriorityResolution::Inserted { dropped }
+    }
+
+    /// Get the path used to look up the route for a request to `path`, which
+    /// is `path` itself unless matching is case insensitive.
+    fn lookup_path<'a>(&self, path: &'a str) -> Cow<'a, str> {
+        if self.case_insensitive {
+            path.to_ascii_lowercase().into()
+        } else {
+            path.into()
+        }
+    }
+
+    /// Get the path `route_path` is inserted into `matchit` as.
+    ///
+    /// If matching is case insensitive only the static segments are lowercased
+    /// so the names of captures, such as `:userId`, are kept.
+    fn matchit_path<'a>(&self, route_path: &'a str) -> Cow<'a, str> {
+        if !self.case_insensitive {
+            return route_path.into();
+        }
+
+        route_path
+            .split('/')
+            .map(|segment| {
+                if segment.starts_with(':') || segment.starts_with('*') {
+                    segment.into()
+                } else {
+                    segment.to_ascii_lowercase()
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("/")
+            .into()
+    }
+
+    fn priority(&self, id: &RouteId) -> i32 {
+        self.route_id_to_priority
+            .get(id)
+            .copied()
+            .unwrap_or(DEFAULT_PRIORITY)
+    }
+
     fn at<'n, 'p>(
         &'n self,
         path: &'p str,
@@ -561,10 +2609,20 @@ impl Node {
     }
 }
 
+enum PriorityResolution {
+    /// The new route was inserted and the listed lower priority routes were dropped.
+    Inserted { dropped: Vec<RouteId> },
+    /// The new route conflicts with a route of higher priority and was not inserted.
+    Rejected,
+    /// The new route conflicts with a route of the same priority.
+    Tie,
+}
+
 impl fmt::Debug for Node {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Node")
             .field("paths", &self.route_id_to_path)
+            .field("case_insensitive", &self.case_insensitive)
             .finish()
     }
 }
@@ -583,6 +2641,29 @@ impl<B, E> Clone for Fallback<B, E> {
     }
 }
 
+/// The service a request is routed to, found with `Router::route_target`.
+enum Target<'a, B> {
+    MethodRouter(&'a MethodRouter<B>),
+    Route(&'a Route<B>),
+    Redirect(Redirect),
+}
+
+impl<'a, B> Target<'a, B>
+where
+    B: HttpBody + Send + 'static,
+{
+    fn call(self, req: Request<B>) -> RouteFuture<B, Infallible> {
+        match self {
+            Target::MethodRouter(inner) => inner.clone().call(req),
+            Target::Route(inner) => inner.clone().call(req),
+            Target::Redirect(redirect) => Route::new(service_fn(move |_: Request<B>| {
+                ready(Ok::<_, Infallible>(redirect.clone().into_response()))
+            }))
+            .call(req),
+        }
+    }
+}
+
 impl<B, E> fmt::Debug for Fallback<B, E> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -604,9 +2685,11 @@ impl<B, E> Fallback<B, E> {
     }
 }
 
+#[allow(clippy::large_enum_variant)]
 enum Endpoint<B> {
     MethodRouter(MethodRouter<B>),
     Route(Route<B>),
+    Predicated(Predicated<B>),
 }
 
 impl<B> Clone for Endpoint<B> {
@@ -614,6 +2697,7 @@ impl<B> Clone for Endpoint<B> {
         match self {
             Endpoint::MethodRouter(inner) => Endpoint::MethodRouter(inner.clone()),
             Endpoint::Route(inner) => Endpoint::Route(inner.clone()),
+            Endpoint::Predicated(inner) => Endpoint::Predicated(inner.clone()),
         }
     }
 }
@@ -623,6 +2707,7 @@ impl<B> fmt::Debug for Endpoint<B> {
         match self {
             Self::MethodRouter(inner) => inner.fmt(f),
             Self::Route(inner) => inner.fmt(f),
+            Self::Predicated(inner) => inner.fmt(f),
         }
     }
 }
@@ -632,4 +2717,10 @@ impl<B> fmt::Debug for Endpoint<B> {
 fn traits() {
     use crate::test_helpers::*;
     assert_send::<Router<()>>();
+
+    // shared between the clones `IntoMakeService` makes for each connection
+    assert_send::<Node>();
+    assert_sync::<Node>();
+    assert_send::<RouteConfig>();
+    assert_sync::<RouteConfig>();
 }
@@ -0,0 +1,50 @@
+use http::{uri::PathAndQuery, Uri};
+
+/// Collapse consecutive slashes in the path of `uri`, such as `//api//users`
+/// into `/api/users`.
+///
+/// Returns `None` if the path doesn't contain consecutive slashes. The query
+/// and percent-encoded characters, including `%2F`, are left untouched.
+pub(super) fn collapse_slashes(uri: &Uri) -> Option<Uri> {
+    let path = uri.path();
+    if !path.contains("//") {
+        return None;
+    }
+
+    let mut collapsed = String::with_capacity(path.len());
+    for c in path.chars() {
+        if c == '/' && collapsed.ends_with('/') {
+            continue;
+        }
+        collapsed.push(c);
+    }
+
+    if let Some(query) = uri.query() {
+        collapsed.push('?');
+        collapsed.push_str(query);
+    }
+
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(PathAndQuery::try_from(collapsed).ok()?);
+    Uri::from_parts(parts).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collapsing() {
+        let collapse =
+            |uri: &'static str| collapse_slashes(&Uri::from_static(uri)).map(|uri| uri.to_string());
+
+        assert_eq!(collapse("/api/users"), None);
+        assert_eq!(collapse("//api//users"), Some("/api/users".to_owned()));
+        assert_eq!(collapse("/a///b/?x=//y"), Some("/a/b/?x=//y".to_owned()));
+        assert_eq!(collapse("/a//%2F%2F"), Some("/a/%2F%2F".to_owned()));
+        assert_eq!(
+            collapse("http://example.com//a"),
+            Some("http://example.com/a".to_owned())
+        );
+    }
+}
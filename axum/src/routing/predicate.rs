@@ -0,0 +1,281 @@
+use super::Route;
+use http::{
+    header::{HeaderName, HeaderValue},
+    request::Parts,
+};
+use std::{fmt, sync::Arc};
+
+/// A condition on a request, used to pick a service with
+/// [`Router::route_predicated`](super::Router::route_predicated).
+///
+/// Predicates receive the request's [`Parts`] so they can be used regardless
+/// of the request body type.
+#[derive(Clone)]
+pub struct Predicate {
+    f: Arc<dyn Fn(&Parts) -> bool + Send + Sync>,
+}
+
+impl Predicate {
+    /// Create a predicate from a function.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum::routing::Predicate;
+    ///
+    /// let is_mobile = Predicate::new(|parts| {
+    ///     parts
+    ///         .headers
+    ///         .get("user-agent")
+    ///         .and_then(|value| value.to_str().ok())
+    ///         .map_or(false, |user_agent| user_agent.contains("Mobile"))
+    /// });
+    /// ```
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&Parts) -> bool + Send + Sync + 'static,
+    {
+        Self { f: Arc::new(f) }
+    }
+
+    /// Create a predicate that matches requests where the header `name` is
+    /// exactly `value`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum::routing::Predicate;
+    /// use http::header::{HeaderName, HeaderValue};
+    ///
+    /// let v2 = Predicate::header(
+    ///     HeaderName::from_static("x-api-version"),
+    ///     HeaderValue::from_static("2"),
+    /// );
+    /// ```
+    pub fn header(name: HeaderName, value: HeaderValue) -> Self {
+        Self::new(move |parts| parts.headers.get(&name) == Some(&value))
+    }
+
+    /// Create a predicate that matches requests for `host`.
+    ///
+    /// The host of the request is resolved like the [`Host`] extractor does
+    /// and compared case insensitively. `host` can be:
+    ///
+    /// - A hostname such as `api.example.com`, which matches requests for
+    ///   that host on any port.
+    /// - A hostname with a port such as `example.com:8080`, which only matches
+    ///   requests for that port.
+    /// - A wildcard such as `*.example.com`, which matches requests for any
+    ///   subdomain of `example.com`, such as `api.example.com` and
+    ///   `a.b.example.com`, but not `example.com` itself. Wildcards can also
+    ///   have a port.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum::routing::Predicate;
+    ///
+    /// let api = Predicate::host("api.example.com");
+    /// let tenants = Predicate::host("*.tenants.example.com");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `host` is empty or contains a `*` that isn't the first label.
+    ///
+    /// [`Host`]: crate::extract::Host
+    pub fn host(host: &str) -> Self {
+        let pattern = HostPattern::new(host);
+        Self::new(move |parts| {
+            crate::extract::resolve_host(&parts.headers, &parts.uri)
+                .map_or(false, |host| pattern.matches(host))
+        })
+    }
+
+    /// Create a predicate that matches every request.
+    ///
+    /// Useful as the last predicate to provide a default service.
+    pub fn always() -> Self {
+        Self::new(|_| true)
+    }
+
+    pub(super) fn matches(&self, parts: &Parts) -> bool {
+        (self.f)(parts)
+    }
+}
+
+impl fmt::Debug for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Predicate").finish()
+    }
+}
+
+/// A pattern for matching hosts, used by [`Predicate::host`].
+struct HostPattern {
+    hostname: HostnamePattern,
+    port: Option<String>,
+}
+
+enum HostnamePattern {
+    Exact(String),
+    // `.example.com` for `*.example.com`
+    Subdomain(String),
+}
+
+impl HostPattern {
+    fn new(pattern: &str) -> Self {
+        let (hostname, port) = split_port(pattern);
+        if hostname.is_empty() {
+            panic!("Invalid host pattern: `{}` has no host", pattern);
+        }
+
+        let hostname = match hostname.strip_prefix('*') {
+            Some(suffix)
+                if suffix.len() > 1 && suffix.starts_with('.') && !suffix.contains('*') =>
+            {
+                HostnamePattern::Subdomain(suffix.to_owned())
+            }
+            None if !hostname.contains('*') => HostnamePattern::Exact(hostname.to_owned()),
+            _ => panic!(
+                "Invalid host pattern: `{}`. Wildcards are only supported as the first label, \
+                 such as `*.example.com`",
+                pattern
+            ),
+        };
+
+        Self {
+            hostname,
+            port: port.map(str::to_owned),
+        }
+    }
+
+    fn matches(&self, host: &str) -> bool {
+        let (hostname, port) = split_port(host);
+
+        if self.port.is_some() && self.port.as_deref() != port {
+            return false;
+        }
+
+        match &self.hostname {
+            HostnamePattern::Exact(pattern) => hostname.eq_ignore_ascii_case(pattern),
+            HostnamePattern::Subdomain(suffix) => {
+                let split = hostname.len().saturating_sub(suffix.len());
+                split > 0
+                    && hostname.is_char_boundary(split)
+                    && hostname[split..].eq_ignore_ascii_case(suffix)
+            }
+        }
+    }
+}
+
+/// Split `host` into the hostname and port, if it has one.
+///
+/// IPv6 addresses, such as `[::1]:8080`, are supported.
+fn split_port(host: &str) -> (&str, Option<&str>) {
+    let hostname_end = if host.starts_with('[') {
+        host.find(']').map_or(host.len(), |idx| idx + 1)
+    } else {
+        host.find(':').unwrap_or(host.len())
+    };
+
+    let (hostname, rest) = host.split_at(hostname_end);
+    (hostname, rest.strip_prefix(':'))
+}
+
+/// The services of a route added with
+/// [`Router::route_predicated`](super::Router::route_predicated), each with
+/// the predicate that selects it.
+pub(super) struct Predicated<B> {
+    routes: Vec<(Predicate, Route<B>)>,
+}
+
+impl<B> Predicated<B> {
+    pub(super) fn new(routes: Vec<(Predicate, Route<B>)>) -> Self {
+        Self { routes }
+    }
+
+    pub(super) fn push(&mut self, predicate: Predicate, route: Route<B>) {
+        self.routes.push((predicate, route));
+    }
+
+    pub(super) fn routes(&self) -> impl Iterator<Item = &Route<B>> {
+        self.routes.iter().map(|(_, route)| route)
+    }
+
+    /// The service of the first predicate that matches the request.
+    pub(super) fn select(&self, parts: &Parts) -> Option<&Route<B>> {
+        self.routes
+            .iter()
+            .find(|(predicate, _)| predicate.matches(parts))
+            .map(|(_, route)| route)
+    }
+
+    pub(super) fn map<F, B2>(self, mut f: F) -> Predicated<B2>
+    where
+        F: FnMut(Route<B>) -> Route<B2>,
+    {
+        Predicated {
+            routes: self
+                .routes
+                .into_iter()
+                .map(|(predicate, route)| (predicate, f(route)))
+                .collect(),
+        }
+    }
+}
+
+impl<B> Clone for Predicated<B> {
+    fn clone(&self) -> Self {
+        Self {
+            routes: self.routes.clone(),
+        }
+    }
+}
+
+impl<B> fmt::Debug for Predicated<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Predicated")
+            .field("routes", &self.routes)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_patterns() {
+        let matches = |pattern, host| HostPattern::new(pattern).matches(host);
+
+        assert!(matches("example.com", "example.com"));
+        assert!(matches("example.com", "Example.COM:8080"));
+        assert!(!matches("example.com", "www.example.com"));
+
+        assert!(matches("example.com:8080", "example.com:8080"));
+        assert!(!matches("example.com:8080", "example.com"));
+        assert!(!matches("example.com:8080", "example.com:80"));
+
+        assert!(matches("*.example.com", "api.example.com"));
+        assert!(matches("*.example.com", "a.b.example.com:443"));
+        assert!(!matches("*.example.com", "example.com"));
+        assert!(!matches("*.example.com", ".example.com"));
+        assert!(!matches("*.example.com", "notexample.com"));
+        assert!(!matches("*.example.com:8080", "api.example.com"));
+
+        assert!(matches("[::1]:3000", "[::1]:3000"));
+        assert!(matches("[::1]", "[::1]:3000"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Wildcards are only supported as the first label")]
+    fn wildcard_not_first_label() {
+        HostPattern::new("api.*.example.com");
+    }
+
+    #[test]
+    #[should_panic(expected = "has no host")]
+    fn empty_host() {
+        HostPattern::new(":8080");
+    }
+}
@@ -26,10 +26,17 @@ use tower_service::Service;
 ///
 /// You normally shouldn't need to care about this type. It's used in
 /// [`Router::layer`](super::Router::layer).
+///
+/// A `Route` can be created ahead of time with [`Route::new`] and passed to
+/// [`Router::route`](super::Router::route) or
+/// [`Router::fallback`](super::Router::fallback), which register it as is
+/// instead of boxing it again. That's useful for tooling that moves routes
+/// between routers.
 pub struct Route<B = Body, E = Infallible>(BoxCloneService<Request<B>, Response, E>);
 
 impl<B, E> Route<B, E> {
-    pub(super) fn new<T>(svc: T) -> Self
+    /// Create a new `Route` by boxing a service.
+    pub fn new<T>(svc: T) -> Self
     where
         T: Service<Request<B>, Error = E> + Clone + Send + 'static,
         T::Response: IntoResponse + 'static,
@@ -188,7 +195,7 @@ fn set_allow_header(headers: &mut HeaderMap, allow_header: &mut Option<Bytes>) {
     }
 }
 
-fn set_content_length(size_hint: http_body::SizeHint, headers: &mut HeaderMap) {
+pub(super) fn set_content_length(size_hint: http_body::SizeHint, headers: &mut HeaderMap) {
     if headers.contains_key(CONTENT_LENGTH) {
         return;
     }
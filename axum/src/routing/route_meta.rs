@@ -0,0 +1,38 @@
+use http::Extensions;
+use std::{fmt, sync::Arc};
+
+/// Metadata attached to a route with
+/// [`Router::route_with_meta`](super::Router::route_with_meta).
+///
+/// The metadata is inserted into the request extensions when a request is
+/// routed to the route, so it's available to middleware added with
+/// [`Router::layer`](super::Router::layer) or
+/// [`Router::route_layer`](super::Router::route_layer), and to handlers with
+/// [`Extension<RouteMeta<M>>`](crate::Extension).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RouteMeta<M>(pub M);
+
+/// Inserts a [`RouteMeta`] into the extensions of requests to a route.
+#[derive(Clone)]
+pub(super) struct InsertRouteMeta(Arc<dyn Fn(&mut Extensions) + Send + Sync>);
+
+impl InsertRouteMeta {
+    pub(super) fn new<M>(meta: M) -> Self
+    where
+        M: Clone + Send + Sync + 'static,
+    {
+        Self(Arc::new(move |extensions| {
+            extensions.insert(RouteMeta(meta.clone()));
+        }))
+    }
+
+    pub(super) fn insert(&self, extensions: &mut Extensions) {
+        (self.0)(extensions)
+    }
+}
+
+impl fmt::Debug for InsertRouteMeta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InsertRouteMeta").finish()
+    }
+}
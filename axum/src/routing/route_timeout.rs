@@ -0,0 +1,88 @@
+use crate::response::{IntoResponse, Response};
+use futures_util::ready;
+use http::{Request, StatusCode};
+use pin_project_lite::pin_project;
+use std::{
+    convert::Infallible,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies [`RouteTimeout`].
+///
+/// Used by [`Router::default_timeout`](super::Router::default_timeout) and
+/// [`Router::route_timeout`](super::Router::route_timeout).
+#[derive(Clone, Copy)]
+pub(super) struct RouteTimeoutLayer {
+    duration: Duration,
+}
+
+impl RouteTimeoutLayer {
+    pub(super) fn new(duration: Duration) -> Self {
+        Self { duration }
+    }
+}
+
+impl<S> Layer<S> for RouteTimeoutLayer {
+    type Service = RouteTimeout<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RouteTimeout {
+            inner,
+            duration: self.duration,
+        }
+    }
+}
+
+/// Service that responds with `504 Gateway Timeout` if the inner service
+/// doesn't respond within `duration`.
+#[derive(Clone)]
+pub(super) struct RouteTimeout<S> {
+    inner: S,
+    duration: Duration,
+}
+
+impl<S, B> Service<Request<B>> for RouteTimeout<S>
+where
+    S: Service<Request<B>, Response = Response, Error = Infallible>,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = ResponseFuture<S::Future>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        ResponseFuture {
+            inner: tokio::time::timeout(self.duration, self.inner.call(req)),
+        }
+    }
+}
+
+pin_project! {
+    pub(super) struct ResponseFuture<F> {
+        #[pin]
+        inner: tokio::time::Timeout<F>,
+    }
+}
+
+impl<F> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<Response, Infallible>>,
+{
+    type Output = Result<Response, Infallible>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match ready!(self.project().inner.poll(cx)) {
+            Ok(result) => Poll::Ready(result),
+            Err(_) => Poll::Ready(Ok(StatusCode::GATEWAY_TIMEOUT.into_response())),
+        }
+    }
+}
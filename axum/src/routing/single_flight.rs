@@ -0,0 +1,189 @@
+use super::Route;
+use crate::{
+    body::{boxed, Bytes, Full},
+    response::{IntoResponse, Response},
+};
+use futures_util::future::{BoxFuture, FutureExt, Map, Shared, WeakShared};
+use http::{HeaderMap, Request, StatusCode, Version};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    fmt,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+use tower_service::Service;
+
+type KeyFn<B> = Arc<dyn Fn(&Request<B>) -> String + Send + Sync>;
+
+type SharedResponse = Shared<BoxFuture<'static, BufferedResponse>>;
+
+type InFlight = Arc<Mutex<InFlightRequests>>;
+
+/// The in-flight requests by key.
+///
+/// Only weak references are kept so a request is dropped once every request
+/// waiting for it has been dropped.
+#[derive(Default)]
+struct InFlightRequests {
+    requests: HashMap<String, (u64, WeakShared<BoxFuture<'static, BufferedResponse>>)>,
+    next_id: u64,
+}
+
+/// Service that shares the response of an in-flight request with concurrent
+/// requests that have the same key.
+///
+/// Used by [`MethodRouter::single_flight`](super::MethodRouter::single_flight).
+pub(super) struct SingleFlight<B> {
+    inner: Route<B>,
+    key_fn: KeyFn<B>,
+    in_flight: InFlight,
+}
+
+impl<B> SingleFlight<B> {
+    pub(super) fn new(inner: Route<B>, key_fn: KeyFn<B>) -> Self {
+        Self {
+            inner,
+            key_fn,
+            in_flight: Default::default(),
+        }
+    }
+}
+
+impl<B> Clone for SingleFlight<B> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            key_fn: Arc::clone(&self.key_fn),
+            in_flight: Arc::clone(&self.in_flight),
+        }
+    }
+}
+
+impl<B> fmt::Debug for SingleFlight<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SingleFlight")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<B> Service<Request<B>> for SingleFlight<B>
+where
+    B: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = Map<SharedResponse, fn(BufferedResponse) -> Result<Response, Infallible>>;
+
+    #[inline]
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let key = (self.key_fn)(&req);
+
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|err| err.into_inner());
+        let existing = in_flight
+            .requests
+            .get(&key)
+            .and_then(|(_, future)| future.upgrade());
+
+        let future = if let Some(future) = existing {
+            // the request is dropped and the response of the in-flight request
+            // is used instead
+            future
+        } else {
+            let id = in_flight.next_id;
+            in_flight.next_id += 1;
+
+            // owned by the shared future so the entry is removed when it
+            // completes, panics or is dropped because every request waiting
+            // for it was cancelled
+            let guard = RemoveOnDrop {
+                in_flight: Arc::clone(&self.in_flight),
+                key: key.clone(),
+                id,
+            };
+            let mut inner = self.inner.clone();
+
+            let future = async move {
+                let _guard = guard;
+                let res = match inner.oneshot_inner(req).await {
+                    Ok(res) => res,
+                    Err(err) => match err {},
+                };
+                BufferedResponse::from_response(res).await
+            }
+            .boxed()
+            .shared();
+
+            let weak = future.downgrade().expect("future hasn't been polled yet");
+            in_flight.requests.insert(key, (id, weak));
+            future
+        };
+
+        future.map(|buffered| Ok(buffered.into_response()))
+    }
+}
+
+/// Removes an in-flight request from the map when dropped.
+struct RemoveOnDrop {
+    in_flight: InFlight,
+    key: String,
+    id: u64,
+}
+
+impl Drop for RemoveOnDrop {
+    fn drop(&mut self) {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|err| err.into_inner());
+        // a newer request with the same key might have replaced this one
+        if matches!(in_flight.requests.get(&self.key), Some((id, _)) if *id == self.id) {
+            in_flight.requests.remove(&self.key);
+        }
+    }
+}
+
+/// A response with its body read into memory, so it can be cloned for each
+/// request sharing it.
+///
+/// Response extensions can't be cloned so they're dropped, also for the
+/// request that ran the handler, so every request gets the same response.
+#[derive(Clone)]
+pub(super) struct BufferedResponse {
+    status: StatusCode,
+    version: Version,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl BufferedResponse {
+    async fn from_response(res: Response) -> Self {
+        let (parts, body) = res.into_parts();
+        match hyper::body::to_bytes(body).await {
+            Ok(body) => Self {
+                status: parts.status,
+                version: parts.version,
+                headers: parts.headers,
+                body,
+            },
+            Err(_) => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                version: parts.version,
+                headers: HeaderMap::new(),
+                body: Bytes::new(),
+            },
+        }
+    }
+}
+
+impl IntoResponse for BufferedResponse {
+    fn into_response(self) -> Response {
+        let mut res = Response::new(boxed(Full::new(self.body)));
+        *res.status_mut() = self.status;
+        *res.version_mut() = self.version;
+        *res.headers_mut() = self.headers;
+        res
+    }
+}
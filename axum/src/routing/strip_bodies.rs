@@ -0,0 +1,89 @@
+use super::route::set_content_length;
+use crate::{
+    body::{boxed, Empty, HttpBody},
+    response::Response,
+};
+use futures_util::ready;
+use http::{
+    header::{CONTENT_LENGTH, TRANSFER_ENCODING},
+    Method, Request, StatusCode,
+};
+use pin_project_lite::pin_project;
+use std::{
+    convert::Infallible,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower_service::Service;
+
+/// Service that removes the body of responses that mustn't have one.
+///
+/// Used by [`Router::strip_disallowed_bodies`](super::Router::strip_disallowed_bodies).
+#[derive(Clone)]
+pub(super) struct StripDisallowedBodies<S> {
+    inner: S,
+}
+
+impl<S> StripDisallowedBodies<S> {
+    pub(super) fn new(inner: S) -> Self {
+        Self { inner }
+    }
+}
+
+impl<S, B> Service<Request<B>> for StripDisallowedBodies<S>
+where
+    S: Service<Request<B>, Response = Response, Error = Infallible>,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = ResponseFuture<S::Future>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        ResponseFuture {
+            is_head: req.method() == Method::HEAD,
+            inner: self.inner.call(req),
+        }
+    }
+}
+
+pin_project! {
+    pub(super) struct ResponseFuture<F> {
+        #[pin]
+        inner: F,
+        is_head: bool,
+    }
+}
+
+impl<F> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<Response, Infallible>>,
+{
+    type Output = Result<Response, Infallible>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let mut res = ready!(this.inner.poll(cx))?;
+
+        let status = res.status();
+        if status.is_informational() || status == StatusCode::NO_CONTENT {
+            // these responses can't have a body so they mustn't describe one
+            // either
+            res.headers_mut().remove(CONTENT_LENGTH);
+            res.headers_mut().remove(TRANSFER_ENCODING);
+        } else if *this.is_head {
+            // the `Content-Length` of `HEAD` responses is the length of the
+            // body a `GET` request would have gotten
+            set_content_length(res.body().size_hint(), res.headers_mut());
+        } else if status != StatusCode::NOT_MODIFIED {
+            return Poll::Ready(Ok(res));
+        }
+
+        Poll::Ready(Ok(res.map(|_| boxed(Empty::new()))))
+    }
+}
@@ -1,4 +1,4 @@
-use http::{Request, Uri};
+use http::{Extensions, Request, Uri};
 use std::{
     sync::Arc,
     task::{Context, Poll},
@@ -34,14 +34,33 @@ where
     }
 
     fn call(&mut self, mut req: Request<B>) -> Self::Future {
-        if let Some(new_uri) = strip_prefix(req.uri(), &self.prefix) {
+        let case_insensitive = req.extensions().get::<CaseInsensitive>().is_some();
+        if let Some(new_uri) = strip_prefix(req.uri(), &self.prefix, case_insensitive) {
             *req.uri_mut() = new_uri;
         }
         self.inner.call(req)
     }
 }
 
-fn strip_prefix(uri: &Uri, prefix: &str) -> Option<Uri> {
+/// Marker for requests routed by a router with
+/// [`Router::case_insensitive`](super::Router::case_insensitive) enabled, so
+/// the prefix of nested services is stripped regardless of case.
+#[derive(Clone, Copy)]
+pub(super) struct CaseInsensitive;
+
+impl CaseInsensitive {
+    /// Mark or unmark a request, depending on the router that is about to call
+    /// the matched service.
+    pub(super) fn set(extensions: &mut Extensions, enabled: bool) {
+        if enabled {
+            extensions.insert(Self);
+        } else {
+            extensions.remove::<Self>();
+        }
+    }
+}
+
+fn strip_prefix(uri: &Uri, prefix: &str, case_insensitive: bool) -> Option<Uri> {
     let path_and_query = uri.path_and_query()?;
 
     // Check whether the prefix matches the path and if so how long the matching prefix is.
@@ -63,7 +82,13 @@ fn strip_prefix(uri: &Uri, prefix: &str) -> Option<Uri> {
 
         match item {
             Item::Both(path_segment, prefix_segment) => {
-                if prefix_segment.starts_with(':') || path_segment == prefix_segment {
+                let segment_matches = if case_insensitive {
+                    path_segment.eq_ignore_ascii_case(prefix_segment)
+                } else {
+                    path_segment == prefix_segment
+                };
+
+                if prefix_segment.starts_with(':') || segment_matches {
                     // the prefix segment is either a param, which matches anything, or
                     // it actually matches the path segment
                     *matching_prefix_length.as_mut().unwrap() += path_segment.len();
@@ -172,7 +197,7 @@ mod tests {
             #[test]
             fn $name() {
                 let uri = $uri.parse().unwrap();
-                let new_uri = strip_prefix(&uri, $prefix).map(|uri| uri.to_string());
+                let new_uri = strip_prefix(&uri, $prefix, false).map(|uri| uri.to_string());
                 assert_eq!(new_uri.as_deref(), $expected);
             }
         };
@@ -357,10 +382,24 @@ mod tests {
         expected = Some("/a"),
     );
 
+    #[test]
+    fn case_sensitive_by_default() {
+        let uri = "/API/users".parse().unwrap();
+        assert_eq!(strip_prefix(&uri, "/api", false), None);
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let uri = "/API/users".parse().unwrap();
+        let new_uri = strip_prefix(&uri, "/api", true).map(|uri| uri.to_string());
+        assert_eq!(new_uri.as_deref(), Some("/users"));
+    }
+
     #[quickcheck]
     fn does_not_panic(uri_and_prefix: UriAndPrefix) -> bool {
         let UriAndPrefix { uri, prefix } = uri_and_prefix;
-        strip_prefix(&uri, &prefix);
+        strip_prefix(&uri, &prefix, false);
+        strip_prefix(&uri, &prefix, true);
         true
     }
 
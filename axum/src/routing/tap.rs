@@ -0,0 +1,92 @@
+use crate::response::Response;
+use futures_util::ready;
+use http::Request;
+use pin_project_lite::pin_project;
+use std::{
+    convert::Infallible,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower_service::Service;
+
+/// Service that calls observers with every request and response.
+///
+/// Used by [`Router::tap`](super::Router::tap).
+pub(super) struct Tap<S, Req, Res> {
+    inner: S,
+    on_request: Arc<Req>,
+    on_response: Arc<Res>,
+}
+
+impl<S, Req, Res> Tap<S, Req, Res> {
+    pub(super) fn new(inner: S, on_request: Arc<Req>, on_response: Arc<Res>) -> Self {
+        Self {
+            inner,
+            on_request,
+            on_response,
+        }
+    }
+}
+
+impl<S, Req, Res> Clone for Tap<S, Req, Res>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            on_request: Arc::clone(&self.on_request),
+            on_response: Arc::clone(&self.on_response),
+        }
+    }
+}
+
+impl<S, Req, Res, B> Service<Request<B>> for Tap<S, Req, Res>
+where
+    S: Service<Request<B>, Response = Response, Error = Infallible>,
+    Req: Fn(&Request<B>),
+    Res: Fn(&Response),
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = ResponseFuture<S::Future, Res>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        (self.on_request)(&req);
+
+        ResponseFuture {
+            inner: self.inner.call(req),
+            on_response: Arc::clone(&self.on_response),
+        }
+    }
+}
+
+pin_project! {
+    pub(super) struct ResponseFuture<F, Res> {
+        #[pin]
+        inner: F,
+        on_response: Arc<Res>,
+    }
+}
+
+impl<F, Res> Future for ResponseFuture<F, Res>
+where
+    F: Future<Output = Result<Response, Infallible>>,
+    Res: Fn(&Response),
+{
+    type Output = Result<Response, Infallible>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let res = ready!(this.inner.poll(cx))?;
+        (this.on_response)(&res);
+        Poll::Ready(Ok(res))
+    }
+}
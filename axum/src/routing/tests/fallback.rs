@@ -1,5 +1,6 @@
 use super::*;
-use crate::handler::Handler;
+use crate::{handler::Handler, Extension};
+use std::sync::{Arc, Mutex};
 
 #[tokio::test]
 async fn basic() {
@@ -31,6 +32,24 @@ async fn nest() {
     assert_eq!(res.text().await, "fallback");
 }
 
+#[tokio::test]
+async fn fallback_sees_extensions_added_later() {
+    async fn fallback(Extension(name): Extension<&'static str>) -> &'static str {
+        name
+    }
+
+    let app = Router::new()
+        .route("/foo", get(|| async {}))
+        .fallback(fallback.into_service())
+        .layer(Extension("shared"));
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/does-not-exist").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.text().await, "shared");
+}
+
 #[tokio::test]
 async fn or() {
     let one = Router::new().route("/one", get(|| async {}));
@@ -49,3 +68,129 @@ async fn or() {
     assert_eq!(res.status(), StatusCode::OK);
     assert_eq!(res.text().await, "fallback");
 }
+
+#[tokio::test]
+async fn prefix_fallbacks() {
+    let app = Router::new()
+        .route("/api/users", get(|| async {}))
+        .fallback_for_prefix("/api", (|| async { "api" }).into_service())
+        .fallback_for_prefix("/api/v2/", (|| async { "api v2" }).into_service())
+        .fallback((|| async { "fallback" }).into_service());
+
+    let client = TestClient::new(app);
+
+    assert_eq!(
+        client.get("/api/users").send().await.status(),
+        StatusCode::OK
+    );
+
+    let res = client.get("/api/does-not-exist").send().await;
+    assert_eq!(res.text().await, "api");
+
+    let res = client.get("/api").send().await;
+    assert_eq!(res.text().await, "api");
+
+    let res = client.get("/api/v2/users").send().await;
+    assert_eq!(res.text().await, "api v2");
+
+    let res = client.get("/apis").send().await;
+    assert_eq!(res.text().await, "fallback");
+}
+
+#[tokio::test]
+async fn prefix_fallback_replaces_existing() {
+    let app = Router::new()
+        .fallback_for_prefix("/api", (|| async { "one" }).into_service())
+        .fallback_for_prefix("/api/", (|| async { "two" }).into_service());
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/api/foo").send().await;
+    assert_eq!(res.text().await, "two");
+}
+
+#[tokio::test]
+async fn nested_prefix_fallbacks() {
+    let api = Router::new()
+        .route("/users", get(|| async {}))
+        .fallback_for_prefix(
+            "/",
+            (|uri: Uri| async move { format!("api fallback: {}", uri) }).into_service(),
+        );
+
+    let app = Router::new().nest("/api", api);
+
+    let client = TestClient::new(app);
+
+    assert_eq!(
+        client.get("/api/users").send().await.status(),
+        StatusCode::OK
+    );
+
+    let res = client.get("/api/foo").send().await;
+    assert_eq!(res.text().await, "api fallback: /foo");
+
+    let res = client.get("/foo").send().await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn merging_prefix_fallbacks() {
+    let one = Router::new().fallback_for_prefix("/one", (|| async { "one" }).into_service());
+    let two = Router::new().fallback_for_prefix("/two", (|| async { "two" }).into_service());
+
+    let client = TestClient::new(one.merge(two));
+
+    assert_eq!(client.get("/one/foo").send().await.text().await, "one");
+    assert_eq!(client.get("/two/foo").send().await.text().await, "two");
+}
+
+#[test]
+#[should_panic(
+    expected = "Cannot merge two `Router`s that both have a fallback for the prefix `/api`"
+)]
+fn merging_routers_with_same_prefix_fallback_panics() {
+    let one: Router = Router::new().fallback_for_prefix("/api", (|| async {}).into_service());
+    let two = Router::new().fallback_for_prefix("/api", (|| async {}).into_service());
+    let _ = one.merge(two);
+}
+
+#[test]
+#[should_panic(expected = "Fallback prefixes must start with a `/`")]
+fn prefix_fallback_without_leading_slash_panics() {
+    let _: Router = Router::new().fallback_for_prefix("api", (|| async {}).into_service());
+}
+
+#[tokio::test]
+async fn on_missing_route() {
+    let missing = Arc::new(Mutex::new(Vec::new()));
+
+    let app = Router::new()
+        .route("/foo", get(|| async {}))
+        .on_missing_route({
+            let missing = Arc::clone(&missing);
+            move |parts| missing.lock().unwrap().push(parts.uri.path().to_owned())
+        })
+        .fallback((|| async { "fallback" }).into_service())
+        .layer(MapRequestLayer::new(|req: Request<Body>| req));
+
+    let client = TestClient::new(app);
+
+    assert_eq!(client.get("/foo").send().await.status(), StatusCode::OK);
+    // method mismatches are not missing routes
+    let res = client.post("/foo").send().await;
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+    let res = client.get("/does-not-exist").send().await;
+    assert_eq!(res.text().await, "fallback");
+
+    assert_eq!(*missing.lock().unwrap(), ["/does-not-exist"]);
+}
+
+#[test]
+#[should_panic(expected = "Cannot merge two `Router`s that both have a missing route hook")]
+fn merging_routers_with_missing_route_hooks() {
+    let _: Router = Router::new()
+        .on_missing_route(|_| {})
+        .merge(Router::new().on_missing_route(|_| {}));
+}
@@ -408,3 +408,64 @@ async fn middleware_that_return_early() {
     );
     assert_eq!(client.get("/public").send().await.status(), StatusCode::OK);
 }
+
+#[test]
+fn conflicts_with() {
+    let app: Router = Router::new()
+        .route("/", get(|| async {}))
+        .route("/users", get(|| async {}))
+        .route("/users/:id", get(|| async {}))
+        .route(
+            "/service",
+            service_fn(|_| async { Ok::<_, Infallible>(Response::new(Body::empty())) }),
+        )
+        .nest("/assets", Router::new().route("/*path", get(|| async {})));
+
+    let other = Router::new()
+        .route("/", get(|| async {}))
+        .route("/users", post(|| async {}))
+        .route("/users/:name", get(|| async {}))
+        .route("/service", post(|| async {}))
+        .route("/assets/*rest", get(|| async {}))
+        .route("/other", get(|| async {}));
+
+    assert_eq!(
+        app.conflicts_with(&other),
+        vec!["/", "/assets/*rest", "/service", "/users/:name"],
+    );
+
+    assert!(app.conflicts_with(&Router::new()).is_empty());
+}
+
+#[tokio::test]
+async fn merged_routers_keep_their_own_extensions() {
+    use crate::Extension;
+
+    #[derive(Clone)]
+    struct State(&'static str);
+
+    async fn handler(Extension(State(name)): Extension<State>) -> &'static str {
+        name
+    }
+
+    let one = Router::new()
+        .route("/one", get(handler))
+        .layer(Extension(State("one")));
+    let two = Router::new()
+        .route("/two", get(handler))
+        .layer(Extension(State("two")));
+
+    let app = Router::new()
+        .route("/app", get(handler))
+        .merge(one)
+        .merge(two)
+        .layer(Extension(State("app")));
+
+    let client = TestClient::new(app);
+
+    // the layer closest to the route runs last so its extension wins
+    for (path, expected) in [("/one", "one"), ("/two", "two"), ("/app", "app")] {
+        let res = client.get(path).send().await;
+        assert_eq!(res.text().await, expected);
+    }
+}
@@ -1,14 +1,19 @@
 use crate::{
-    body::{Bytes, Empty},
+    body::{BoxBody, Bytes, Empty},
     error_handling::HandleErrorLayer,
     extract::{self, Path},
     handler::Handler,
     response::IntoResponse,
-    routing::{delete, get, get_service, on, on_service, patch, patch_service, post, MethodFilter},
+    routing::{
+        any, delete, get, get_service, on, on_service, patch, patch_service, post, MethodFilter,
+        TrailingSlashPolicy,
+    },
     test_helpers::*,
     BoxError, Json, Router,
 };
-use http::{header::CONTENT_LENGTH, HeaderMap, Method, Request, Response, StatusCode, Uri};
+use http::{
+    header::CONTENT_LENGTH, HeaderMap, HeaderValue, Method, Request, Response, StatusCode, Uri,
+};
 use hyper::Body;
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -20,7 +25,10 @@ use std::{
     time::Duration,
 };
 use tower::{
-    service_fn, timeout::TimeoutLayer, util::MapResponseLayer, ServiceBuilder, ServiceExt,
+    service_fn,
+    timeout::TimeoutLayer,
+    util::{MapRequestLayer, MapResponseLayer},
+    ServiceBuilder, ServiceExt,
 };
 use tower_http::{auth::RequireAuthorizationLayer, limit::RequestBodyLimitLayer};
 use tower_service::Service;
@@ -171,6 +179,43 @@ async fn routing_between_services() {
     assert_eq!(res.text().await, "handler");
 }
 
+#[tokio::test]
+async fn routing_to_services_with_any_response_body() {
+    use http_body::Full;
+
+    let app = Router::new()
+        .route(
+            "/hyper",
+            service_fn(|_: Request<Body>| async {
+                Ok::<_, Infallible>(Response::new(Body::from("hyper")))
+            }),
+        )
+        .route(
+            "/full",
+            service_fn(|_: Request<Body>| async {
+                Ok::<_, Infallible>(Response::new(Full::from("full")))
+            }),
+        )
+        .nest(
+            "/empty",
+            service_fn(|_: Request<Body>| async {
+                Ok::<_, Infallible>(Response::new(Empty::<Bytes>::new()))
+            }),
+        );
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/hyper").send().await;
+    assert_eq!(res.text().await, "hyper");
+
+    let res = client.get("/full").send().await;
+    assert_eq!(res.text().await, "full");
+
+    let res = client.get("/empty/foo").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.text().await, "");
+}
+
 #[tokio::test]
 async fn middleware_on_single_route() {
     use tower::ServiceBuilder;
@@ -424,6 +469,36 @@ async fn empty_route() {
     TestClient::new(app);
 }
 
+#[tokio::test]
+#[should_panic(expected = "Invalid route \"//\": paths cannot contain empty segments (`//`)")]
+async fn route_with_only_empty_segments() {
+    let app = Router::new().route("//", get(|| async {}));
+    TestClient::new(app);
+}
+
+#[tokio::test]
+#[should_panic(
+    expected = "Invalid route \"/foo//bar\": paths cannot contain empty segments (`//`)"
+)]
+async fn route_with_empty_segment() {
+    let app = Router::new().route("/foo//bar", get(|| async {}));
+    TestClient::new(app);
+}
+
+#[tokio::test]
+async fn route_with_trailing_slash_is_allowed() {
+    let app = Router::new()
+        .route("/foo", get(|| async { "without" }))
+        .route("/foo/", get(|| async { "with" }));
+    let client = TestClient::new(app);
+
+    let res = client.get("/foo").send().await;
+    assert_eq!(res.text().await, "without");
+
+    let res = client.get("/foo/").send().await;
+    assert_eq!(res.text().await, "with");
+}
+
 #[tokio::test]
 async fn middleware_still_run_for_unmatched_requests() {
     #[derive(Clone)]
@@ -741,3 +816,1641 @@ async fn layer_response_into_response() {
     assert_eq!(res.headers()["x-foo"], "bar");
     assert_eq!(res.status(), StatusCode::IM_A_TEAPOT);
 }
+
+#[tokio::test]
+async fn higher_priority_route_replaces_conflicting_route() {
+    let app = Router::new()
+        .route("/api/*rest", get(|| async { "catch-all" }))
+        .route_with_priority("/api/:id", get(|| async { "id" }), 1);
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/api/foo").send().await;
+    assert_eq!(res.text().await, "id");
+
+    let res = client.get("/api/foo/bar").send().await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn lower_priority_route_is_dropped() {
+    let app = Router::new()
+        .route("/api/*rest", get(|| async { "catch-all" }))
+        .route_with_priority("/api/:id", get(|| async { "id" }), -1);
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/api/foo").send().await;
+    assert_eq!(res.text().await, "catch-all");
+}
+
+#[test]
+fn dropped_route_metadata_is_removed() {
+    let app = Router::<Body>::new()
+        .route_named("catch-all", "/api/*rest", get(|| async {}))
+        .route_timeout("/api/*rest", Duration::from_secs(1))
+        .route_with_priority("/api/:id", get(|| async {}), 1);
+
+    assert_eq!(app.url_for("catch-all", [("rest", "foo")]), None);
+    assert!(app.config.timeouts.is_empty());
+
+    // the name can be used again
+    let app = app.route_named("catch-all", "/other/*rest", get(|| async {}));
+    assert_eq!(
+        app.url_for("catch-all", [("rest", "foo")]).as_deref(),
+        Some("/other/foo")
+    );
+}
+
+#[tokio::test]
+async fn priorities_are_kept_when_nesting_and_merging() {
+    let users = Router::new().route_with_priority("/users/:id", get(|| async { "user" }), 1);
+
+    let app = Router::new()
+        .route("/api/*rest", get(|| async { "catch-all" }))
+        .merge(Router::new().nest("/api", users))
+        .route("/other", get(|| async { "other" }));
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/api/users/1").send().await;
+    assert_eq!(res.text().await, "user");
+
+    let res = client.get("/other").send().await;
+    assert_eq!(res.text().await, "other");
+}
+
+#[test]
+fn unreachable_routes() {
+    let app = Router::<Body>::new()
+        .route("/api/*rest", get(|| async {}))
+        .route_with_priority("/api/:id", get(|| async {}), -1)
+        .route("/other", get(|| async {}));
+    assert_eq!(app.unreachable_routes(), ["/api/:id"]);
+
+    let users = Router::new()
+        .route("/users/:id", get(|| async {}))
+        .route_with_priority("/users", get(|| async {}), -1)
+        .route_with_priority("/*rest", get(|| async {}), 1);
+    let app = Router::<Body>::new()
+        .route("/admin", get(|| async {}))
+        .nest("/api", users)
+        .prefix_paths("/v1");
+    assert_eq!(
+        app.unreachable_routes(),
+        ["/v1/api/users", "/v1/api/users/:id"]
+    );
+
+    // `/api/:id` is added again with a higher priority and replaces the
+    // wildcard
+    let app = Router::<Body>::new()
+        .route("/api/*rest", get(|| async {}))
+        .route_with_priority("/api/:id", get(|| async {}), -1)
+        .merge(Router::new().route_with_priority("/api/:id", get(|| async {}), 1));
+    assert_eq!(app.unreachable_routes(), ["/api/*rest"]);
+}
+
+#[tokio::test]
+#[should_panic(
+    expected = "Invalid route: insertion failed due to conflict with previously registered \
+    route: /api/*rest"
+)]
+async fn conflicting_routes_with_same_priority_panics() {
+    Router::<Body>::new()
+        .route_with_priority("/api/*rest", get(|| async {}), 1)
+        .route_with_priority("/api/:id", get(|| async {}), 1);
+}
+
+#[tokio::test]
+async fn route_table() {
+    let app = Router::<Body>::new()
+        .route("/foo", get(|| async {}))
+        .route("/foo", post(|| async {}))
+        .route("/any", any(|| async {}))
+        .route(
+            "/service",
+            service_fn(|_: Request<Body>| async {
+                Ok::<_, Infallible>(Response::new(Body::empty()))
+            }),
+        )
+        .nest("/", Router::new().route("/nested/:id", delete(|| async {})))
+        .nest("/tail/", get(|| async {}));
+
+    assert_eq!(
+        app.route_table(),
+        vec![
+            ("/any".to_owned(), vec![]),
+            ("/foo".to_owned(), vec![Method::GET, Method::POST]),
+            ("/nested/:id".to_owned(), vec![Method::DELETE]),
+            ("/service".to_owned(), vec![]),
+            ("/tail/*".to_owned(), vec![]),
+        ],
+    );
+}
+
+#[tokio::test]
+async fn boxed_router_accepts_any_body() {
+    let echo = Router::<BoxBody>::new()
+        .route("/echo", post(|body: String| async { body }))
+        .fallback((|| async { "fallback" }).into_service());
+
+    let app: Router<Body> = echo.boxed();
+
+    let client = TestClient::new(app);
+
+    let res = client.post("/echo").body("hello").send().await;
+    assert_eq!(res.text().await, "hello");
+
+    let res = client.get("/does-not-exist").send().await;
+    assert_eq!(res.text().await, "fallback");
+}
+
+#[tokio::test]
+async fn method_not_allowed_handler() {
+    async fn method_not_allowed(method: Method) -> (StatusCode, String) {
+        (
+            StatusCode::METHOD_NOT_ALLOWED,
+            format!("{} not allowed", method),
+        )
+    }
+
+    let app = Router::new()
+        .route("/foo", get(|| async {}))
+        .method_not_allowed_handler(method_not_allowed.into_service())
+        .route("/bar", post(|| async {}))
+        .route(
+            "/custom",
+            get(|| async {}).fallback((|| async { "custom" }).into_service()),
+        )
+        .merge(Router::new().route("/baz", get(|| async {})))
+        .nest("/nested", Router::new().route("/", get(|| async {})));
+
+    let client = TestClient::new(app);
+
+    for path in ["/foo", "/baz", "/nested"] {
+        let res = client.post(path).send().await;
+        assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(res.headers()["allow"], "GET,HEAD");
+        assert_eq!(res.text().await, "POST not allowed");
+    }
+
+    let res = client.get("/bar").send().await;
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(res.text().await, "GET not allowed");
+
+    let res = client.post("/custom").send().await;
+    assert_eq!(res.text().await, "custom");
+
+    let res = client.get("/foo").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn method_not_allowed_handler_when_merging() {
+    let one = Router::new().route("/one", get(|| async {}));
+    let two = Router::new()
+        .route("/two", get(|| async {}))
+        .method_not_allowed_handler((|| async { "not allowed" }).into_service());
+
+    let client = TestClient::new(one.merge(two));
+
+    let res = client.post("/one").send().await;
+    assert_eq!(res.text().await, "not allowed");
+
+    let res = client.post("/two").send().await;
+    assert_eq!(res.text().await, "not allowed");
+}
+
+#[tokio::test]
+#[should_panic(expected = "Cannot merge two `Router`s that both have a method not allowed handler")]
+async fn merging_routers_with_method_not_allowed_handlers_panics() {
+    let one = Router::<Body>::new().method_not_allowed_handler((|| async {}).into_service());
+    let two = Router::new().method_not_allowed_handler((|| async {}).into_service());
+    let _ = one.merge(two);
+}
+
+#[tokio::test]
+async fn pre_routing_layer_runs_before_routing() {
+    fn rewrite_to_foo<B>(mut req: Request<B>) -> Request<B> {
+        *req.uri_mut() = Uri::from_static("/foo");
+        req
+    }
+
+    let routes = || {
+        Router::new()
+            .route("/foo", get(|| async { "foo" }))
+            .route("/bar", get(|| async { "bar" }))
+    };
+
+    // regular layers run after routing so rewriting the uri does nothing
+    let client = TestClient::new(routes().layer(MapRequestLayer::new(rewrite_to_foo)));
+    let res = client.get("/bar").send().await;
+    assert_eq!(res.text().await, "bar");
+
+    let client = TestClient::new(
+        routes()
+            .pre_routing_layer(MapRequestLayer::new(rewrite_to_foo))
+            .layer(MapResponseLayer::new(|mut res: Response<BoxBody>| {
+                res.headers_mut()
+                    .insert("x-outer", HeaderValue::from_static("1"));
+                res
+            })),
+    );
+    let res = client.get("/bar").send().await;
+    assert_eq!(res.headers()["x-outer"], "1");
+    assert_eq!(res.text().await, "foo");
+}
+
+#[test]
+#[should_panic(
+    expected = "Cannot add routes or fallbacks to a `Router` after calling `pre_routing_layer`"
+)]
+fn adding_routes_after_pre_routing_layer_panics() {
+    let _: Router = Router::new()
+        .pre_routing_layer(MapRequestLayer::new(|req: Request<Body>| req))
+        .route("/", get(|| async {}));
+}
+
+#[tokio::test]
+async fn prefix_paths() {
+    let app = Router::new()
+        .route("/", get(|| async { "root" }))
+        .route("/users/:id", get(|uri: Uri| async move { uri.to_string() }))
+        .fallback_for_prefix("/users", (|| async { "users fallback" }).into_service())
+        .prefix_paths("/v1/");
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/v1").send().await;
+    assert_eq!(res.text().await, "root");
+
+    let res = client.get("/v1/users/1").send().await;
+    assert_eq!(res.text().await, "/v1/users/1");
+
+    let res = client.get("/v1/users/1/foo").send().await;
+    assert_eq!(res.text().await, "users fallback");
+
+    let res = client.get("/users/1").send().await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+#[should_panic(expected = "Path prefixes must start with a `/`")]
+fn prefix_paths_without_leading_slash_panics() {
+    let _: Router = Router::new().prefix_paths("v1");
+}
+
+#[cfg(feature = "catch-panic")]
+#[tokio::test]
+async fn catch_panic() {
+    async fn panics() {
+        panic!("oh no")
+    }
+
+    let app = Router::new()
+        .route("/panic", get(panics))
+        .route("/ok", get(|| async { "ok" }))
+        .catch_panic();
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/panic").send().await;
+    assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+    let res = client.get("/ok").send().await;
+    assert_eq!(res.text().await, "ok");
+}
+
+#[tokio::test]
+async fn trailing_slash_strict() {
+    let app = Router::new()
+        .route("/foo", get(|| async {}))
+        .trailing_slash(TrailingSlashPolicy::Strict);
+
+    let client = TestClient::new(app);
+
+    assert_eq!(client.get("/foo").send().await.status(), StatusCode::OK);
+    assert_eq!(
+        client.get("/foo/").send().await.status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn trailing_slash_redirect() {
+    let app = Router::new()
+        .trailing_slash(TrailingSlashPolicy::Redirect)
+        .route("/foo", get(|| async {}))
+        .route("/bar/", get(|| async {}));
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/foo/?a=a").send().await;
+    assert_eq!(res.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(res.headers()["location"], "/foo?a=a");
+
+    let res = client.get("/bar").send().await;
+    assert_eq!(res.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(res.headers()["location"], "/bar/");
+
+    assert_eq!(client.get("/foo").send().await.status(), StatusCode::OK);
+    assert_eq!(
+        client.get("/baz/").send().await.status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn trailing_slash_ignore() {
+    let app = Router::new()
+        .route(
+            "/users/:id",
+            get(|Path(id): Path<u32>| async move { id.to_string() }),
+        )
+        .route("/foo", get(|| async { "foo" }))
+        .route("/foo/", get(|| async { "foo/" }))
+        .trailing_slash(TrailingSlashPolicy::Ignore);
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/users/1/").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.text().await, "1");
+
+    // exact matches take precedence
+    assert_eq!(client.get("/foo").send().await.text().await, "foo");
+    assert_eq!(client.get("/foo/").send().await.text().await, "foo/");
+}
+
+#[tokio::test]
+async fn case_insensitive() {
+    let app = Router::new()
+        .route(
+            "/Users/:userName",
+            get(|Path(name): Path<String>| async move { name }),
+        )
+        .route("/caf%C3%A9", get(|| async { "café" }))
+        .fallback_for_prefix("/Admin", (|| async { "admin fallback" }).into_service())
+        .case_insensitive(true)
+        .route(
+            "/static/*path",
+            get(|uri: Uri| async move { uri.to_string() }),
+        )
+        .fallback_for_prefix("/API", (|| async { "api fallback" }).into_service());
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/users/Alice").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.text().await, "Alice");
+
+    let res = client.get("/USERS/Bob").send().await;
+    assert_eq!(res.text().await, "Bob");
+
+    let res = client.get("/CAF%c3%a9").send().await;
+    assert_eq!(res.text().await, "café");
+
+    let res = client.get("/Static/Foo/Bar.TXT").send().await;
+    assert_eq!(res.text().await, "/Static/Foo/Bar.TXT");
+
+    let res = client.get("/api/unknown").send().await;
+    assert_eq!(res.text().await, "api fallback");
+
+    let res = client.get("/ADMIN/unknown").send().await;
+    assert_eq!(res.text().await, "admin fallback");
+}
+
+#[tokio::test]
+async fn case_sensitive_by_default() {
+    let app = Router::new().route("/users", get(|| async {}));
+
+    let client = TestClient::new(app);
+
+    assert_eq!(client.get("/users").send().await.status(), StatusCode::OK);
+    assert_eq!(
+        client.get("/Users").send().await.status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[test]
+#[should_panic(expected = "Invalid route")]
+fn case_insensitive_routes_that_only_differ_by_case() {
+    let _: Router = Router::new()
+        .route("/users", get(|| async {}))
+        .route("/Users", post(|| async {}))
+        .case_insensitive(true);
+}
+
+#[tokio::test]
+async fn case_insensitive_nested() {
+    let app = Router::new()
+        .nest(
+            "/api",
+            Router::new().route("/users", get(|uri: Uri| async move { uri.to_string() })),
+        )
+        .nest(
+            "/assets",
+            get_service(service_fn(|req: Request<Body>| async move {
+                Ok::<_, Infallible>(req.uri().to_string())
+            })),
+        )
+        .case_insensitive(true);
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/API/Users").send().await;
+    assert_eq!(res.text().await, "/Users");
+
+    let res = client.get("/Assets/Logo.png").send().await;
+    assert_eq!(res.text().await, "/Logo.png");
+}
+
+#[tokio::test]
+async fn nested_case_sensitive_by_default() {
+    let app = Router::new().nest(
+        "/assets",
+        get_service(service_fn(|req: Request<Body>| async move {
+            Ok::<_, Infallible>(req.uri().to_string())
+        })),
+    );
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/assets/Logo.png").send().await;
+    assert_eq!(res.text().await, "/Logo.png");
+
+    let res = client.get("/Assets/Logo.png").send().await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn call_once() {
+    let app = Router::new().route("/", get(|| async { "Hello, World!" }));
+
+    let res = app
+        .call_once(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+    assert_eq!(&body[..], b"Hello, World!");
+}
+
+#[tokio::test]
+async fn method_shorthands() {
+    let app = Router::new()
+        .get("/users", || async { "list" })
+        .post("/users", || async { "create" })
+        .patch("/users/:id", |Path(id): Path<u32>| async move {
+            format!("update {}", id)
+        });
+
+    let client = TestClient::new(app);
+
+    assert_eq!(client.get("/users").send().await.text().await, "list");
+    assert_eq!(client.post("/users").send().await.text().await, "create");
+    assert_eq!(
+        client.patch("/users/1").send().await.text().await,
+        "update 1"
+    );
+
+    let res = client.put("/users").send().await;
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(res.headers()["allow"], "GET,HEAD,POST");
+}
+
+#[test]
+fn url_for() {
+    let users = Router::new()
+        .route_named("users.show", "/:id", get(|| async {}))
+        .route_named("users.files", "/:id/files/*path", get(|| async {}));
+
+    let app: Router = Router::new()
+        .route_named("root", "/", get(|| async {}))
+        .nest("/users", users)
+        .merge(Router::new().route_named("health", "/health", get(|| async {})))
+        .prefix_paths("/v1");
+
+    assert_eq!(
+        app.url_for("root", [("unused", "")]).as_deref(),
+        Some("/v1")
+    );
+    assert_eq!(
+        app.url_for("users.show", [("id", "a/b?c")]).as_deref(),
+        Some("/v1/users/a%2Fb%3Fc")
+    );
+    assert_eq!(
+        app.url_for("users.files", [("id", "1"), ("path", "/a/b")])
+            .as_deref(),
+        Some("/v1/users/1/files/a/b")
+    );
+    assert_eq!(
+        app.url_for("health", Vec::<(&str, &str)>::new()).as_deref(),
+        Some("/v1/health")
+    );
+    assert_eq!(app.url_for("users.show", [("user", "1")]), None);
+    assert_eq!(app.url_for("unknown", [("id", "1")]), None);
+}
+
+#[test]
+#[should_panic(expected = "Invalid route name: `users` is already used for `/users`")]
+fn duplicate_route_names() {
+    let _: Router = Router::new()
+        .route_named("users", "/users", get(|| async {}))
+        .merge(Router::new().route_named("users", "/people", get(|| async {})));
+}
+
+#[tokio::test(start_paused = true)]
+async fn default_timeout() {
+    async fn slow() -> &'static str {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        "slow"
+    }
+
+    async fn fast() -> &'static str {
+        "fast"
+    }
+
+    let nested = Router::new()
+        .route("/slow", get(slow))
+        .default_timeout(Duration::from_secs(10));
+
+    let app = Router::new()
+        .route("/slow", get(slow))
+        .route("/fast", get(fast))
+        .route("/long-poll", get(slow))
+        .route("/export", get(slow))
+        .nest("/nested", nested)
+        .without_timeout("/long-poll")
+        .route_timeout("/export", Duration::from_secs(10))
+        .default_timeout(Duration::from_secs(1))
+        .route("/added-later", get(slow));
+
+    let status = |path: &'static str| {
+        let app = app.clone();
+        async move {
+            let req = Request::builder().uri(path).body(Body::empty()).unwrap();
+            app.oneshot(req).await.unwrap().status()
+        }
+    };
+
+    assert_eq!(status("/slow").await, StatusCode::GATEWAY_TIMEOUT);
+    assert_eq!(status("/fast").await, StatusCode::OK);
+    assert_eq!(status("/long-poll").await, StatusCode::OK);
+    assert_eq!(status("/export").await, StatusCode::OK);
+    assert_eq!(status("/nested/slow").await, StatusCode::OK);
+    assert_eq!(status("/added-later").await, StatusCode::OK);
+}
+
+#[test]
+#[should_panic(
+    expected = "The route at `/users` already has a timeout. Note that `Router::route_timeout` \
+                and `Router::without_timeout` must be called before `Router::default_timeout`"
+)]
+fn route_timeout_after_default_timeout() {
+    let _: Router = Router::new()
+        .route("/users", get(|| async {}))
+        .default_timeout(Duration::from_secs(1))
+        .route_timeout("/users", Duration::from_secs(10));
+}
+
+#[test]
+#[should_panic(
+    expected = "Cannot set the timeout of `/users` since there is no route at that path"
+)]
+fn route_timeout_without_route() {
+    let _: Router = Router::new().route_timeout("/users", Duration::from_secs(1));
+}
+
+#[test]
+#[should_panic(expected = "Cannot combine `Router`s with different timeouts for `/users`")]
+fn merging_different_route_timeouts() {
+    let _: Router = Router::new()
+        .route("/users", get(|| async {}))
+        .route_timeout("/users", Duration::from_secs(1))
+        .merge(
+            Router::new()
+                .route("/users", post(|| async {}))
+                .without_timeout("/users"),
+        );
+}
+
+#[tokio::test]
+async fn alias_prefix() {
+    async fn matched_path(path: extract::MatchedPath) -> String {
+        path.as_str().to_owned()
+    }
+
+    let api = Router::new()
+        .route_named("users.show", "/users/:id", get(matched_path))
+        .fallback_for_prefix("/users", get(|| async { "users fallback" }))
+        .alias_prefix(&["/api/v1", "/api"]);
+
+    assert_eq!(
+        api.url_for("users.show", [("id", "1")]).as_deref(),
+        Some("/api/v1/users/1")
+    );
+
+    let client = TestClient::new(Router::new().merge(api));
+
+    let res = client.get("/api/v1/users/1").send().await;
+    assert_eq!(res.text().await, "/api/v1/users/:id");
+
+    let res = client.get("/api/users/1").send().await;
+    assert_eq!(res.text().await, "/api/users/:id");
+
+    let res = client.get("/api/users/1/posts").send().await;
+    assert_eq!(res.text().await, "users fallback");
+
+    let res = client.get("/users/1").send().await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn route_predicated() {
+    use crate::routing::Predicate;
+    use http::header::HeaderName;
+
+    let users = Router::new()
+        .route_predicated(
+            "/users/:id",
+            &[
+                (
+                    Predicate::header(
+                        HeaderName::from_static("x-api-version"),
+                        HeaderValue::from_static("2"),
+                    ),
+                    get(|Path(id): Path<u32>| async move { format!("v2 {}", id) }),
+                ),
+                (
+                    Predicate::new(|parts| parts.uri.query() == Some("legacy")),
+                    get(|Path(id): Path<u32>| async move { format!("v1 {}", id) }),
+                ),
+            ],
+        )
+        .layer(MapResponseLayer::new(|mut res: Response<BoxBody>| {
+            res.headers_mut()
+                .insert("x-layered", HeaderValue::from_static("yes"));
+            res
+        }));
+
+    let app = Router::new()
+        .nest("/api", users)
+        .fallback(get(|| async { "fallback" }));
+
+    let client = TestClient::new(app);
+
+    let res = client
+        .get("/api/users/1")
+        .header("x-api-version", "2")
+        .send()
+        .await;
+    assert_eq!(res.headers()["x-layered"], "yes");
+    assert_eq!(res.text().await, "v2 1");
+
+    let res = client.get("/api/users/1?legacy").send().await;
+    assert_eq!(res.text().await, "v1 1");
+
+    let res = client.get("/api/users/1").send().await;
+    assert_eq!(res.text().await, "fallback");
+}
+
+#[cfg(feature = "decompression")]
+#[tokio::test]
+async fn decompress_requests() {
+    use async_compression::tokio::bufread::GzipEncoder;
+    use tokio::io::AsyncReadExt;
+
+    let mut compressed = Vec::new();
+    GzipEncoder::new(&b"Hello, World!"[..])
+        .read_to_end(&mut compressed)
+        .await
+        .unwrap();
+
+    let app = Router::<BoxBody>::new()
+        .route(
+            "/",
+            post(|headers: HeaderMap, body: String| async move {
+                assert!(headers.get("content-encoding").is_none());
+                let has_content_length = headers.contains_key(CONTENT_LENGTH);
+                format!("{} (content-length: {})", body, has_content_length)
+            }),
+        )
+        .decompress_requests();
+
+    let client = TestClient::new(app);
+
+    let res = client
+        .post("/")
+        .header("content-encoding", "gzip")
+        .body(compressed)
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.text().await, "Hello, World! (content-length: false)");
+
+    let res = client.post("/").body("not compressed").send().await;
+    assert_eq!(res.text().await, "not compressed (content-length: true)");
+
+    let res = client
+        .post("/")
+        .header("content-encoding", "identity")
+        .body("identity")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    for encoding in ["zstd", "gzip, br"] {
+        let res = client
+            .post("/")
+            .header("content-encoding", encoding)
+            .body("body")
+            .send()
+            .await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(res.headers()["accept-encoding"], "gzip, deflate, br");
+    }
+}
+
+#[cfg(feature = "decompression")]
+#[tokio::test]
+async fn decompress_requests_with_limit() {
+    use async_compression::tokio::bufread::GzipEncoder;
+    use tokio::io::AsyncReadExt;
+
+    let mut compressed = Vec::new();
+    GzipEncoder::new(&[0; 1024][..])
+        .read_to_end(&mut compressed)
+        .await
+        .unwrap();
+    assert!(compressed.len() < 100);
+
+    let app = Router::<BoxBody>::new()
+        .route(
+            "/",
+            post(|body: Bytes| async move { body.len().to_string() }),
+        )
+        .decompress_requests_with_limit(100);
+    let client = TestClient::new(app);
+
+    let res = client
+        .post("/")
+        .header("content-encoding", "gzip")
+        .body(compressed)
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let res = client.post("/").body(vec![0; 1024]).send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.text().await, "1024");
+}
+
+#[cfg(feature = "compression")]
+#[tokio::test]
+async fn auto_compress() {
+    use crate::routing::CompressionConfig;
+    use http::header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE};
+
+    let large = "a".repeat(2048);
+    let app = Router::new()
+        .route(
+            "/json",
+            get(|| async { Json(json!({ "data": "a".repeat(2048) })) }),
+        )
+        .route("/small", get(|| async { Json(json!({ "data": "a" })) }))
+        .route(
+            "/png",
+            get({
+                let large = large.clone();
+                || async move { ([(CONTENT_TYPE, "image/png")], large) }
+            }),
+        )
+        .route(
+            "/precompressed",
+            get(move || async move {
+                (
+                    [(CONTENT_TYPE, "text/plain"), (CONTENT_ENCODING, "br")],
+                    large,
+                )
+            }),
+        )
+        .auto_compress(CompressionConfig::new().br(false));
+
+    let encoding = |path: &'static str, accept_encoding: &'static str| {
+        let app = app.clone();
+        async move {
+            let req = Request::get(path)
+                .header(ACCEPT_ENCODING, accept_encoding)
+                .body(Body::empty())
+                .unwrap();
+            let res = app.oneshot(req).await.unwrap();
+            res.headers()
+                .get(CONTENT_ENCODING)
+                .map(|value| value.to_str().unwrap().to_owned())
+        }
+    };
+
+    assert_eq!(encoding("/json", "gzip").await.as_deref(), Some("gzip"));
+    assert_eq!(
+        encoding("/json", "br, deflate").await.as_deref(),
+        Some("deflate")
+    );
+    assert_eq!(encoding("/json", "br").await, None);
+    assert_eq!(encoding("/json", "identity").await, None);
+    assert_eq!(encoding("/small", "gzip").await, None);
+    assert_eq!(encoding("/png", "gzip").await, None);
+    assert_eq!(
+        encoding("/precompressed", "gzip").await.as_deref(),
+        Some("br")
+    );
+}
+
+#[tokio::test]
+async fn with_health_checks() {
+    use crate::routing::HealthConfig;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    let ready = Arc::new(AtomicBool::new(false));
+
+    let app = Router::new().with_health_checks(
+        HealthConfig::new()
+            .readiness("ready", {
+                let ready = Arc::clone(&ready);
+                move || {
+                    let ready = ready.load(Ordering::SeqCst);
+                    async move { ready }
+                }
+            })
+            .timeout(Duration::from_millis(50)),
+    );
+    let client = TestClient::new(app);
+
+    let res = client.get("/healthz").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = client.get("/readyz").send().await;
+    assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    ready.store(true, Ordering::SeqCst);
+    let res = client.get("/readyz").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[cfg(feature = "json")]
+#[tokio::test]
+async fn with_health_checks_details() {
+    use crate::routing::HealthConfig;
+
+    let app = Router::new().with_health_checks(
+        HealthConfig::new()
+            .health_path("/live")
+            .liveness("ok", || async { true })
+            .readiness("failing", || async { false })
+            .readiness("hung", futures_util::future::pending::<bool>)
+            .timeout(Duration::from_millis(50))
+            .details(true),
+    );
+    let client = TestClient::new(app);
+
+    let res = client.get("/live").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(
+        res.json::<Value>().await,
+        json!({ "status": "ok", "checks": { "ok": "ok" } }),
+    );
+
+    let res = client.get("/readyz").send().await;
+    assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(
+        res.json::<Value>().await,
+        json!({
+            "status": "unavailable",
+            "checks": { "failing": "failing", "hung": "timeout" },
+        }),
+    );
+}
+
+#[tokio::test]
+async fn route_prebuilt_route() {
+    let route = crate::routing::Route::new(service_fn(|req: Request<Body>| async move {
+        Ok::<_, Infallible>(format!("route: {}", req.uri().path()).into_response())
+    }));
+
+    let app = Router::new()
+        .route("/a", route.clone())
+        .route("/b", route.clone())
+        .fallback(route);
+    let client = TestClient::new(app);
+
+    let res = client.get("/a").send().await;
+    assert_eq!(res.text().await, "route: /a");
+
+    let res = client.post("/b").send().await;
+    assert_eq!(res.text().await, "route: /b");
+
+    let res = client.get("/c").send().await;
+    assert_eq!(res.text().await, "route: /c");
+}
+
+#[tokio::test]
+async fn strip_disallowed_bodies() {
+    let app = Router::new()
+        .route(
+            "/no-content",
+            get(|| async { (StatusCode::NO_CONTENT, "body") }),
+        )
+        .route(
+            "/not-modified",
+            get(|| async { (StatusCode::NOT_MODIFIED, [(CONTENT_LENGTH, "4")], "body") }),
+        )
+        .route(
+            "/service",
+            service_fn(|_: Request<Body>| async {
+                Ok::<_, Infallible>(Response::new(Body::from("body")))
+            }),
+        )
+        .strip_disallowed_bodies();
+
+    let send = |method: Method, uri: &'static str| {
+        let app = app.clone();
+        async move {
+            let req = Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap();
+            let res = app.oneshot(req).await.unwrap();
+            let (parts, body) = res.into_parts();
+            let body = hyper::body::to_bytes(body).await.unwrap();
+            (parts.status, parts.headers, body)
+        }
+    };
+
+    let (status, headers, body) = send(Method::GET, "/no-content").await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(headers.get(CONTENT_LENGTH).is_none());
+    assert!(body.is_empty());
+
+    let (status, headers, body) = send(Method::GET, "/not-modified").await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert_eq!(headers[CONTENT_LENGTH], "4");
+    assert!(body.is_empty());
+
+    let (status, headers, body) = send(Method::HEAD, "/service").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[CONTENT_LENGTH], "4");
+    assert!(body.is_empty());
+
+    let (_, _, body) = send(Method::GET, "/service").await;
+    assert_eq!(body, "body");
+}
+
+#[tokio::test]
+async fn map_html_body() {
+    use crate::{body::StreamBody, response::Html};
+
+    let app = Router::new()
+        .route("/html", get(|| async { Html("<p>hi</p>") }))
+        .route("/text", get(|| async { "<p>hi</p>" }))
+        .route(
+            "/stream",
+            get(|| async {
+                let chunks = futures_util::stream::iter([Ok::<_, Infallible>("<p>hi</p>")]);
+                ([("content-type", "text/html")], StreamBody::new(chunks))
+            }),
+        )
+        .map_html_body(|html| html.replace("hi", "hello"));
+    let client = TestClient::new(app);
+
+    let res = client.get("/html").send().await;
+    assert_eq!(res.headers()[CONTENT_LENGTH], "12");
+    assert_eq!(res.text().await, "<p>hello</p>");
+
+    let res = client.get("/text").send().await;
+    assert_eq!(res.text().await, "<p>hi</p>");
+
+    let res = client.get("/stream").send().await;
+    assert_eq!(res.text().await, "<p>hi</p>");
+}
+
+#[tokio::test]
+async fn tap() {
+    use std::sync::{Arc, Mutex};
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    let app = Router::new()
+        .route("/", get(|| async { "hi" }))
+        .fallback((|| async { StatusCode::NOT_FOUND }).into_service())
+        .tap(
+            {
+                let seen = Arc::clone(&seen);
+                move |req: &Request<Body>| {
+                    seen.lock()
+                        .unwrap()
+                        .push(format!("{} {}", req.method(), req.uri().path()));
+                }
+            },
+            {
+                let seen = Arc::clone(&seen);
+                move |res: &crate::response::Response| {
+                    seen.lock().unwrap().push(res.status().as_str().to_owned());
+                }
+            },
+        );
+    let client = TestClient::new(app);
+
+    let res = client.get("/").send().await;
+    assert_eq!(res.text().await, "hi");
+
+    let res = client.get("/missing").send().await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        ["GET /", "200", "GET /missing", "404"]
+    );
+}
+
+#[tokio::test]
+async fn require_http_versions() {
+    let app = Router::new()
+        .route("/", get(|| async {}))
+        .require_http_versions(&[http::Version::HTTP_11, http::Version::HTTP_2]);
+
+    let status = |version| {
+        let app = app.clone();
+        async move {
+            let req = Request::builder()
+                .version(version)
+                .uri("/")
+                .body(Body::empty())
+                .unwrap();
+            app.oneshot(req).await.unwrap().status()
+        }
+    };
+
+    assert_eq!(status(http::Version::HTTP_11).await, StatusCode::OK);
+    assert_eq!(status(http::Version::HTTP_2).await, StatusCode::OK);
+    assert_eq!(
+        status(http::Version::HTTP_09).await,
+        StatusCode::HTTP_VERSION_NOT_SUPPORTED
+    );
+    assert_eq!(
+        status(http::Version::HTTP_3).await,
+        StatusCode::HTTP_VERSION_NOT_SUPPORTED
+    );
+
+    // reqwest uses HTTP/1.1
+    let client = TestClient::new(app);
+    let res = client.get("/missing").send().await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test(start_paused = true)]
+async fn body_read_timeout() {
+    let app = Router::<BoxBody>::new()
+        .route("/", post(|body: String| async move { body }))
+        .body_read_timeout(Duration::from_secs(1));
+
+    // bodies that make progress are read normally, however long they take in
+    // total
+    let (mut tx, body) = Body::channel();
+    tokio::spawn(async move {
+        for chunk in ["a", "b", "c"] {
+            tokio::time::sleep(Duration::from_millis(800)).await;
+            tx.send_data(Bytes::from(chunk)).await.unwrap();
+        }
+    });
+    let res = app
+        .clone()
+        .oneshot(Request::post("/").body(body).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+    assert_eq!(body, "abc");
+
+    // bodies that stall time out
+    let (mut tx, body) = Body::channel();
+    let sender = tokio::spawn(async move {
+        tx.send_data(Bytes::from("a")).await.unwrap();
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        drop(tx);
+    });
+    let res = app
+        .oneshot(Request::post("/").body(body).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::REQUEST_TIMEOUT);
+    assert_eq!(res.headers()["connection"], "close");
+    sender.await.unwrap();
+}
+
+#[tokio::test]
+async fn serve_with_graceful_shutdown() {
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let (tx, rx) = futures::channel::oneshot::channel::<()>();
+    let app = Router::new().route("/", get(|| async { "Hello, World!" }));
+    let server = tokio::spawn(app.serve(addr, async {
+        rx.await.ok();
+    }));
+
+    let res = loop {
+        match reqwest::get(format!("http://{}", addr)).await {
+            Ok(res) => break res,
+            Err(_) => tokio::task::yield_now().await,
+        }
+    };
+    assert_eq!(res.text().await.unwrap(), "Hello, World!");
+
+    tx.send(()).unwrap();
+    server.await.unwrap().unwrap();
+
+    // binding fails while the address is in use
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let res = Router::new()
+        .serve(listener.local_addr().unwrap(), std::future::pending())
+        .await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn route_with_meta() {
+    use crate::{routing::RouteMeta, Extension};
+
+    #[derive(Clone)]
+    struct RateLimit(u32);
+
+    async fn handler(meta: Option<Extension<RouteMeta<RateLimit>>>) -> String {
+        match meta {
+            Some(Extension(RouteMeta(RateLimit(limit)))) => limit.to_string(),
+            None => "none".to_owned(),
+        }
+    }
+
+    let api = Router::new()
+        .route_with_meta("/users", get(handler), RateLimit(10))
+        .route_with_meta("/users", post(handler), "users");
+
+    let app = Router::new()
+        .route("/", get(handler))
+        .nest("/api", api)
+        .route_layer(MapRequestLayer::new(|req: Request<Body>| {
+            let name = req.extensions().get::<RouteMeta<&str>>().map(|meta| meta.0);
+            assert_eq!(name.is_some(), req.uri().path() == "/api/users");
+            req
+        }));
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/").send().await;
+    assert_eq!(res.text().await, "none");
+
+    let res = client.get("/api/users").send().await;
+    assert_eq!(res.text().await, "10");
+
+    let res = client.post("/api/users").send().await;
+    assert_eq!(res.text().await, "10");
+}
+
+#[tokio::test]
+async fn route_meta_is_kept_when_routes_are_moved() {
+    use crate::{routing::RouteMeta, Extension};
+
+    async fn handler(meta: Option<Extension<RouteMeta<&'static str>>>) -> &'static str {
+        meta.map_or("none", |Extension(RouteMeta(meta))| meta)
+    }
+
+    let users = Router::new()
+        .route_with_meta("/users", get(handler), "users")
+        .alias_prefix(&["/v1", "/v2"]);
+
+    let app = Router::new()
+        .route("/", get(handler))
+        .merge(users)
+        .merge(Router::new().route_with_meta("/teams", get(handler), "teams"));
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/").send().await;
+    assert_eq!(res.text().await, "none");
+
+    let res = client.get("/v1/users").send().await;
+    assert_eq!(res.text().await, "users");
+
+    let res = client.get("/v2/users").send().await;
+    assert_eq!(res.text().await, "users");
+
+    let res = client.get("/teams").send().await;
+    assert_eq!(res.text().await, "teams");
+}
+
+#[tokio::test]
+async fn todo() {
+    let app = Router::new()
+        .todo("/users/:id", MethodFilter::GET | MethodFilter::DELETE)
+        .route("/users/:id", post(|| async {}));
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/users/1").send().await;
+    assert_eq!(res.status(), StatusCode::NOT_IMPLEMENTED);
+
+    let res = client.delete("/users/1").send().await;
+    assert_eq!(res.status(), StatusCode::NOT_IMPLEMENTED);
+
+    let res = client.post("/users/1").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = client.put("/users/1").send().await;
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+#[tokio::test]
+async fn route_methods() {
+    let app = Router::new()
+        .route_methods(
+            "/users/:id",
+            &[Method::PUT, Method::PATCH],
+            |method: Method| async move { method.to_string() },
+        )
+        .route("/users/:id", get(|| async { "get" }));
+
+    let client = TestClient::new(app);
+
+    let res = client.put("/users/1").send().await;
+    assert_eq!(res.text().await, "PUT");
+
+    let res = client.patch("/users/1").send().await;
+    assert_eq!(res.text().await, "PATCH");
+
+    let res = client.get("/users/1").send().await;
+    assert_eq!(res.text().await, "get");
+
+    let res = client.delete("/users/1").send().await;
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+#[tokio::test]
+#[should_panic(expected = "Invalid route: no `MethodFilter` for `CONNECT`")]
+async fn route_methods_unsupported_method() {
+    let _: Router = Router::new().route_methods("/", &[Method::CONNECT], || async {});
+}
+
+#[tokio::test]
+async fn fallback_layer() {
+    let app = Router::new()
+        .route("/foo", get(|| async {}))
+        .merge(Router::new().fallback_for_prefix("/api", (|| async { "api" }).into_service()))
+        .fallback((|| async { "fallback" }).into_service())
+        .fallback_layer(MapResponseLayer::new(|mut res: Response<BoxBody>| {
+            res.headers_mut()
+                .insert("x-fallback", HeaderValue::from_static("true"));
+            res
+        }));
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/foo").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(res.headers().get("x-fallback").is_none());
+
+    let res = client.post("/foo").send().await;
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert!(res.headers().get("x-fallback").is_none());
+
+    let res = client.get("/bar").send().await;
+    assert_eq!(res.headers()["x-fallback"], "true");
+    assert_eq!(res.text().await, "fallback");
+
+    let res = client.get("/api/bar").send().await;
+    assert_eq!(res.headers()["x-fallback"], "true");
+    assert_eq!(res.text().await, "api");
+}
+
+#[cfg(feature = "json")]
+#[tokio::test]
+async fn expose_route_listing() {
+    let app = Router::new()
+        .route("/users", get(|| async {}).post(|| async {}))
+        .route("/users/:id", get(|| async {}))
+        .expose_route_listing("/__routes")
+        .route("/later", get(|| async {}));
+    let client = TestClient::new(app);
+
+    let res = client.get("/__routes").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()["content-type"], "application/json");
+    assert_eq!(
+        res.json::<Value>().await,
+        json!([
+            { "path": "/users", "methods": ["GET", "POST"] },
+            { "path": "/users/:id", "methods": ["GET"] },
+        ]),
+    );
+
+    let res = client.post("/__routes").send().await;
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+#[test]
+fn debug_tree() {
+    let app: Router = Router::new()
+        .route("/users/:id/posts", get(|| async {}))
+        .route("/users/:id", get(|| async {}))
+        .route("/files/*path", get(|| async {}))
+        .route_with_priority("/users/me", get(|| async {}), 1)
+        .route("/", get(|| async {}));
+
+    assert_eq!(
+        app.debug_tree(),
+        "\
+/                 static
+/files/*path      wildcard
+/users/me         static (priority 1)
+/users/:id        param
+/users/:id/posts  param
+",
+    );
+
+    let app = app.case_insensitive(true);
+    assert!(app.debug_tree().starts_with("(case insensitive)\n/ "));
+
+    assert_eq!(Router::<Body>::new().debug_tree(), "");
+}
+
+#[test]
+fn match_path() {
+    use crate::routing::{MatchOutcome, Predicate};
+
+    fn route(path: &str, params: &[(&str, &str)]) -> MatchOutcome {
+        MatchOutcome::Route {
+            path: path.to_owned(),
+            params: params
+                .iter()
+                .map(|(key, value)| ((*key).to_owned(), (*value).to_owned()))
+                .collect(),
+        }
+    }
+
+    let app = Router::<Body>::new()
+        .route("/", any(|| async {}))
+        .route("/Users/:id", get(|| async {}))
+        .nest(
+            "/api",
+            Router::new().route("/files/*path", get(|| async {}).post(|| async {})),
+        )
+        .nest("/static", get_service(service_fn(|_| async { Ok(()) })))
+        .route_predicated(
+            "/beta",
+            &[(
+                Predicate::header(
+                    http::header::HeaderName::from_static("x-beta"),
+                    HeaderValue::from_static("1"),
+                ),
+                get(|| async {}),
+            )],
+        )
+        .route_for_host("admin.example.com", "/admin", get(|| async {}))
+        .case_insensitive(true)
+        .trailing_slash(TrailingSlashPolicy::Redirect);
+
+    assert_eq!(app.match_path(&Method::PUT, "/"), route("/", &[]));
+    assert_eq!(
+        app.match_path(&Method::HEAD, "/users/Alice%20B"),
+        route("/Users/:id", &[("id", "Alice B")])
+    );
+    assert_eq!(
+        app.match_path(&Method::DELETE, "/users/1"),
+        MatchOutcome::MethodNotAllowed {
+            path: "/Users/:id".to_owned()
+        }
+    );
+    assert_eq!(
+        app.match_path(&Method::POST, "/api/files/a/b"),
+        route("/api/files/*path", &[("path", "/a/b")])
+    );
+    assert_eq!(
+        app.match_path(&Method::GET, "/static/style.css"),
+        route("/static", &[])
+    );
+    assert_eq!(
+        app.match_path(&Method::GET, "/users/1/"),
+        MatchOutcome::Redirect {
+            location: "/users/1".to_owned()
+        }
+    );
+    assert_eq!(
+        app.match_path(&Method::GET, "/beta"),
+        MatchOutcome::Fallback
+    );
+    assert_eq!(
+        app.match_path(&Method::GET, "/teams"),
+        MatchOutcome::Fallback
+    );
+    assert_eq!(
+        app.match_path(&Method::GET, "/admin"),
+        MatchOutcome::Fallback
+    );
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        http::header::HOST,
+        HeaderValue::from_static("admin.example.com"),
+    );
+    headers.insert("x-beta", HeaderValue::from_static("1"));
+    assert_eq!(
+        app.match_path_with_headers(&Method::GET, "/admin", &headers),
+        route("/admin", &[])
+    );
+    assert_eq!(
+        app.match_path_with_headers(&Method::GET, "/beta", &headers),
+        route("/beta", &[])
+    );
+}
+
+#[tokio::test]
+async fn made_services_share_routing_data() {
+    let app = Router::new()
+        .route("/", get(|| async { "root" }))
+        .route("/foo", post(|| async { "foo" }));
+
+    let mut make_service = app.into_make_service();
+    let a: Router = make_service.call(()).await.unwrap();
+    let b: Router = make_service.call(()).await.unwrap();
+    assert!(std::sync::Arc::ptr_eq(&a.node, &b.node));
+    assert!(std::sync::Arc::ptr_eq(&a.config, &b.config));
+
+    // changing one of them doesn't change the others
+    let a = a
+        .route("/bar", get(|| async { "bar" }))
+        .route_timeout("/bar", Duration::from_secs(1));
+    assert!(!std::sync::Arc::ptr_eq(&a.node, &b.node));
+    assert!(!std::sync::Arc::ptr_eq(&a.config, &b.config));
+
+    let res = a
+        .oneshot(Request::builder().uri("/bar").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = b
+        .clone()
+        .oneshot(Request::builder().uri("/bar").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let res = b
+        .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn on_route_registered() {
+    use crate::routing::Predicate;
+
+    let registered = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    let users = Router::new().route("/:id", get(|| async {}).delete(|| async {}));
+
+    let _app: Router = Router::new()
+        .on_route_registered({
+            let registered = std::sync::Arc::clone(&registered);
+            move |path, method_router| {
+                registered
+                    .lock()
+                    .unwrap()
+                    .push((path.to_owned(), method_router.methods()))
+            }
+        })
+        .route("/", get(|| async {}))
+        .route("/", post(|| async {}))
+        .route(
+            "/service",
+            service_fn(|_: Request<Body>| async {
+                Ok::<_, Infallible>(Response::new(Body::empty()))
+            }),
+        )
+        .nest("/users", users)
+        .nest(
+            "/assets",
+            service_fn(|_: Request<Body>| async {
+                Ok::<_, Infallible>(Response::new(Body::empty()))
+            }),
+        )
+        .merge(Router::new().route("/merged", patch(|| async {})))
+        .route_predicated(
+            "/predicated",
+            &[(Predicate::host("a.example.com"), get(|| async {}))],
+        )
+        .route_for_host("b.example.com", "/host", get(|| async {}))
+        .route_for_host("c.example.com", "/host", get(|| async {}))
+        .layer(tower_http::trace::TraceLayer::new_for_http())
+        .route("/after-layer", get(|| async {}));
+
+    assert_eq!(
+        *registered.lock().unwrap(),
+        [
+            ("/".to_owned(), vec![Method::GET]),
+            ("/".to_owned(), vec![Method::POST]),
+            ("/service".to_owned(), vec![]),
+            ("/users/:id".to_owned(), vec![Method::GET, Method::DELETE]),
+            ("/assets/*".to_owned(), vec![]),
+            ("/merged".to_owned(), vec![Method::PATCH]),
+            ("/predicated".to_owned(), vec![]),
+            ("/host".to_owned(), vec![]),
+            ("/host".to_owned(), vec![]),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn normalize_path() {
+    let app = Router::new()
+        .route(
+            "/api/users",
+            get(
+                |uri: Uri, extract::OriginalUri(original): extract::OriginalUri| async move {
+                    format!("{} {}", uri, original)
+                },
+            ),
+        )
+        .nest(
+            "/nested",
+            Router::new().route("/:name", get(|Path(name): Path<String>| async { name })),
+        )
+        .normalize_path();
+    let client = TestClient::new(app);
+
+    let res = client.get("//api//users?a=//b").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.text().await, "/api/users?a=//b //api//users?a=//b");
+
+    let res = client.get("/nested///foo").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.text().await, "foo");
+
+    let res = client.get("/api/%2F/users").send().await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    // without `normalize_path` the paths don't match
+    let client = TestClient::new(Router::new().route("/api/users", get(|| async {})));
+    let res = client.get("//api//users").send().await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn route_for_host() {
+    let app = Router::new()
+        .route_for_host("api.example.com", "/", get(|| async { "api" }))
+        .route_for_host("www.example.com:8080", "/", get(|| async { "www" }))
+        .route_for_host("*.tenants.example.com", "/", get(|| async { "tenant" }))
+        .route("/shared", get(|| async { "shared" }));
+    let client = TestClient::new(app);
+
+    let get_host = |host: &'static str, path: &'static str| {
+        let req = client.get(path).header("host", host).send();
+        async move {
+            let res = req.await;
+            (res.status(), res.text().await)
+        }
+    };
+
+    assert_eq!(get_host("api.example.com", "/").await.1, "api");
+    assert_eq!(get_host("API.example.com:3000", "/").await.1, "api");
+    assert_eq!(get_host("www.example.com:8080", "/").await.1, "www");
+    assert_eq!(get_host("acme.tenants.example.com", "/").await.1, "tenant");
+    assert_eq!(
+        get_host("www.example.com", "/").await.0,
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+        get_host("tenants.example.com", "/").await.0,
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(get_host("api.example.com", "/shared").await.1, "shared");
+}
+
+#[test]
+#[should_panic(expected = "conflict with previously registered route")]
+fn route_for_host_conflicts_with_route() {
+    let _: Router = Router::new().route("/", get(|| async {})).route_for_host(
+        "api.example.com",
+        "/",
+        get(|| async {}),
+    );
+}
@@ -455,3 +455,118 @@ nested_route_test!(nest_9, nest = "/a", route = "/a/", expected = "/a/a/");
 nested_route_test!(nest_11, nest = "/a/", route = "/", expected = "/a/");
 nested_route_test!(nest_12, nest = "/a/", route = "/a", expected = "/a/a");
 nested_route_test!(nest_13, nest = "/a/", route = "/a/", expected = "/a/a/");
+
+#[tokio::test]
+async fn nested_router_keeps_its_layers() {
+    fn set_header(
+        name: &'static str,
+    ) -> MapResponseLayer<impl Fn(Response<BoxBody>) -> Response<BoxBody> + Clone> {
+        MapResponseLayer::new(move |mut res: Response<BoxBody>| {
+            res.headers_mut()
+                .insert(name, HeaderValue::from_static("yes"));
+            res
+        })
+    }
+
+    let inner = Router::new()
+        .route("/handler", get(|| async {}))
+        .route(
+            "/service",
+            service_fn(|_: Request<Body>| async {
+                Ok::<_, Infallible>(Response::new(Body::empty()))
+            }),
+        )
+        .fallback_for_prefix("/things", get(|| async {}))
+        .route_layer(set_header("x-route-layer"))
+        .layer(set_header("x-layer"));
+
+    let app = Router::new()
+        .route("/outer", get(|| async {}))
+        .nest("/inner", inner);
+
+    let client = TestClient::new(app);
+
+    for path in ["/inner/handler", "/inner/service"] {
+        let res = client.get(path).send().await;
+        assert_eq!(res.status(), StatusCode::OK, "{}", path);
+        assert_eq!(res.headers()["x-route-layer"], "yes", "{}", path);
+        assert_eq!(res.headers()["x-layer"], "yes", "{}", path);
+    }
+
+    // prefix fallbacks get `layer` but not `route_layer`
+    let res = client.get("/inner/things/1").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(res.headers().get("x-route-layer").is_none());
+    assert_eq!(res.headers()["x-layer"], "yes");
+
+    // the layers don't leak out to the routes of the outer router
+    let res = client.get("/outer").send().await;
+    assert!(res.headers().get("x-route-layer").is_none());
+    assert!(res.headers().get("x-layer").is_none());
+}
+
+#[tokio::test]
+async fn bypass_outer_route_layers() {
+    fn set_header(
+        name: &'static str,
+    ) -> MapResponseLayer<impl Fn(Response<BoxBody>) -> Response<BoxBody> + Clone> {
+        MapResponseLayer::new(move |mut res: Response<BoxBody>| {
+            res.headers_mut()
+                .insert(name, HeaderValue::from_static("yes"));
+            res
+        })
+    }
+
+    let health = Router::new()
+        .route("/health", get(|| async {}))
+        .route_layer(set_header("x-inner"))
+        .bypass_outer_route_layers();
+
+    let app = Router::new()
+        .route("/users", get(|| async {}))
+        .nest("/internal", health)
+        .route_layer(set_header("x-outer-route-layer"))
+        .layer(set_header("x-outer-layer"));
+
+    let app = Router::new()
+        .nest("/api", app)
+        .route_layer(set_header("x-outermost-route-layer"));
+
+    let client = TestClient::new(app);
+
+    let res = client.get("/api/users").send().await;
+    assert_eq!(res.headers()["x-outer-route-layer"], "yes");
+    assert_eq!(res.headers()["x-outer-layer"], "yes");
+    assert_eq!(res.headers()["x-outermost-route-layer"], "yes");
+
+    let res = client.get("/api/internal/health").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()["x-inner"], "yes");
+    assert!(res.headers().get("x-outer-route-layer").is_none());
+    assert_eq!(res.headers()["x-outer-layer"], "yes");
+    assert!(res.headers().get("x-outermost-route-layer").is_none());
+}
+
+#[tokio::test]
+async fn nesting_at_path_with_own_route() {
+    let admin = Router::new().route("/users", get(|| async { "users" }));
+
+    let app = Router::new()
+        .route("/admin", get(|| async { "dashboard" }))
+        .nest("/admin", admin)
+        .nest("/static", get(|uri: Uri| async move { uri.to_string() }))
+        .route("/static", get(|| async { "static index" }));
+    let client = TestClient::new(app);
+
+    let res = client.get("/admin").send().await;
+    assert_eq!(res.text().await, "dashboard");
+
+    let res = client.get("/admin/users").send().await;
+    assert_eq!(res.text().await, "users");
+
+    let res = client.get("/static").send().await;
+    assert_eq!(res.text().await, "static index");
+
+    let res = client.get("/static/app.js").send().await;
+    assert_eq!(res.text().await, "/app.js");
+}
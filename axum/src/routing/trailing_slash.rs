@@ -0,0 +1,37 @@
+/// How a [`Router`](super::Router) handles requests whose path only differs
+/// from a route by a trailing slash.
+///
+/// See [`Router::trailing_slash`](super::Router::trailing_slash) for more
+/// details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrailingSlashPolicy {
+    /// Paths must match routes exactly. A request for `/foo/` is not routed
+    /// to `/foo`, and vice versa, but is sent to the fallback.
+    ///
+    /// This is the default.
+    Strict,
+    /// Respond with `308 Permanent Redirect` to the path of the matching
+    /// route. The query string is preserved.
+    Redirect,
+    /// Route the request as if the path matched the route exactly.
+    Ignore,
+}
+
+impl Default for TrailingSlashPolicy {
+    fn default() -> Self {
+        Self::Strict
+    }
+}
+
+/// Add or remove the trailing slash of `path`.
+///
+/// Returns `None` for `/` since there is no other form of it.
+pub(super) fn toggle_trailing_slash(path: &str) -> Option<String> {
+    if path == "/" {
+        None
+    } else if let Some(path) = path.strip_suffix('/') {
+        Some(path.to_owned())
+    } else {
+        Some(format!("{}/", path))
+    }
+}
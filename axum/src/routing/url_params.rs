@@ -1,6 +1,5 @@
 use crate::util::PercentDecodedStr;
 use http::Extensions;
-use matchit::Params;
 use std::sync::Arc;
 
 pub(crate) enum UrlParams {
@@ -8,7 +7,40 @@ pub(crate) enum UrlParams {
     InvalidUtf8InPathParam { key: Arc<str> },
 }
 
-pub(super) fn insert_url_params(extensions: &mut Extensions, params: Params) {
+/// The URL params exactly as they appear in the request's path, used by
+/// `RawPath`.
+pub(crate) struct RawUrlParams(pub(crate) Vec<(Arc<str>, PercentDecodedStr)>);
+
+/// The name of the wildcard capture, such as `rest` in `/files/*rest`, of the
+/// most recently matched route.
+#[derive(Clone)]
+pub(crate) struct WildcardParam(pub(crate) Arc<str>);
+
+pub(super) fn insert_url_params(
+    extensions: &mut Extensions,
+    params: &[(&str, &str)],
+    route_path: &str,
+) {
+    if let Some(wildcard) = route_path
+        .rsplit('/')
+        .next()
+        .and_then(|segment| segment.strip_prefix('*'))
+    {
+        if wildcard != super::NEST_TAIL_PARAM {
+            extensions.insert(WildcardParam(wildcard.into()));
+        }
+    }
+
+    let raw_params = params
+        .iter()
+        .filter(|(key, _)| !key.starts_with(super::NEST_TAIL_PARAM))
+        .map(|(k, v)| (Arc::from(*k), PercentDecodedStr::undecoded(v)));
+    if let Some(RawUrlParams(current)) = extensions.get_mut() {
+        current.extend(raw_params);
+    } else {
+        extensions.insert(RawUrlParams(raw_params.collect()));
+    }
+
     let current_params = extensions.get_mut();
 
     if let Some(UrlParams::InvalidUtf8InPathParam { .. }) = current_params {
@@ -21,9 +53,9 @@ pub(super) fn insert_url_params(extensions: &mut Extensions, params: Params) {
         .filter(|(key, _)| !key.starts_with(super::NEST_TAIL_PARAM))
         .map(|(k, v)| {
             if let Some(decoded) = PercentDecodedStr::new(v) {
-                Ok((Arc::from(k), decoded))
+                Ok((Arc::from(*k), decoded))
             } else {
-                Err(Arc::from(k))
+                Err(Arc::from(*k))
             }
         })
         .collect::<Result<Vec<_>, _>>();
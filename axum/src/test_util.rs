@@ -0,0 +1,277 @@
+//! Utilities for testing applications built with axum.
+//!
+//! Requires the `test-util` feature.
+
+use crate::{
+    body::HttpBody,
+    extract::MatchedPath,
+    response::{IntoResponse, Response},
+    routing::Router,
+};
+use http::{Request, StatusCode};
+use std::{
+    convert::Infallible,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+use tokio::sync::{mpsc, oneshot};
+use tower::util::MapRequestLayer;
+use tower_service::Service;
+
+/// Records the [`MatchedPath`] of every request handled by a [`Router`].
+///
+/// This makes it possible to test which route a request was routed to,
+/// without having the handlers report it.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{
+///     Router,
+///     routing::get,
+///     http::Request,
+///     body::Body,
+///     test_util::MatchedPathRecorder,
+/// };
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let recorder = MatchedPathRecorder::new();
+///
+/// let app = recorder.record(
+///     Router::new()
+///         .route("/users", get(|| async {}))
+///         .route("/users/:id", get(|| async {})),
+/// );
+///
+/// let req = Request::builder().uri("/users/42").body(Body::empty()).unwrap();
+/// app.clone().call_once(req).await.unwrap();
+///
+/// let req = Request::builder().uri("/teams").body(Body::empty()).unwrap();
+/// app.call_once(req).await.unwrap();
+///
+/// assert_eq!(
+///     recorder.matched_paths(),
+///     [Some("/users/:id".to_owned()), None],
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MatchedPathRecorder {
+    matched_paths: Arc<Mutex<Vec<Option<String>>>>,
+}
+
+impl MatchedPathRecorder {
+    /// Create a new `MatchedPathRecorder` that hasn't recorded anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the matched path of requests handled by `router`.
+    ///
+    /// Requests that are sent to a fallback are recorded as `None`. This uses
+    /// [`Router::layer`] so routes and fallbacks added to the returned router
+    /// aren't recorded.
+    pub fn record<B>(&self, router: Router<B>) -> Router<B>
+    where
+        B: HttpBody + Send + 'static,
+    {
+        let matched_paths = Arc::clone(&self.matched_paths);
+        router.layer(MapRequestLayer::new(move |req: Request<B>| {
+            let matched_path = req
+                .extensions()
+                .get::<MatchedPath>()
+                .map(|path| path.as_str().to_owned());
+            matched_paths.lock().unwrap().push(matched_path);
+            req
+        }))
+    }
+
+    /// Get the recorded matched paths, in the order the requests were
+    /// received.
+    pub fn matched_paths(&self) -> Vec<Option<String>> {
+        self.matched_paths.lock().unwrap().clone()
+    }
+
+    /// Get the matched path of the most recent request.
+    ///
+    /// Returns `None` if no requests were recorded or the most recent request
+    /// was sent to a fallback.
+    pub fn last(&self) -> Option<String> {
+        self.matched_paths.lock().unwrap().last().cloned().flatten()
+    }
+
+    /// Forget the recorded matched paths.
+    pub fn clear(&self) {
+        self.matched_paths.lock().unwrap().clear();
+    }
+}
+
+/// Create a [`Router`] with a route at `path` whose requests are sent over a
+/// channel.
+///
+/// Every request to the route is sent to the returned receiver together with
+/// a [`oneshot::Sender`] for its response. This makes it possible to script
+/// the responses of a backend from a test, or to connect parts of an
+/// application in the same process, without implementing a service.
+///
+/// If the receiver has been dropped, or the response sender is dropped without
+/// sending a response, the request gets a `503 Service Unavailable` response.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{
+///     body::Body,
+///     http::{Request, StatusCode},
+///     response::IntoResponse,
+///     test_util::channel_route,
+/// };
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let (app, mut requests) = channel_route::<Body>("/users/:id");
+///
+/// tokio::spawn(async move {
+///     while let Some((req, respond)) = requests.recv().await {
+///         let res = format!("you requested {}", req.uri()).into_response();
+///         let _ = respond.send(res);
+///     }
+/// });
+///
+/// let req = Request::builder().uri("/users/1").body(Body::empty()).unwrap();
+/// let res = app.call_once(req).await.unwrap();
+/// assert_eq!(res.status(), StatusCode::OK);
+/// # }
+/// ```
+pub fn channel_route<B>(
+    path: &str,
+) -> (
+    Router<B>,
+    mpsc::UnboundedReceiver<(Request<B>, oneshot::Sender<Response>)>,
+)
+where
+    B: HttpBody + Send + 'static,
+{
+    let (tx, rx) = mpsc::unbounded_channel();
+    let router = Router::new().route(path, ChannelService { tx });
+    (router, rx)
+}
+
+struct ChannelService<B> {
+    tx: mpsc::UnboundedSender<(Request<B>, oneshot::Sender<Response>)>,
+}
+
+impl<B> Clone for ChannelService<B> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+impl<B> Service<Request<B>> for ChannelService<B>
+where
+    B: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, Infallible>> + Send>>;
+
+    #[inline]
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let (res_tx, res_rx) = oneshot::channel();
+        let sent = self.tx.send((req, res_tx)).is_ok();
+
+        Box::pin(async move {
+            if !sent {
+                return Ok(StatusCode::SERVICE_UNAVAILABLE.into_response());
+            }
+
+            Ok(res_rx
+                .await
+                .unwrap_or_else(|_| StatusCode::SERVICE_UNAVAILABLE.into_response()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{routing::get, test_helpers::*};
+
+    #[tokio::test]
+    async fn records_matched_paths() {
+        let recorder = MatchedPathRecorder::new();
+
+        let app = recorder.record(
+            Router::new()
+                .route("/users/:id", get(|| async {}))
+                .nest("/api", Router::new().route("/teams/:id", get(|| async {}))),
+        );
+
+        let client = TestClient::new(app);
+
+        client.get("/users/42").send().await;
+        assert_eq!(recorder.last().as_deref(), Some("/users/:id"));
+
+        client.get("/api/teams/1").send().await;
+        client.get("/not-found").send().await;
+        assert_eq!(
+            recorder.matched_paths(),
+            [
+                Some("/users/:id".to_owned()),
+                Some("/api/teams/:id".to_owned()),
+                None
+            ]
+        );
+
+        recorder.clear();
+        assert!(recorder.matched_paths().is_empty());
+    }
+
+    #[tokio::test]
+    async fn channel_route() {
+        let (app, mut requests) = super::channel_route("/users/:id");
+
+        tokio::spawn(async move {
+            while let Some((req, respond)) = requests.recv().await {
+                if req.uri().path() == "/users/0" {
+                    // dropping the sender without responding
+                    continue;
+                }
+                let res = format!("user {}", req.uri().path()).into_response();
+                respond.send(res).unwrap();
+            }
+        });
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/users/1").send().await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "user /users/1");
+
+        let res = client.get("/users/0").send().await;
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let res = client.get("/teams").send().await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn channel_route_receiver_dropped() {
+        let (app, requests) = super::channel_route("/");
+        drop(requests);
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/").send().await;
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
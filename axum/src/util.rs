@@ -15,6 +15,14 @@ impl PercentDecodedStr {
             .map(|decoded| Self(decoded.as_ref().into()))
     }
 
+    /// Wrap `s` as is, without percent-decoding it.
+    pub(crate) fn undecoded<S>(s: S) -> Self
+    where
+        S: AsRef<str>,
+    {
+        Self(s.as_ref().into())
+    }
+
     pub(crate) fn as_str(&self) -> &str {
         &*self.0
     }